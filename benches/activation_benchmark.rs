@@ -1,15 +1,17 @@
+mod common;
+
+use common::fixed_uniform;
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use ndarray::{Array2, Array};
-use ndarray_rand::RandomExt;
-use ndarray_rand::rand_distr::Uniform;
 use rust_dl_from_scratch::chapter02::matrix::Matrix;
 use rust_dl_from_scratch::chapter02::activation::{sigmoid, softmax, sigmoid_matrix, softmax_matrix};
 
+const SEED: u64 = 42;
+
 fn benchmark_sigmoid_small(c: &mut Criterion) {
     let mut group = c.benchmark_group("Sigmoid Small (100x10)");
     
     // 生成测试数据
-    let data_ndarray = Array::random((100, 10), Uniform::new(-5.0, 5.0));
+    let data_ndarray = fixed_uniform(100, 10, SEED, -5.0, 5.0);
     let data_matrix = Matrix::from_vec(
         data_ndarray.outer_iter()
             .map(|row| row.to_vec())
@@ -30,7 +32,7 @@ fn benchmark_sigmoid_small(c: &mut Criterion) {
 fn benchmark_sigmoid_large(c: &mut Criterion) {
     let mut group = c.benchmark_group("Sigmoid Large (1000x100)");
     
-    let data_ndarray = Array::random((1000, 100), Uniform::new(-5.0, 5.0));
+    let data_ndarray = fixed_uniform(1000, 100, SEED, -5.0, 5.0);
     let data_matrix = Matrix::from_vec(
         data_ndarray.outer_iter()
             .map(|row| row.to_vec())
@@ -51,7 +53,7 @@ fn benchmark_sigmoid_large(c: &mut Criterion) {
 fn benchmark_softmax_small(c: &mut Criterion) {
     let mut group = c.benchmark_group("Softmax Small (100x10)");
     
-    let data_ndarray = Array::random((100, 10), Uniform::new(-5.0, 5.0));
+    let data_ndarray = fixed_uniform(100, 10, SEED, -5.0, 5.0);
     let data_matrix = Matrix::from_vec(
         data_ndarray.outer_iter()
             .map(|row| row.to_vec())
@@ -72,7 +74,7 @@ fn benchmark_softmax_small(c: &mut Criterion) {
 fn benchmark_softmax_large(c: &mut Criterion) {
     let mut group = c.benchmark_group("Softmax Large (1000x100)");
     
-    let data_ndarray = Array::random((1000, 100), Uniform::new(-5.0, 5.0));
+    let data_ndarray = fixed_uniform(1000, 100, SEED, -5.0, 5.0);
     let data_matrix = Matrix::from_vec(
         data_ndarray.outer_iter()
             .map(|row| row.to_vec())
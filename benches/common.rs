@@ -0,0 +1,49 @@
+// benches/common.rs
+// 基准测试共用的固定数据：使用带种子的随机数生成器，保证每次运行的输入完全相同，
+// 这样不同运行之间的结果才可以比较
+#![allow(dead_code)]
+
+use ndarray::Array2;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_dl_from_scratch::chapter02::init::NormalInit;
+use rust_dl_from_scratch::chapter02::matrix::Matrix;
+use rust_dl_from_scratch::chapter02::network::{SimpleNet, SimpleNetMatrix};
+
+/// [0, 1) 上均匀分布的固定数据集
+pub fn fixed_dataset(rows: usize, cols: usize, seed: u64) -> Array2<f64> {
+    fixed_uniform(rows, cols, seed, 0.0, 1.0)
+}
+
+/// [low, high) 上均匀分布的固定数据集
+pub fn fixed_uniform(rows: usize, cols: usize, seed: u64, low: f64, high: f64) -> Array2<f64> {
+    let mut rng = fixed_rng(seed);
+    Array2::from_shape_fn((rows, cols), |_| rng.random_range(low..high))
+}
+
+pub fn fixed_rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// 权重相同的 ndarray 版和 Matrix 版网络
+pub fn fixed_nets(
+    input_size: usize,
+    hidden_size: usize,
+    output_size: usize,
+    seed: u64,
+) -> (SimpleNet, SimpleNetMatrix) {
+    let net = SimpleNet::with_initializer(
+        input_size,
+        hidden_size,
+        output_size,
+        &NormalInit { std: 1.0 },
+        seed,
+    );
+    let net_matrix = SimpleNetMatrix {
+        w1: Matrix::from(&net.w1),
+        b1: Matrix::from(&net.b1),
+        w2: Matrix::from(&net.w2),
+        b2: Matrix::from(&net.b2),
+    };
+    (net, net_matrix)
+}
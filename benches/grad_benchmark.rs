@@ -0,0 +1,46 @@
+mod common;
+
+use common::{fixed_dataset, fixed_nets, fixed_rng};
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use ndarray::Array2;
+use rand::Rng;
+use rust_dl_from_scratch::chapter02::init::NormalInit;
+use rust_dl_from_scratch::chapter05::two_layer_net::TwoLayerNet;
+
+const SEED: u64 = 42;
+
+/// 随机的 one-hot 标签
+fn fixed_one_hot(rows: usize, classes: usize, seed: u64) -> Array2<f64> {
+    let mut rng = fixed_rng(seed);
+    let mut t = Array2::zeros((rows, classes));
+    for mut row in t.rows_mut() {
+        row[rng.random_range(0..classes)] = 1.0;
+    }
+    t
+}
+
+// 784-50-10 上数值微分与误差反向传播求梯度的对比：
+// 数值微分每个参数都要做两次前向传播，两者相差几个数量级
+fn benchmark_gradient_784_50_10(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Gradient (784x50x10, batch=10)");
+    group.sample_size(10);
+
+    let x = fixed_dataset(10, 784, SEED);
+    let t = fixed_one_hot(10, 10, SEED);
+    let (simple_net, _) = fixed_nets(784, 50, 10, SEED);
+    let mut two_layer_net =
+        TwoLayerNet::with_initializer(784, 50, 10, &NormalInit { std: 0.01 }, SEED);
+
+    group.bench_function("numerical (SimpleNet)", |b| {
+        b.iter(|| simple_net.numerical_gradient(black_box(&x), black_box(&t)))
+    });
+
+    group.bench_function("backprop (TwoLayerNet)", |b| {
+        b.iter(|| two_layer_net.gradient(black_box(&x), black_box(&t)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_gradient_784_50_10);
+criterion_main!(benches);
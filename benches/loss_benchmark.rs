@@ -1,9 +1,13 @@
+mod common;
+
+use common::{fixed_dataset, fixed_rng};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use ndarray::{Array2, Array};
-use ndarray_rand::RandomExt;
-use ndarray_rand::rand_distr::Uniform;
+use ndarray::Array2;
+use rand::Rng;
 use rust_dl_from_scratch::chapter02::matrix::Matrix;
-use rust_dl_from_scratch::chapter02::loss::cross_entropy_error_optimized;
+use rust_dl_from_scratch::chapter02::loss::{cross_entropy_error_optimized, cross_entropy_matrix};
+
+const SEED: u64 = 42;
 
 // ndarray 版本的损失函数
 fn mean_squared_error_ndarray(y: &Array2<f64>, t: &Array2<f64>) -> f64 {
@@ -16,8 +20,8 @@ fn cross_entropy_error_ndarray(y: &Array2<f64>, t: &Array2<f64>) -> f64 {
     let delta = 1e-7;
     let y_safe = y + delta;
     let log_y = y_safe.mapv(|x| x.ln());
-    let cross_entropy = -(t * log_y).sum_axis(ndarray::Axis(1)).mean().unwrap();
-    cross_entropy
+    
+    -(t * log_y).sum_axis(ndarray::Axis(1)).mean().unwrap()
 }
 
 // Matrix 版本的损失函数
@@ -31,28 +35,12 @@ fn mean_squared_error_matrix(y: &Matrix, t: &Matrix) -> f64 {
     sum / (y.rows as f64)
 }
 
-fn cross_entropy_error_matrix(y: &Matrix, t: &Matrix) -> f64 {
-    let delta = 1e-7;
-    let batch_size = y.rows;
-    let mut sum = 0.0;
-
-    for (y_row, t_row) in y.data.iter().zip(t.data.iter()) {
-        for (y_val, t_val) in y_row.iter().zip(t_row.iter()) {
-            if *t_val == 1.0 {
-                sum += (*y_val + delta).ln();
-            }
-        }
-    }
-
-    -sum / (batch_size as f64)
-}
-
 fn benchmark_mse_small(c: &mut Criterion) {
     let mut group = c.benchmark_group("MSE Small (100x10)");
     
     // 生成测试数据
-    let y_ndarray = Array::random((100, 10), Uniform::new(0.0, 1.0));
-    let t_ndarray = Array::random((100, 10), Uniform::new(0.0, 1.0));
+    let y_ndarray = fixed_dataset(100, 10, SEED);
+    let t_ndarray = fixed_dataset(100, 10, SEED + 1);
     
     let y_matrix = Matrix::from_vec(
         y_ndarray.outer_iter()
@@ -79,8 +67,8 @@ fn benchmark_mse_small(c: &mut Criterion) {
 fn benchmark_mse_large(c: &mut Criterion) {
     let mut group = c.benchmark_group("MSE Large (1000x100)");
     
-    let y_ndarray = Array::random((1000, 100), Uniform::new(0.0, 1.0));
-    let t_ndarray = Array::random((1000, 100), Uniform::new(0.0, 1.0));
+    let y_ndarray = fixed_dataset(1000, 100, SEED);
+    let t_ndarray = fixed_dataset(1000, 100, SEED + 1);
     
     let y_matrix = Matrix::from_vec(
         y_ndarray.outer_iter()
@@ -110,11 +98,12 @@ fn benchmark_cross_entropy_small(c: &mut Criterion) {
     // 生成one-hot编码的标签
     let mut y_data = Vec::new();
     let mut t_data = Vec::new();
+    let mut rng = fixed_rng(SEED);
     
     for _ in 0..100 {
         let mut y_row = vec![0.1; 10];
         let mut t_row = vec![0.0; 10];
-        let true_class = (rand::random::<f64>() * 10.0) as usize;
+        let true_class = rng.random_range(0..10);
         y_row[true_class] = 0.9;
         t_row[true_class] = 1.0;
         y_data.push(y_row);
@@ -132,7 +121,7 @@ fn benchmark_cross_entropy_small(c: &mut Criterion) {
     });
 
     group.bench_function("matrix", |b| {
-        b.iter(|| cross_entropy_error_matrix(black_box(&y_matrix), black_box(&t_matrix)))
+        b.iter(|| cross_entropy_matrix(black_box(&y_matrix), black_box(&t_matrix)))
     });
 
     group.bench_function("ndarray_optimized", |b| {
@@ -147,11 +136,12 @@ fn benchmark_cross_entropy_large(c: &mut Criterion) {
     
     let mut y_data = Vec::new();
     let mut t_data = Vec::new();
+    let mut rng = fixed_rng(SEED);
     
     for _ in 0..1000 {
         let mut y_row = vec![0.01; 100];
         let mut t_row = vec![0.0; 100];
-        let true_class = (rand::random::<f64>() * 100.0) as usize;
+        let true_class = rng.random_range(0..100);
         y_row[true_class] = 0.99;
         t_row[true_class] = 1.0;
         y_data.push(y_row);
@@ -169,7 +159,7 @@ fn benchmark_cross_entropy_large(c: &mut Criterion) {
     });
 
     group.bench_function("matrix", |b| {
-        b.iter(|| cross_entropy_error_matrix(black_box(&y_matrix), black_box(&t_matrix)))
+        b.iter(|| cross_entropy_matrix(black_box(&y_matrix), black_box(&t_matrix)))
     });
 
     group.bench_function("ndarray_optimized", |b| {
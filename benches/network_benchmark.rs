@@ -1,19 +1,19 @@
+mod common;
+
+use common::{fixed_nets, fixed_uniform};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use ndarray::{Array2, Array};
-use ndarray_rand::RandomExt;
-use ndarray_rand::rand_distr::Uniform;
 use rust_dl_from_scratch::chapter02::matrix::Matrix;
-use rust_dl_from_scratch::chapter02::network::{SimpleNet, SimpleNetMatrix};
+
+const SEED: u64 = 42;
 
 fn benchmark_predict_small(c: &mut Criterion) {
     let mut group = c.benchmark_group("Network Predict Small (10x5x3, batch=32)");
     
     // 创建网络
-    let net_ndarray = SimpleNet::new(10, 5, 3);
-    let net_matrix = SimpleNetMatrix::new(10, 5, 3);
+    let (net_ndarray, net_matrix) = fixed_nets(10, 5, 3, SEED);
     
     // 生成测试数据
-    let input_ndarray = Array::random((32, 10), Uniform::new(-1.0, 1.0));
+    let input_ndarray = fixed_uniform(32, 10, SEED, -1.0, 1.0);
     let input_matrix = Matrix::from_vec(
         input_ndarray.outer_iter()
             .map(|row| row.to_vec())
@@ -34,10 +34,9 @@ fn benchmark_predict_small(c: &mut Criterion) {
 fn benchmark_predict_medium(c: &mut Criterion) {
     let mut group = c.benchmark_group("Network Predict Medium (100x50x10, batch=64)");
     
-    let net_ndarray = SimpleNet::new(100, 50, 10);
-    let net_matrix = SimpleNetMatrix::new(100, 50, 10);
+    let (net_ndarray, net_matrix) = fixed_nets(100, 50, 10, SEED);
     
-    let input_ndarray = Array::random((64, 100), Uniform::new(-1.0, 1.0));
+    let input_ndarray = fixed_uniform(64, 100, SEED, -1.0, 1.0);
     let input_matrix = Matrix::from_vec(
         input_ndarray.outer_iter()
             .map(|row| row.to_vec())
@@ -58,10 +57,9 @@ fn benchmark_predict_medium(c: &mut Criterion) {
 fn benchmark_predict_large(c: &mut Criterion) {
     let mut group = c.benchmark_group("Network Predict Large (784x128x10, batch=128)");
     
-    let net_ndarray = SimpleNet::new(784, 128, 10);
-    let net_matrix = SimpleNetMatrix::new(784, 128, 10);
+    let (net_ndarray, net_matrix) = fixed_nets(784, 128, 10, SEED);
     
-    let input_ndarray = Array::random((128, 784), Uniform::new(-1.0, 1.0));
+    let input_ndarray = fixed_uniform(128, 784, SEED, -1.0, 1.0);
     let input_matrix = Matrix::from_vec(
         input_ndarray.outer_iter()
             .map(|row| row.to_vec())
@@ -82,10 +80,9 @@ fn benchmark_predict_large(c: &mut Criterion) {
 fn benchmark_batch_processing(c: &mut Criterion) {
     let mut group = c.benchmark_group("Batch Processing (256x784x128x10)");
     
-    let net_ndarray = SimpleNet::new(784, 128, 10);
-    let net_matrix = SimpleNetMatrix::new(784, 128, 10);
+    let (net_ndarray, net_matrix) = fixed_nets(784, 128, 10, SEED);
     
-    let input_ndarray = Array::random((256, 784), Uniform::new(-1.0, 1.0));
+    let input_ndarray = fixed_uniform(256, 784, SEED, -1.0, 1.0);
     let input_matrix = Matrix::from_vec(
         input_ndarray.outer_iter()
             .map(|row| row.to_vec())
@@ -103,11 +100,31 @@ fn benchmark_batch_processing(c: &mut Criterion) {
     group.finish();
 }
 
+fn benchmark_matrix_dot(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Matrix Dot (128x784 · 784x128)");
+
+    let a_ndarray = fixed_uniform(128, 784, SEED, -1.0, 1.0);
+    let b_ndarray = fixed_uniform(784, 128, SEED + 1, -1.0, 1.0);
+    let a_matrix = Matrix::from(&a_ndarray);
+    let b_matrix = Matrix::from(&b_ndarray);
+
+    group.bench_function("ndarray", |b| {
+        b.iter(|| black_box(&a_ndarray).dot(black_box(&b_ndarray)))
+    });
+
+    group.bench_function("matrix", |b| {
+        b.iter(|| black_box(&a_matrix).dot(black_box(&b_matrix)))
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     benchmark_predict_small,
     benchmark_predict_medium,
     benchmark_predict_large,
-    benchmark_batch_processing
+    benchmark_batch_processing,
+    benchmark_matrix_dot
 );
 criterion_main!(benches);
\ No newline at end of file
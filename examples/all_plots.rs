@@ -5,6 +5,7 @@ use rust_dl_from_scratch::chapter02::activation::sigmoid;
 use rust_dl_from_scratch::chapter02::grad::numerical_gradient;
 use rust_dl_from_scratch::chapter02::loss::cross_entropy_error;
 use rust_dl_from_scratch::chapter02::network::SimpleNet;
+use rust_dl_from_scratch::chapter02::optimizer::{Optimizer, Sgd};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("🎨 Rust Deep Learning Visualization Examples");
@@ -58,7 +59,7 @@ fn plot_activation_functions() -> Result<(), Box<dyn std::error::Error>> {
 
     // Sigmoid subplot
     {
-        let mut chart = ChartBuilder::on(&left)
+        let mut chart = ChartBuilder::on(left)
             .caption("Sigmoid Function", ("sans-serif", 24))
             .margin(5)
             .x_label_area_size(40)
@@ -67,7 +68,7 @@ fn plot_activation_functions() -> Result<(), Box<dyn std::error::Error>> {
 
         chart.configure_mesh().x_desc("x").y_desc("σ(x)").draw()?;
 
-        let x_vals: Vec<f64> = linspace(-6.0, 6.0, 200).into_iter().collect();
+        let x_vals: Vec<f64> = linspace(-6.0, 6.0, 200).collect();
         let y_vals: Vec<f64> = x_vals
             .iter()
             .map(|&x| {
@@ -85,7 +86,7 @@ fn plot_activation_functions() -> Result<(), Box<dyn std::error::Error>> {
 
     // ReLU subplot
     {
-        let mut chart = ChartBuilder::on(&middle)
+        let mut chart = ChartBuilder::on(middle)
             .caption("ReLU Function", ("sans-serif", 24))
             .margin(5)
             .x_label_area_size(40)
@@ -98,7 +99,7 @@ fn plot_activation_functions() -> Result<(), Box<dyn std::error::Error>> {
             .y_desc("ReLU(x)")
             .draw()?;
 
-        let x_vals: Vec<f64> = linspace(-3.0, 3.0, 200).into_iter().collect();
+        let x_vals: Vec<f64> = linspace(-3.0, 3.0, 200).collect();
         let relu_vals: Vec<f64> = x_vals.iter().map(|&x| x.max(0.0)).collect();
 
         chart.draw_series(LineSeries::new(
@@ -109,7 +110,7 @@ fn plot_activation_functions() -> Result<(), Box<dyn std::error::Error>> {
 
     // Tanh subplot
     {
-        let mut chart = ChartBuilder::on(&right)
+        let mut chart = ChartBuilder::on(right)
             .caption("Tanh Function", ("sans-serif", 24))
             .margin(5)
             .x_label_area_size(40)
@@ -122,7 +123,7 @@ fn plot_activation_functions() -> Result<(), Box<dyn std::error::Error>> {
             .y_desc("tanh(x)")
             .draw()?;
 
-        let x_vals: Vec<f64> = linspace(-3.0, 3.0, 200).into_iter().collect();
+        let x_vals: Vec<f64> = linspace(-3.0, 3.0, 200).collect();
         let tanh_vals: Vec<f64> = x_vals.iter().map(|&x| x.tanh()).collect();
 
         chart.draw_series(LineSeries::new(
@@ -156,6 +157,7 @@ fn plot_training_loss() -> Result<(), Box<dyn std::error::Error>> {
     let x = array![[0.6, 0.9]];
     let t = array![[0.0, 1.0]];
     let mut net = SimpleNet::new(2, 3, 2);
+    let mut optimizer = Sgd::new(0.1);
     let mut losses = Vec::new();
 
     for epoch in 0..30 {
@@ -176,7 +178,7 @@ fn plot_training_loss() -> Result<(), Box<dyn std::error::Error>> {
                 },
                 &net.w1,
             );
-            net.w1 = &net.w1 + &grad_w1.mapv(|v| -0.1 * v);
+            optimizer.update(&mut [&mut net.w1], &[grad_w1]);
         }
     }
 
@@ -186,7 +188,7 @@ fn plot_training_loss() -> Result<(), Box<dyn std::error::Error>> {
             &BLUE,
         ))?
         .label("Training Loss")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &BLUE));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], BLUE));
 
     chart.draw_series(
         losses
@@ -227,10 +229,11 @@ fn plot_gradient_descent() -> Result<(), Box<dyn std::error::Error>> {
     // Gradient descent
     let mut pos = array![[0.0, 3.0]];
     let mut path = vec![(0.0, 3.0)];
+    let mut optimizer = Sgd::new(0.1);
 
     for _step in 0..20 {
         let grad = numerical_gradient(&objective, &pos);
-        pos = &pos - &(grad * 0.1);
+        optimizer.update(&mut [&mut pos], &[grad]);
         path.push((pos[[0, 0]], pos[[0, 1]]));
     }
 
@@ -258,7 +261,7 @@ fn plot_gradient_descent() -> Result<(), Box<dyn std::error::Error>> {
     chart
         .draw_series(LineSeries::new(path.iter().map(|(x, y)| (*x, *y)), &RED))?
         .label("Optimization Path")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &RED));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], RED));
 
     chart.draw_series(
         path.iter()
@@ -307,11 +310,11 @@ fn plot_loss_heatmap() -> Result<(), Box<dyn std::error::Error>> {
     let t = array![[0.0, 1.0]];
 
     let resolution = 40;
-    let w_range: Vec<f64> = linspace(-3.0, 3.0, resolution).into_iter().collect();
+    let w_range: Vec<f64> = linspace(-3.0, 3.0, resolution).collect();
     let step = 6.0 / resolution as f64;
 
-    for (i, &w1) in w_range.iter().enumerate() {
-        for (j, &w2) in w_range.iter().enumerate() {
+    for &w1 in w_range.iter() {
+        for &w2 in w_range.iter() {
             let mut net = SimpleNet::new(2, 3, 2);
             net.w1[[0, 0]] = w1;
             net.w2[[0, 0]] = w2;
@@ -346,7 +349,7 @@ fn plot_comparison_chart() -> Result<(), Box<dyn std::error::Error>> {
 
     // Left: Before vs After Training
     {
-        let mut chart = ChartBuilder::on(&left)
+        let mut chart = ChartBuilder::on(left)
             .caption("Network Predictions: Before vs After", ("sans-serif", 20))
             .margin(5)
             .x_label_area_size(40)
@@ -390,7 +393,7 @@ fn plot_comparison_chart() -> Result<(), Box<dyn std::error::Error>> {
 
     // Right: Multiple activation functions comparison
     {
-        let mut chart = ChartBuilder::on(&right)
+        let mut chart = ChartBuilder::on(right)
             .caption("Activation Functions Comparison", ("sans-serif", 20))
             .margin(5)
             .x_label_area_size(40)
@@ -403,7 +406,7 @@ fn plot_comparison_chart() -> Result<(), Box<dyn std::error::Error>> {
             .y_desc("Output")
             .draw()?;
 
-        let x_vals: Vec<f64> = linspace(-3.0, 3.0, 100).into_iter().collect();
+        let x_vals: Vec<f64> = linspace(-3.0, 3.0, 100).collect();
 
         // Sigmoid
         let sigmoid_vals: Vec<f64> = x_vals
@@ -423,7 +426,7 @@ fn plot_comparison_chart() -> Result<(), Box<dyn std::error::Error>> {
                 &BLUE,
             ))?
             .label("Sigmoid")
-            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &BLUE));
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], BLUE));
 
         // ReLU
         let relu_vals: Vec<f64> = x_vals.iter().map(|&x| x.max(0.0)).collect();
@@ -434,7 +437,7 @@ fn plot_comparison_chart() -> Result<(), Box<dyn std::error::Error>> {
                 &RED,
             ))?
             .label("ReLU")
-            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &RED));
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], RED));
 
         // Tanh
         let tanh_vals: Vec<f64> = x_vals.iter().map(|&x| x.tanh()).collect();
@@ -445,7 +448,7 @@ fn plot_comparison_chart() -> Result<(), Box<dyn std::error::Error>> {
                 &GREEN,
             ))?
             .label("Tanh")
-            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &GREEN));
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], GREEN));
 
         chart.configure_series_labels().draw()?;
     }
@@ -0,0 +1,126 @@
+// examples/decision_boundary.rs
+use plotters::prelude::*;
+use rust_dl_from_scratch::chapter02::init::HeInit;
+use rust_dl_from_scratch::chapter02::metrics::{accuracy, to_one_hot};
+use rust_dl_from_scratch::chapter02::model::argmax_predictions;
+use rust_dl_from_scratch::chapter02::trainer::{RunConfig, Trainer};
+use rust_dl_from_scratch::chapter02::visualize::decision_grid;
+use rust_dl_from_scratch::chapter05::two_layer_net::TwoLayerNet;
+use rust_dl_from_scratch::datasets::synthetic::{SyntheticDataset, make_moons};
+use std::path::Path;
+
+const SEED: u64 = 42;
+const X_RANGE: (f64, f64) = (-1.5, 2.5);
+const Y_RANGE: (f64, f64) = (-1.0, 1.5);
+const RESOLUTION: usize = 100;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Training an MLP on two moons...");
+
+    // Create output directory if it doesn't exist
+    std::fs::create_dir_all("output")?;
+
+    let (net, data) = train(SEED);
+    let acc = train_accuracy(&net, &data);
+    println!("Training accuracy: {:.2}%", acc * 100.0);
+
+    plot_decision_boundary(&net, &data, Path::new("output/decision_boundary.png"))?;
+    println!("Decision boundary plot saved to output/decision_boundary.png");
+    Ok(())
+}
+
+/// Generate two moons and fit a small TwoLayerNet on them
+fn train(seed: u64) -> (TwoLayerNet, SyntheticDataset) {
+    let config = RunConfig::new(seed);
+    let data = make_moons(200, 0.1, seed);
+    let t = to_one_hot(&data.labels, 2);
+
+    let net = TwoLayerNet::with_initializer(2, 32, 2, &HeInit, config.weight_seed());
+    let mut trainer = Trainer::new(net, 0.2, 300, 20).with_run_config(config);
+    trainer.fit(&data.x, &t);
+    (trainer.model, data)
+}
+
+fn train_accuracy(net: &TwoLayerNet, data: &SyntheticDataset) -> f64 {
+    accuracy(&argmax_predictions(net, &data.x), &data.labels)
+}
+
+fn plot_decision_boundary(
+    net: &TwoLayerNet,
+    data: &SyntheticDataset,
+    path: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let root = BitMapBackend::new(path, (800, 600)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Two Moons Decision Boundary", ("sans-serif", 40))
+        .margin(10)
+        .x_label_area_size(50)
+        .y_label_area_size(50)
+        .build_cartesian_2d(X_RANGE.0..X_RANGE.1, Y_RANGE.0..Y_RANGE.1)?;
+
+    chart
+        .configure_mesh()
+        .x_desc("Feature 1")
+        .y_desc("Feature 2")
+        .draw()?;
+
+    // Shade each grid cell with the color of its predicted class
+    let grid = decision_grid(net, X_RANGE, Y_RANGE, RESOLUTION);
+    let dx = (X_RANGE.1 - X_RANGE.0) / (RESOLUTION - 1) as f64;
+    let dy = (Y_RANGE.1 - Y_RANGE.0) / (RESOLUTION - 1) as f64;
+    chart.draw_series(grid.indexed_iter().map(|((i, j), &class)| {
+        let x = X_RANGE.0 + j as f64 * dx;
+        let y = Y_RANGE.0 + i as f64 * dy;
+        let color = if class == 0 {
+            BLUE.mix(0.2)
+        } else {
+            RED.mix(0.2)
+        };
+        Rectangle::new(
+            [(x - dx / 2.0, y - dy / 2.0), (x + dx / 2.0, y + dy / 2.0)],
+            color.filled(),
+        )
+    }))?;
+
+    // Overlay the training points
+    for (class, color) in [(0, BLUE), (1, RED)] {
+        chart
+            .draw_series(
+                data.x
+                    .rows()
+                    .into_iter()
+                    .zip(data.labels.iter())
+                    .filter(|&(_, &label)| label == class)
+                    .map(move |(p, _)| Circle::new((p[0], p[1]), 4, color.filled())),
+            )?
+            .label(format!("Class {}", class))
+            .legend(move |(x, y)| Circle::new((x + 5, y), 4, color.filled()));
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .draw()?;
+    root.present()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decision_boundary_example() {
+        let (net, data) = train(SEED);
+        assert!(train_accuracy(&net, &data) > 0.95);
+
+        let dir = std::env::temp_dir().join("decision_boundary_example");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("decision_boundary.png");
+        plot_decision_boundary(&net, &data, &path).unwrap();
+        assert!(path.exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
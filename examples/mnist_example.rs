@@ -34,7 +34,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Mean pixel value: {:.3}", first_image.mean().unwrap());
 
     // Show distribution of labels in training set
-    let mut label_counts = vec![0; 10];
+    let mut label_counts = [0; 10];
     for &label in mnist.train_labels.iter() {
         label_counts[label as usize] += 1;
     }
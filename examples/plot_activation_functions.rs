@@ -35,7 +35,7 @@ fn plot_sigmoid() -> Result<(), Box<dyn std::error::Error>> {
         .draw()?;
 
     // Generate sigmoid data
-    let x_vals: Vec<f64> = linspace(-10.0, 10.0, 1000).into_iter().collect();
+    let x_vals: Vec<f64> = linspace(-10.0, 10.0, 1000).collect();
     let y_vals: Vec<f64> = x_vals
         .iter()
         .map(|&x| {
@@ -51,7 +51,7 @@ fn plot_sigmoid() -> Result<(), Box<dyn std::error::Error>> {
             &BLUE,
         ))?
         .label("sigmoid(x)")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &BLUE));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], BLUE));
 
     chart.configure_series_labels().draw()?;
     root.present()?;
@@ -77,7 +77,7 @@ fn plot_softmax() -> Result<(), Box<dyn std::error::Error>> {
         .draw()?;
 
     // Generate softmax data
-    let x_vals: Vec<f64> = linspace(-5.0, 5.0, 200).into_iter().collect();
+    let x_vals: Vec<f64> = linspace(-5.0, 5.0, 200).collect();
     let mut y1_vals = Vec::new();
     let mut y2_vals = Vec::new();
     let mut y3_vals = Vec::new();
@@ -101,7 +101,7 @@ fn plot_softmax() -> Result<(), Box<dyn std::error::Error>> {
             &RED,
         ))?
         .label("class 1")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &RED));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], RED));
 
     chart
         .draw_series(LineSeries::new(
@@ -109,7 +109,7 @@ fn plot_softmax() -> Result<(), Box<dyn std::error::Error>> {
             &BLUE,
         ))?
         .label("class 2")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &BLUE));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], BLUE));
 
     chart
         .draw_series(LineSeries::new(
@@ -117,7 +117,7 @@ fn plot_softmax() -> Result<(), Box<dyn std::error::Error>> {
             &GREEN,
         ))?
         .label("class 3")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &GREEN));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], GREEN));
 
     chart.configure_series_labels().draw()?;
     root.present()?;
@@ -139,7 +139,7 @@ fn plot_relu_and_tanh() -> Result<(), Box<dyn std::error::Error>> {
     chart.configure_mesh().x_desc("x").y_desc("f(x)").draw()?;
 
     // Generate data
-    let x_vals: Vec<f64> = linspace(-5.0, 5.0, 1000).into_iter().collect();
+    let x_vals: Vec<f64> = linspace(-5.0, 5.0, 1000).collect();
 
     // ReLU function
     let relu_vals: Vec<f64> = x_vals.iter().map(|&x| x.max(0.0)).collect();
@@ -153,7 +153,7 @@ fn plot_relu_and_tanh() -> Result<(), Box<dyn std::error::Error>> {
             &RED,
         ))?
         .label("ReLU(x)")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &RED));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], RED));
 
     chart
         .draw_series(LineSeries::new(
@@ -161,7 +161,7 @@ fn plot_relu_and_tanh() -> Result<(), Box<dyn std::error::Error>> {
             &BLUE,
         ))?
         .label("tanh(x)")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &BLUE));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], BLUE));
 
     chart.configure_series_labels().draw()?;
     root.present()?;
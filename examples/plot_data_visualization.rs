@@ -1,24 +1,50 @@
 // examples/plot_data_visualization.rs
+use ndarray::Array2;
 use plotters::prelude::*;
-use rand::{Rng, thread_rng};
-use rand_distr::{Distribution, Normal, Uniform};
+use rust_dl_from_scratch::datasets::synthetic::{
+    make_moons, noisy_circle, normal_cloud, quadratic_regression, spiral, uniform_square,
+};
+use std::str::FromStr;
+
+// Defaults for the number of points per dataset and the RNG seed.
+// Override them with the N_POINTS and SEED environment variables, e.g.
+// `N_POINTS=300 SEED=7 cargo run --example plot_data_visualization`
+const N_POINTS: usize = 100;
+const SEED: u64 = 42;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Creating data visualization examples...");
 
+    let n = env_or("N_POINTS", N_POINTS);
+    let seed = env_or("SEED", SEED);
+    println!("Using {} points per dataset, seed {}", n, seed);
+
     // Create output directory if it doesn't exist
     std::fs::create_dir_all("output")?;
 
-    plot_scatter_data()?;
-    plot_classification_data()?;
-    plot_regression_data()?;
-    plot_multiple_datasets()?;
+    plot_scatter_data(n, seed)?;
+    plot_classification_data(n, seed)?;
+    plot_regression_data(n, seed)?;
+    plot_multiple_datasets(n, seed)?;
 
     println!("Data visualization plots saved to output/ directory");
     Ok(())
 }
 
-fn plot_scatter_data() -> Result<(), Box<dyn std::error::Error>> {
+/// Read `name` from the environment, falling back to `default` if unset or unparsable
+fn env_or<T: FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Convert an (n, 2) array of points into (x, y) pairs for plotting
+fn points(x: &Array2<f64>) -> Vec<(f64, f64)> {
+    x.rows().into_iter().map(|row| (row[0], row[1])).collect()
+}
+
+fn plot_scatter_data(n: usize, seed: u64) -> Result<(), Box<dyn std::error::Error>> {
     let root = BitMapBackend::new("output/scatter_plot.png", (800, 600)).into_drawing_area();
     root.fill(&WHITE)?;
 
@@ -32,24 +58,8 @@ fn plot_scatter_data() -> Result<(), Box<dyn std::error::Error>> {
     chart.configure_mesh().x_desc("X").y_desc("Y").draw()?;
 
     // Generate random data
-    let mut rng = thread_rng();
-    let normal = Normal::new(0.0, 1.0).unwrap();
-
-    let data1: Vec<(f64, f64)> = (0..100)
-        .map(|_| {
-            let x = normal.sample(&mut rng);
-            let y = normal.sample(&mut rng);
-            (x, y)
-        })
-        .collect();
-
-    let data2: Vec<(f64, f64)> = (0..100)
-        .map(|_| {
-            let x = normal.sample(&mut rng) + 1.5;
-            let y = normal.sample(&mut rng) + 1.5;
-            (x, y)
-        })
-        .collect();
+    let data1 = points(&normal_cloud(n, (0.0, 0.0), 1.0, seed));
+    let data2 = points(&normal_cloud(n, (1.5, 1.5), 1.0, seed.wrapping_add(1)));
 
     // Plot first dataset
     chart
@@ -77,7 +87,7 @@ fn plot_scatter_data() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn plot_classification_data() -> Result<(), Box<dyn std::error::Error>> {
+fn plot_classification_data(n: usize, seed: u64) -> Result<(), Box<dyn std::error::Error>> {
     let root = BitMapBackend::new("output/classification_data.png", (800, 600)).into_drawing_area();
     root.fill(&WHITE)?;
 
@@ -86,7 +96,7 @@ fn plot_classification_data() -> Result<(), Box<dyn std::error::Error>> {
         .margin(10)
         .x_label_area_size(50)
         .y_label_area_size(50)
-        .build_cartesian_2d(-4f64..4f64, -4f64..4f64)?;
+        .build_cartesian_2d(-2f64..3f64, -1.5f64..2f64)?;
 
     chart
         .configure_mesh()
@@ -95,27 +105,13 @@ fn plot_classification_data() -> Result<(), Box<dyn std::error::Error>> {
         .draw()?;
 
     // Generate classification data (two moons pattern)
-    let mut rng = thread_rng();
-    let noise = Normal::new(0.0, 0.1).unwrap();
-
-    let mut class_0 = Vec::new();
-    let mut class_1 = Vec::new();
-
-    // Generate first moon
-    for i in 0..100 {
-        let t = std::f64::consts::PI * (i as f64) / 100.0;
-        let x = 2.0 * t.cos() + noise.sample(&mut rng);
-        let y = t.sin() + noise.sample(&mut rng);
-        class_0.push((x, y));
-    }
-
-    // Generate second moon
-    for i in 0..100 {
-        let t = std::f64::consts::PI * (i as f64) / 100.0;
-        let x = 2.0 * (t + std::f64::consts::PI).cos() + 2.0 + noise.sample(&mut rng);
-        let y = (t + std::f64::consts::PI).sin() - 0.5 + noise.sample(&mut rng);
-        class_1.push((x, y));
-    }
+    let moons = make_moons(2 * n, 0.1, seed);
+    let (class_0, class_1): (Vec<_>, Vec<_>) = points(&moons.x)
+        .into_iter()
+        .zip(moons.labels.iter())
+        .partition(|&(_, &label)| label == 0);
+    let class_0: Vec<(f64, f64)> = class_0.into_iter().map(|(p, _)| p).collect();
+    let class_1: Vec<(f64, f64)> = class_1.into_iter().map(|(p, _)| p).collect();
 
     // Plot class 0
     chart
@@ -143,7 +139,7 @@ fn plot_classification_data() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn plot_regression_data() -> Result<(), Box<dyn std::error::Error>> {
+fn plot_regression_data(n: usize, seed: u64) -> Result<(), Box<dyn std::error::Error>> {
     let root = BitMapBackend::new("output/regression_data.png", (800, 600)).into_drawing_area();
     root.fill(&WHITE)?;
 
@@ -156,18 +152,9 @@ fn plot_regression_data() -> Result<(), Box<dyn std::error::Error>> {
 
     chart.configure_mesh().x_desc("X").y_desc("Y").draw()?;
 
-    // Generate regression data
-    let mut rng = thread_rng();
-    let noise = Normal::new(0.0, 0.3).unwrap();
-    let uniform = Uniform::new(-2.0, 2.0).unwrap();
-
-    let data: Vec<(f64, f64)> = (0..200)
-        .map(|_| {
-            let x = uniform.sample(&mut rng);
-            let y = x * x + 0.5 * x + noise.sample(&mut rng); // Quadratic with noise
-            (x, y)
-        })
-        .collect();
+    // Generate regression data: y = x² + 0.5x with noise
+    let (xs, ys) = quadratic_regression(2 * n, 0.3, seed);
+    let data: Vec<(f64, f64)> = xs.iter().copied().zip(ys.iter().copied()).collect();
 
     // Plot data points
     chart
@@ -190,7 +177,7 @@ fn plot_regression_data() -> Result<(), Box<dyn std::error::Error>> {
     chart
         .draw_series(LineSeries::new(true_func, &RED))?
         .label("True Function: y = x² + 0.5x")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &RED));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], RED));
 
     chart.configure_series_labels().draw()?;
     root.present()?;
@@ -198,7 +185,7 @@ fn plot_regression_data() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn plot_multiple_datasets() -> Result<(), Box<dyn std::error::Error>> {
+fn plot_multiple_datasets(n: usize, seed: u64) -> Result<(), Box<dyn std::error::Error>> {
     let root = BitMapBackend::new("output/multiple_datasets.png", (1200, 800)).into_drawing_area();
     root.fill(&WHITE)?;
 
@@ -215,7 +202,7 @@ fn plot_multiple_datasets() -> Result<(), Box<dyn std::error::Error>> {
 
     // Subplot 1: Normal distribution
     {
-        let mut chart = ChartBuilder::on(&upper_left)
+        let mut chart = ChartBuilder::on(upper_left)
             .caption("Normal Distribution", ("sans-serif", 20))
             .margin(5)
             .x_label_area_size(30)
@@ -224,12 +211,7 @@ fn plot_multiple_datasets() -> Result<(), Box<dyn std::error::Error>> {
 
         chart.configure_mesh().draw()?;
 
-        let mut rng = thread_rng();
-        let normal = Normal::new(0.0, 1.0).unwrap();
-
-        let data: Vec<(f64, f64)> = (0..100)
-            .map(|_| (normal.sample(&mut rng), normal.sample(&mut rng)))
-            .collect();
+        let data = points(&normal_cloud(n, (0.0, 0.0), 1.0, seed));
 
         chart.draw_series(
             data.iter()
@@ -239,7 +221,7 @@ fn plot_multiple_datasets() -> Result<(), Box<dyn std::error::Error>> {
 
     // Subplot 2: Uniform distribution
     {
-        let mut chart = ChartBuilder::on(&upper_right)
+        let mut chart = ChartBuilder::on(upper_right)
             .caption("Uniform Distribution", ("sans-serif", 20))
             .margin(5)
             .x_label_area_size(30)
@@ -248,12 +230,7 @@ fn plot_multiple_datasets() -> Result<(), Box<dyn std::error::Error>> {
 
         chart.configure_mesh().draw()?;
 
-        let mut rng = thread_rng();
-        let uniform = Uniform::new(-1.0, 1.0).unwrap();
-
-        let data: Vec<(f64, f64)> = (0..100)
-            .map(|_| (uniform.sample(&mut rng), uniform.sample(&mut rng)))
-            .collect();
+        let data = points(&uniform_square(n, -1.0, 1.0, seed));
 
         chart.draw_series(
             data.iter()
@@ -263,7 +240,7 @@ fn plot_multiple_datasets() -> Result<(), Box<dyn std::error::Error>> {
 
     // Subplot 3: Circular pattern
     {
-        let mut chart = ChartBuilder::on(&lower_left)
+        let mut chart = ChartBuilder::on(lower_left)
             .caption("Circular Pattern", ("sans-serif", 20))
             .margin(5)
             .x_label_area_size(30)
@@ -272,18 +249,7 @@ fn plot_multiple_datasets() -> Result<(), Box<dyn std::error::Error>> {
 
         chart.configure_mesh().draw()?;
 
-        let mut rng = thread_rng();
-        let noise = Normal::new(0.0, 0.1).unwrap();
-
-        let data: Vec<(f64, f64)> = (0..100)
-            .map(|i| {
-                let angle = 2.0 * std::f64::consts::PI * (i as f64) / 100.0;
-                let radius = 1.0 + noise.sample(&mut rng);
-                let x = radius * angle.cos();
-                let y = radius * angle.sin();
-                (x, y)
-            })
-            .collect();
+        let data = points(&noisy_circle(n, 1.0, 0.1, seed));
 
         chart.draw_series(
             data.iter()
@@ -293,7 +259,7 @@ fn plot_multiple_datasets() -> Result<(), Box<dyn std::error::Error>> {
 
     // Subplot 4: Spiral pattern
     {
-        let mut chart = ChartBuilder::on(&lower_right)
+        let mut chart = ChartBuilder::on(lower_right)
             .caption("Spiral Pattern", ("sans-serif", 20))
             .margin(5)
             .x_label_area_size(30)
@@ -302,18 +268,7 @@ fn plot_multiple_datasets() -> Result<(), Box<dyn std::error::Error>> {
 
         chart.configure_mesh().draw()?;
 
-        let mut rng = thread_rng();
-        let noise = Normal::new(0.0, 0.05).unwrap();
-
-        let data: Vec<(f64, f64)> = (0..200)
-            .map(|i| {
-                let t = (i as f64) * 0.1;
-                let radius = 0.1 * t;
-                let x = radius * t.cos() + noise.sample(&mut rng);
-                let y = radius * t.sin() + noise.sample(&mut rng);
-                (x, y)
-            })
-            .collect();
+        let data = points(&spiral(2 * n, 0.05, seed));
 
         chart.draw_series(
             data.iter()
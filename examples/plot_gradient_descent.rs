@@ -1,7 +1,12 @@
 // examples/plot_gradient_descent.rs
-use ndarray::{Array2, linspace};
+use ndarray::linspace;
 use plotters::prelude::*;
-use rust_dl_from_scratch::chapter02::grad::numerical_gradient;
+use rust_dl_from_scratch::chapter02::optimizer::Sgd;
+use rust_dl_from_scratch::chapter02::visualize::{Path2d, optimizer_path};
+
+const START: (f64, f64) = (0.0, 3.0);
+const LEARNING_RATE: f64 = 0.1;
+const NUM_ITERATIONS: usize = 50;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Visualizing gradient descent on a 2D function...");
@@ -21,9 +26,13 @@ fn objective_function(x: f64, y: f64) -> f64 {
     (x - 2.0).powi(2) + (y - 1.0).powi(2)
 }
 
-// Wrapper for numerical gradient computation
-fn objective_function_array(params: &Array2<f64>) -> f64 {
-    objective_function(params[[0, 0]], params[[0, 1]])
+fn descent_path() -> Path2d {
+    optimizer_path(
+        objective_function,
+        START,
+        &mut Sgd::new(LEARNING_RATE),
+        NUM_ITERATIONS,
+    )
 }
 
 fn plot_gradient_descent_2d() -> Result<(), Box<dyn std::error::Error>> {
@@ -39,45 +48,22 @@ fn plot_gradient_descent_2d() -> Result<(), Box<dyn std::error::Error>> {
 
     chart.configure_mesh().x_desc("x").y_desc("y").draw()?;
 
-    // Starting point
-    let mut current_pos = Array2::from_elem((1, 2), 0.0);
-    current_pos[[0, 0]] = 0.0; // x
-    current_pos[[0, 1]] = 3.0; // y
-
-    let learning_rate = 0.1;
-    let num_iterations = 50;
-    let mut path = Vec::new();
-
-    // Perform gradient descent
-    for i in 0..num_iterations {
-        let x = current_pos[[0, 0]];
-        let y = current_pos[[0, 1]];
-        let loss = objective_function(x, y);
-
-        path.push((x, y));
-
-        if i % 10 == 0 {
-            println!(
-                "Iteration {}: x={:.3}, y={:.3}, f(x,y)={:.3}",
-                i, x, y, loss
-            );
-        }
-
-        // Calculate gradient
-        let grad = numerical_gradient(objective_function_array, &current_pos);
-
-        // Update position
-        current_pos = &current_pos - &(grad * learning_rate);
+    let path = descent_path();
+    for (i, &(x, y)) in path.iter().enumerate().step_by(10) {
+        println!(
+            "Iteration {}: x={:.3}, y={:.3}, f(x,y)={:.3}",
+            i,
+            x,
+            y,
+            objective_function(x, y)
+        );
     }
 
-    // Add final point
-    path.push((current_pos[[0, 0]], current_pos[[0, 1]]));
-
     // Draw the path
     chart
         .draw_series(LineSeries::new(path.iter().map(|(x, y)| (*x, *y)), &BLUE))?
         .label("Gradient Descent Path")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &BLUE));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], BLUE));
 
     // Draw points along the path
     chart.draw_series(path.iter().enumerate().map(|(i, (x, y))| {
@@ -133,20 +119,18 @@ fn plot_gradient_descent_contour() -> Result<(), Box<dyn std::error::Error>> {
     chart.configure_mesh().x_desc("x").y_desc("y").draw()?;
 
     // Draw contour lines
-    let x_range: Vec<f64> = linspace(-1.0, 5.0, 100).into_iter().collect();
-    let y_range: Vec<f64> = linspace(-2.0, 4.0, 100).into_iter().collect();
+    let x_range: Vec<f64> = linspace(-1.0, 5.0, 100).collect();
+    let y_range: Vec<f64> = linspace(-2.0, 4.0, 100).collect();
 
-    let levels = vec![0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
-    let colors = vec![&CYAN, &MAGENTA, &YELLOW, &RED, &BLUE, &GREEN];
+    let levels = [0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+    let colors = [&CYAN, &MAGENTA, &YELLOW, &RED, &BLUE, &GREEN];
 
     for (level, color) in levels.iter().zip(colors.iter()) {
         let mut contour_points = Vec::new();
 
         // Simple contour extraction (not perfect but works for this example)
-        for i in 0..x_range.len() - 1 {
-            for j in 0..y_range.len() - 1 {
-                let x = x_range[i];
-                let y = y_range[j];
+        for &x in &x_range[..x_range.len() - 1] {
+            for &y in &y_range[..y_range.len() - 1] {
                 let z = objective_function(x, y);
 
                 if (z - level).abs() < 0.1 {
@@ -164,30 +148,13 @@ fn plot_gradient_descent_contour() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
-    // Perform gradient descent again
-    let mut current_pos = Array2::from_elem((1, 2), 0.0);
-    current_pos[[0, 0]] = 0.0; // x
-    current_pos[[0, 1]] = 3.0; // y
-
-    let learning_rate = 0.1;
-    let num_iterations = 50;
-    let mut path = Vec::new();
-
-    for _i in 0..num_iterations {
-        let x = current_pos[[0, 0]];
-        let y = current_pos[[0, 1]];
-        path.push((x, y));
-
-        let grad = numerical_gradient(objective_function_array, &current_pos);
-        current_pos = &current_pos - &(grad * learning_rate);
-    }
-    path.push((current_pos[[0, 0]], current_pos[[0, 1]]));
+    let path = descent_path();
 
     // Draw the gradient descent path
     chart
         .draw_series(LineSeries::new(path.iter().map(|(x, y)| (*x, *y)), &BLACK))?
         .label("Gradient Descent Path")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &BLACK));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], BLACK));
 
     // Draw path points
     chart.draw_series(
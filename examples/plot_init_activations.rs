@@ -0,0 +1,89 @@
+// examples/plot_init_activations.rs
+use ndarray::Array2;
+use plotters::prelude::*;
+use rust_dl_from_scratch::chapter02::activation::sigmoid;
+use rust_dl_from_scratch::chapter02::init::{Initializer, NormalInit, XavierInit};
+use rust_dl_from_scratch::utils::math::histogram;
+
+const SAMPLES: usize = 1000;
+const NODES: usize = 100;
+const LAYERS: usize = 5;
+const BINS: usize = 30;
+const SEED: u64 = 42;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Plotting hidden-layer activation distributions per initializer...");
+
+    std::fs::create_dir_all("output")?;
+
+    // Same setup as chapter 6: 5 sigmoid layers of 100 units fed with N(0, 1) inputs
+    let x = NormalInit { std: 1.0 }.init((SAMPLES, NODES), SEED);
+    let initializers: [(&str, &dyn Initializer); 3] = [
+        ("N(0, 1)", &NormalInit { std: 1.0 }),
+        ("N(0, 0.01)", &NormalInit { std: 0.01 }),
+        ("Xavier", &XavierInit),
+    ];
+
+    let rows: Vec<(&str, Vec<Array2<f64>>)> = initializers
+        .iter()
+        .map(|&(name, init)| (name, hidden_activations(&x, init)))
+        .collect();
+
+    plot_activation_histograms(&rows)?;
+    println!("Activation histograms saved to output/init_activation_histogram.png");
+    Ok(())
+}
+
+/// Forward `x` through `LAYERS` sigmoid layers and keep every layer's output
+fn hidden_activations(x: &Array2<f64>, init: &dyn Initializer) -> Vec<Array2<f64>> {
+    let mut activations = Vec::with_capacity(LAYERS);
+    let mut z = x.clone();
+    for layer in 0..LAYERS {
+        let w = init.init((NODES, NODES), SEED + 1 + layer as u64);
+        z = sigmoid(&z.dot(&w));
+        activations.push(z.clone());
+    }
+    activations
+}
+
+fn plot_activation_histograms(
+    rows: &[(&str, Vec<Array2<f64>>)],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let root =
+        BitMapBackend::new("output/init_activation_histogram.png", (1500, 900)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let areas = root.split_evenly((rows.len(), LAYERS));
+    let width = 1.0 / BINS as f64;
+    let colors = [BLUE, RED, GREEN];
+
+    for (row, ((name, activations), color)) in rows.iter().zip(colors).enumerate() {
+        for (layer, a) in activations.iter().enumerate() {
+            let counts = histogram(&a.flatten().view(), BINS, (0.0, 1.0));
+            let max_count = counts.iter().copied().max().unwrap_or(1) as f64;
+
+            let mut chart = ChartBuilder::on(&areas[row * LAYERS + layer])
+                .caption(
+                    format!("{} - layer {}", name, layer + 1),
+                    ("sans-serif", 18),
+                )
+                .margin(5)
+                .x_label_area_size(25)
+                .y_label_area_size(40)
+                .build_cartesian_2d(0f64..1f64, 0f64..(max_count * 1.1))?;
+
+            chart.configure_mesh().x_labels(3).y_labels(3).draw()?;
+
+            chart.draw_series(counts.iter().enumerate().map(|(i, &count)| {
+                let x0 = i as f64 * width;
+                Rectangle::new(
+                    [(x0, 0.0), (x0 + width, count as f64)],
+                    color.mix(0.6).filled(),
+                )
+            }))?;
+        }
+    }
+
+    root.present()?;
+    Ok(())
+}
@@ -33,8 +33,8 @@ fn plot_loss_surface() -> Result<(), Box<dyn std::error::Error>> {
     t_mut[[0, 1]] = 1.0;
 
     // Create a simple 3D-like visualization by varying two parameters
-    let w1_range: Vec<f64> = linspace(-3.0, 3.0, 20).into_iter().collect();
-    let w2_range: Vec<f64> = linspace(-3.0, 3.0, 20).into_iter().collect();
+    let w1_range: Vec<f64> = linspace(-3.0, 3.0, 20).collect();
+    let w2_range: Vec<f64> = linspace(-3.0, 3.0, 20).collect();
 
     let mut chart = ChartBuilder::on(&root)
         .caption("Loss Function Surface (2D projection)", ("sans-serif", 40))
@@ -120,8 +120,8 @@ fn plot_loss_heatmap() -> Result<(), Box<dyn std::error::Error>> {
 
     // Higher resolution for heatmap
     let resolution = 50;
-    let w1_range: Vec<f64> = linspace(-3.0, 3.0, resolution).into_iter().collect();
-    let w2_range: Vec<f64> = linspace(-3.0, 3.0, resolution).into_iter().collect();
+    let w1_range: Vec<f64> = linspace(-3.0, 3.0, resolution).collect();
+    let w2_range: Vec<f64> = linspace(-3.0, 3.0, resolution).collect();
 
     let mut loss_grid = Vec::new();
 
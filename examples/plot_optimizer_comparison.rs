@@ -0,0 +1,98 @@
+// examples/plot_optimizer_comparison.rs
+use plotters::coord::types::RangedCoordf64;
+use plotters::prelude::*;
+use rust_dl_from_scratch::chapter02::visualize::{Path2d, optimizer_paths};
+
+const START: (f64, f64) = (-7.0, 2.0);
+const STEPS: usize = 30;
+const X_RANGE: (f64, f64) = (-10.0, 10.0);
+const Y_RANGE: (f64, f64) = (-5.0, 5.0);
+
+// The book's test function: stretched along x, so the gradient mostly points along y
+fn objective(x: f64, y: f64) -> f64 {
+    x * x / 20.0 + y * y
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Comparing SGD, Momentum, AdaGrad and Adam on f(x, y) = x²/20 + y²...");
+
+    std::fs::create_dir_all("output")?;
+
+    let paths = optimizer_paths(objective, START, STEPS);
+    for (name, path) in &paths {
+        let &(x, y) = path.last().unwrap();
+        println!(
+            "{:>8}: ended at ({:.3}, {:.3}), f = {:.5}",
+            name,
+            x,
+            y,
+            objective(x, y)
+        );
+    }
+
+    plot_paths(&paths)?;
+    println!("Optimizer comparison saved to output/optimizer_comparison.png");
+    Ok(())
+}
+
+fn plot_paths(paths: &[(&str, Path2d)]) -> Result<(), Box<dyn std::error::Error>> {
+    let root =
+        BitMapBackend::new("output/optimizer_comparison.png", (1200, 900)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let areas = root.split_evenly((2, 2));
+    for ((name, path), area) in paths.iter().zip(areas.iter()) {
+        let mut chart = ChartBuilder::on(area)
+            .caption(*name, ("sans-serif", 28))
+            .margin(10)
+            .x_label_area_size(30)
+            .y_label_area_size(30)
+            .build_cartesian_2d(X_RANGE.0..X_RANGE.1, Y_RANGE.0..Y_RANGE.1)?;
+        chart.configure_mesh().disable_mesh().draw()?;
+
+        draw_contours(&mut chart)?;
+
+        chart.draw_series(LineSeries::new(path.iter().copied(), RED.stroke_width(2)))?;
+        chart.draw_series(
+            path.iter()
+                .map(|&(x, y)| Circle::new((x, y), 3, RED.filled())),
+        )?;
+        // The minimum at the origin
+        chart.draw_series(std::iter::once(Cross::new(
+            (0.0, 0.0),
+            6,
+            BLACK.stroke_width(2),
+        )))?;
+    }
+
+    root.present()?;
+    Ok(())
+}
+
+/// Approximate contour lines: mark grid cells whose value is close to each level
+fn draw_contours<DB: DrawingBackend>(
+    chart: &mut ChartContext<DB, Cartesian2d<RangedCoordf64, RangedCoordf64>>,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let resolution = 300;
+    let levels = [0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+    let mut points = Vec::new();
+    for i in 0..resolution {
+        for j in 0..resolution {
+            let x = X_RANGE.0 + (X_RANGE.1 - X_RANGE.0) * i as f64 / resolution as f64;
+            let y = Y_RANGE.0 + (Y_RANGE.1 - Y_RANGE.0) * j as f64 / resolution as f64;
+            let z = objective(x, y);
+            if levels.iter().any(|level| (z - level).abs() < 0.02 * level) {
+                points.push((x, y));
+            }
+        }
+    }
+    chart.draw_series(
+        points
+            .into_iter()
+            .map(|p| Circle::new(p, 1, RGBColor(150, 150, 150).filled())),
+    )?;
+    Ok(())
+}
@@ -0,0 +1,108 @@
+// examples/plot_weight_histogram.rs
+use ndarray::{Array2, array};
+use plotters::prelude::*;
+use rust_dl_from_scratch::chapter02::grad::numerical_gradient;
+use rust_dl_from_scratch::chapter02::loss::cross_entropy_error;
+use rust_dl_from_scratch::chapter02::network::SimpleNet;
+use rust_dl_from_scratch::utils::math::histogram;
+
+const BINS: usize = 30;
+const RANGE: (f64, f64) = (-3.0, 3.0);
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Training network and plotting w1 distribution...");
+
+    std::fs::create_dir_all("output")?;
+
+    let x = array![
+        [0.6, 0.9, 0.1, 0.3],
+        [0.2, 0.1, 0.8, 0.7],
+        [0.9, 0.8, 0.2, 0.1],
+        [0.1, 0.3, 0.9, 0.6]
+    ];
+    let t = array![[0.0, 1.0], [1.0, 0.0], [0.0, 1.0], [1.0, 0.0]];
+
+    let mut net = SimpleNet::new(4, 50, 2);
+    let before = histogram(&net.w1.flatten().view(), BINS, RANGE);
+
+    let lr = 0.5;
+    for epoch in 0..200 {
+        let grad_w1 = numerical_gradient(
+            |w| {
+                let mut cloned = net.clone();
+                cloned.w1 = w.clone();
+                loss_fn(&cloned, &x, &t)
+            },
+            &net.w1,
+        );
+        let grad_w2 = numerical_gradient(
+            |w| {
+                let mut cloned = net.clone();
+                cloned.w2 = w.clone();
+                loss_fn(&cloned, &x, &t)
+            },
+            &net.w2,
+        );
+
+        net.w1 = &net.w1 - &(grad_w1 * lr);
+        net.w2 = &net.w2 - &(grad_w2 * lr);
+
+        if epoch % 50 == 0 {
+            println!("Epoch {}: Loss = {:.6}", epoch, loss_fn(&net, &x, &t));
+        }
+    }
+    let after = histogram(&net.w1.flatten().view(), BINS, RANGE);
+
+    plot_histograms(&before, &after)?;
+
+    println!("Weight histogram saved to output/weight_histogram.png");
+    Ok(())
+}
+
+fn loss_fn(net: &SimpleNet, x: &Array2<f64>, t: &Array2<f64>) -> f64 {
+    let y = net.predict(x);
+    cross_entropy_error(&y, t)
+}
+
+fn plot_histograms(before: &[usize], after: &[usize]) -> Result<(), Box<dyn std::error::Error>> {
+    let root = BitMapBackend::new("output/weight_histogram.png", (1200, 500)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let areas = root.split_evenly((1, 2));
+    let max_count = before
+        .iter()
+        .chain(after.iter())
+        .copied()
+        .max()
+        .unwrap_or(1) as f64;
+    let width = (RANGE.1 - RANGE.0) / BINS as f64;
+
+    for (area, counts, caption, color) in [
+        (&areas[0], before, "w1 before training", BLUE),
+        (&areas[1], after, "w1 after training", RED),
+    ] {
+        let mut chart = ChartBuilder::on(area)
+            .caption(caption, ("sans-serif", 30))
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(50)
+            .build_cartesian_2d(RANGE.0..RANGE.1, 0f64..(max_count * 1.1))?;
+
+        chart
+            .configure_mesh()
+            .x_desc("Weight")
+            .y_desc("Count")
+            .draw()?;
+
+        chart.draw_series(counts.iter().enumerate().map(|(i, &count)| {
+            let x0 = RANGE.0 + i as f64 * width;
+            Rectangle::new(
+                [(x0, 0.0), (x0 + width, count as f64)],
+                color.mix(0.6).filled(),
+            )
+        }))?;
+    }
+
+    root.present()?;
+    Ok(())
+}
@@ -0,0 +1,73 @@
+// examples/simple_convnet_mnist.rs
+use ndarray::{Array2, s};
+use rust_dl_from_scratch::chapter02::early_stopping::{EarlyStopping, Monitor};
+use rust_dl_from_scratch::chapter02::init::HeInit;
+use rust_dl_from_scratch::chapter02::trainer::{RunConfig, Trainer};
+use rust_dl_from_scratch::chapter05::simple_conv_net::{ConvNetConfig, SimpleConvNet};
+use rust_dl_from_scratch::datasets::MnistDataset;
+
+const SEED: u64 = 42;
+const TRAIN_SIZE: usize = 5000;
+const TEST_SIZE: usize = 1000;
+const EPOCHS: usize = 5;
+const BATCH_SIZE: usize = 100;
+const LR: f64 = 0.1;
+const PATIENCE: usize = 2;
+const CHECKPOINT: &str = "output/simple_convnet.ckpt";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Training SimpleConvNet on MNIST...");
+    std::fs::create_dir_all("output")?;
+
+    let (train_x, train_t, test_x, test_t) = MnistDataset::load_one_hot()?;
+    // Im2col on the CPU is slow, so train on a subset (the full set reaches ~99%)
+    let train_x = subset(&train_x, TRAIN_SIZE);
+    let train_t = subset(&train_t, TRAIN_SIZE);
+    let test_x = subset(&test_x, TEST_SIZE);
+    let test_t = subset(&test_t, TEST_SIZE);
+    println!(
+        "Using {} training and {} test samples",
+        train_x.nrows(),
+        test_x.nrows()
+    );
+
+    let config = RunConfig::new(SEED);
+    let net =
+        SimpleConvNet::with_initializer(ConvNetConfig::default(), &HeInit, config.weight_seed());
+    // Stop once the test loss has not improved for PATIENCE epochs and keep the best weights
+    let mut trainer = Trainer::new(net, LR, EPOCHS, BATCH_SIZE)
+        .with_run_config(config)
+        .with_early_stopping(EarlyStopping::new(Monitor::TestLoss, PATIENCE))
+        .with_checkpoint(CHECKPOINT);
+    // A checkpoint left behind by an interrupted run: continue from its last finished epoch
+    if std::path::Path::new(CHECKPOINT).exists() {
+        trainer.resume_from(CHECKPOINT)?;
+        println!("Resuming from {}", CHECKPOINT);
+    }
+    let history = trainer.train((&train_x, &train_t), (&test_x, &test_t));
+    println!("{}", history);
+    if let Some(e) = history.checkpoint_error {
+        return Err(e.into());
+    }
+    std::fs::remove_file(CHECKPOINT)?;
+
+    if let Some(best) = history.best_epoch {
+        println!("Best epoch: {}", best + 1);
+    }
+
+    let net = trainer.model;
+    println!(
+        "Train accuracy: {:.2}%",
+        net.accuracy(&train_x, &train_t) * 100.0
+    );
+    println!(
+        "Test accuracy: {:.2}%",
+        net.accuracy(&test_x, &test_t) * 100.0
+    );
+    Ok(())
+}
+
+/// First `n` rows as f64
+fn subset(a: &Array2<f32>, n: usize) -> Array2<f64> {
+    a.slice(s![..n.min(a.nrows()), ..]).mapv(f64::from)
+}
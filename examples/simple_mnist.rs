@@ -11,12 +11,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Quick load of normalized test data
     println!("Loading test data...");
-    let (test_images, test_labels) = MnistDataset::load_test_normalized()?;
+    let (test_images, _test_labels) = MnistDataset::load_test_normalized()?;
     println!("✓ Loaded {} test samples", test_images.nrows());
 
     // Load small subset for quick experiments
     println!("Loading small subset...");
-    let (small_images, small_labels) = MnistDataset::load_small_subset()?;
+    let (small_images, _small_labels) = MnistDataset::load_small_subset()?;
     println!(
         "✓ Loaded {} samples for quick testing",
         small_images.nrows()
@@ -51,8 +51,7 @@ pub fn or_gate(x1: f64, x2: f64) -> f64 {
 pub fn xor_gate(x1: f64, x2: f64) -> f64 {
     let s1 = nand_gate(x1, x2);
     let s2 = or_gate(x1, x2);
-    let y = and_gate(s1, s2);
-    y
+    and_gate(s1, s2)
 }
 
 // add test
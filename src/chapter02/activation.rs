@@ -1,5 +1,5 @@
 // src/chapter02/activation.rs
-use ndarray::{Array2, Axis};
+use ndarray::{Array1, Array2, Axis};
 use super::matrix::Matrix;
 
 // ndarray 版本的激活函数
@@ -7,7 +7,15 @@ pub fn sigmoid(x: &Array2<f64>) -> Array2<f64> {
     x.mapv(|v| 1.0 / (1.0 + (-v).exp()))
 }
 
+/// 按行计算 softmax。
+///
+/// 如果输入的列数为 0（形状为 (n, 0)），每一行都是空的，没有可以归一化的元素，
+/// 直接返回同样形状的空数组，而不会出现 -inf 最大值或除以 0 产生的 NaN
 pub fn softmax(x: &Array2<f64>) -> Array2<f64> {
+    if x.ncols() == 0 {
+        return Array2::zeros(x.raw_dim());
+    }
+
     let mut result = x.clone();
     
     // 对每一行进行 softmax 计算
@@ -24,12 +32,29 @@ pub fn softmax(x: &Array2<f64>) -> Array2<f64> {
     result
 }
 
+/// 单个样本的 softmax 雅可比矩阵 ∂y_i/∂x_j = y_i(δ_ij - y_j)，即 diag(y) - y·yᵀ。
+///
+/// 输入是 softmax 的输出 y（而不是 softmax 的输入），结果为 n×n 矩阵。
+/// 由于 y 的各分量之和恒为 1，雅可比矩阵的每一行之和都为 0
+pub fn softmax_jacobian(y: &Array1<f64>) -> Array2<f64> {
+    let n = y.len();
+    Array2::from_shape_fn((n, n), |(i, j)| {
+        let delta = if i == j { 1.0 } else { 0.0 };
+        y[i] * (delta - y[j])
+    })
+}
+
 // Matrix 版本的激活函数（保持向后兼容）
 pub fn sigmoid_matrix(x: &Matrix) -> Matrix {
     x.map(|v| 1.0 / (1.0 + (-v).exp()))
 }
 
+/// Matrix 版本的 softmax，列数为 0 时同样直接返回空矩阵
 pub fn softmax_matrix(x: &Matrix) -> Matrix {
+    if x.cols == 0 {
+        return x.clone();
+    }
+
     let mut result = Vec::new();
 
     for row in &x.data {
@@ -90,6 +115,18 @@ mod tests {
         assert!((result.sum() - 1.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_softmax_zero_width_rows() {
+        let x = Array2::<f64>::zeros((2, 0));
+        let result = softmax(&x);
+        assert_eq!(result.shape(), &[2, 0]);
+        assert!(!result.iter().any(|v| v.is_nan()));
+
+        let m = Matrix::new(2, 0, 0.0);
+        let y = softmax_matrix(&m);
+        assert_eq!(y.shape(), (2, 0));
+    }
+
     #[test]
     fn test_sigmoid_matrix() {
         let x = Matrix::from_vec(vec![vec![0.0], vec![1.0]]);
@@ -107,4 +144,27 @@ mod tests {
             assert!((sum - 1.0).abs() < 1e-6);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_softmax_jacobian() {
+        use crate::chapter02::grad::numerical_jacobian;
+
+        let x = array![0.3, -1.2, 2.0, 0.5];
+        let softmax_1d = |v: &Array1<f64>| {
+            softmax(&v.clone().insert_axis(Axis(0))).row(0).to_owned()
+        };
+        let y = softmax_1d(&x);
+        let jacobian = softmax_jacobian(&y);
+        assert_eq!(jacobian.shape(), &[4, 4]);
+
+        // 每一行之和为 0
+        for sum in jacobian.sum_axis(Axis(1)) {
+            assert!(sum.abs() < 1e-12);
+        }
+
+        let numeric = numerical_jacobian(softmax_1d, &x);
+        for (a, n) in jacobian.iter().zip(numeric.iter()) {
+            assert!((a - n).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,154 @@
+// src/chapter02/early_stopping.rs
+use super::trainer::TrainContext;
+use ndarray::Array2;
+
+/// 早停时观察的指标
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Monitor {
+    TrainLoss,
+    TrainAcc,
+    /// 测试（验证）集上的损失，需要用 `Trainer::train` 提供测试集
+    #[default]
+    TestLoss,
+    /// 测试（验证）集上的准确率，需要用 `Trainer::train` 提供测试集
+    TestAcc,
+}
+
+impl Monitor {
+    /// 从 epoch 末的训练状态中取出该指标，没有测试集时测试指标为 None
+    pub fn value(&self, ctx: &TrainContext) -> Option<f64> {
+        match self {
+            Monitor::TrainLoss => Some(ctx.train_loss),
+            Monitor::TrainAcc => Some(ctx.train_acc),
+            Monitor::TestLoss => ctx.test_loss,
+            Monitor::TestAcc => ctx.test_acc,
+        }
+    }
+
+    /// 是否为测试集上的指标，只有用 `Trainer::train` 训练时才有值
+    pub fn needs_test_set(&self) -> bool {
+        matches!(self, Monitor::TestLoss | Monitor::TestAcc)
+    }
+
+    /// 损失越小越好，准确率越大越好
+    pub fn lower_is_better(&self) -> bool {
+        matches!(self, Monitor::TrainLoss | Monitor::TestLoss)
+    }
+}
+
+/// 早停：被观察的指标连续 `patience` 个 epoch 没有改善超过 `min_delta` 时停止训练，
+/// `restore_best_weights` 为 true 时训练结束后把模型参数恢复为指标最好的那个 epoch 的值。
+///
+/// 通过 `Trainer::with_early_stopping` 使用
+#[derive(Debug, Clone)]
+pub struct EarlyStopping {
+    pub monitor: Monitor,
+    pub patience: usize,
+    /// 比目前最好的值至少好这么多才算改善
+    pub min_delta: f64,
+    pub restore_best_weights: bool,
+    best: Option<f64>,
+    best_epoch: Option<usize>,
+    wait: usize,
+    best_params: Vec<Array2<f64>>,
+}
+
+impl EarlyStopping {
+    /// `min_delta` 默认为 0，默认恢复最好的参数
+    pub fn new(monitor: Monitor, patience: usize) -> Self {
+        Self {
+            monitor,
+            patience,
+            min_delta: 0.0,
+            restore_best_weights: true,
+            best: None,
+            best_epoch: None,
+            wait: 0,
+            best_params: Vec::new(),
+        }
+    }
+
+    pub fn with_min_delta(mut self, min_delta: f64) -> Self {
+        self.min_delta = min_delta;
+        self
+    }
+
+    pub fn with_restore_best_weights(mut self, restore: bool) -> Self {
+        self.restore_best_weights = restore;
+        self
+    }
+
+    /// 目前为止指标最好的 epoch（从 0 开始）
+    pub fn best_epoch(&self) -> Option<usize> {
+        self.best_epoch
+    }
+
+    /// 清空记录，开始新一次训练
+    pub fn reset(&mut self) {
+        self.best = None;
+        self.best_epoch = None;
+        self.wait = 0;
+        self.best_params.clear();
+    }
+
+    /// 记录一个 epoch 结束时的指标和模型参数，返回是否应当停止训练
+    pub fn observe(&mut self, epoch: usize, value: f64, params: &[&Array2<f64>]) -> bool {
+        let improved = match self.best {
+            None => !value.is_nan(),
+            Some(best) if self.monitor.lower_is_better() => value < best - self.min_delta,
+            Some(best) => value > best + self.min_delta,
+        };
+        if improved {
+            self.best = Some(value);
+            self.best_epoch = Some(epoch);
+            self.wait = 0;
+            if self.restore_best_weights {
+                self.best_params = params.iter().map(|&p| p.clone()).collect();
+            }
+            return false;
+        }
+        self.wait += 1;
+        self.wait >= self.patience
+    }
+
+    /// 需要恢复时返回最好的 epoch 的参数
+    pub(crate) fn best_params(&self) -> Option<&[Array2<f64>]> {
+        (self.restore_best_weights && !self.best_params.is_empty()).then_some(&self.best_params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    fn run(stopper: &mut EarlyStopping, values: &[f64]) -> Option<usize> {
+        let param = array![[0.0]];
+        values
+            .iter()
+            .enumerate()
+            .position(|(epoch, &v)| stopper.observe(epoch, v, &[&param]))
+    }
+
+    #[test]
+    fn test_patience_and_min_delta() {
+        // 第 1 轮之后不再改善，patience 2 -> 第 3 轮（下标 3）停止
+        let mut stopper = EarlyStopping::new(Monitor::TestLoss, 2);
+        assert_eq!(run(&mut stopper, &[1.0, 0.5, 0.6, 0.5, 0.4]), Some(3));
+        assert_eq!(stopper.best_epoch(), Some(1));
+
+        // 小于 min_delta 的下降不算改善
+        let mut stopper = EarlyStopping::new(Monitor::TestLoss, 2).with_min_delta(0.1);
+        assert_eq!(run(&mut stopper, &[1.0, 0.95, 0.92, 0.5]), Some(2));
+        assert_eq!(stopper.best_epoch(), Some(0));
+
+        // 准确率越大越好
+        let mut stopper = EarlyStopping::new(Monitor::TestAcc, 1);
+        assert_eq!(run(&mut stopper, &[0.5, 0.7, 0.9, 0.8]), Some(3));
+        assert_eq!(stopper.best_epoch(), Some(2));
+
+        stopper.reset();
+        assert_eq!(stopper.best_epoch(), None);
+        assert_eq!(run(&mut stopper, &[0.1, 0.2, 0.3]), None);
+    }
+}
@@ -0,0 +1,94 @@
+// src/chapter02/ensemble.rs
+use super::model::Model;
+use ndarray::Array2;
+
+/// 加权平均的集成模型：把多个模型（如不同种子训练出的网络）的 softmax 输出按权重平均。
+/// 权重在预测时自动归一化，所以只需要给出相对大小
+pub struct Ensemble {
+    pub models: Vec<Box<dyn Model>>,
+    pub weights: Vec<f64>,
+}
+
+impl Ensemble {
+    pub fn new(models: Vec<Box<dyn Model>>, weights: Vec<f64>) -> Self {
+        assert_eq!(
+            models.len(),
+            weights.len(),
+            "Ensemble: need exactly one weight per model"
+        );
+        assert!(
+            weights.iter().all(|&w| w >= 0.0) && weights.iter().sum::<f64>() > 0.0,
+            "Ensemble: weights must be non-negative and not all zero"
+        );
+        Self { models, weights }
+    }
+
+    /// 各成员权重相同
+    pub fn uniform(models: Vec<Box<dyn Model>>) -> Self {
+        let weights = vec![1.0; models.len()];
+        Self::new(models, weights)
+    }
+
+    /// 归一化后（总和为 1）的权重
+    pub fn normalized_weights(&self) -> Vec<f64> {
+        let total: f64 = self.weights.iter().sum();
+        self.weights.iter().map(|w| w / total).collect()
+    }
+}
+
+impl Model for Ensemble {
+    fn predict(&self, x: &Array2<f64>) -> Array2<f64> {
+        self.models
+            .iter()
+            .zip(self.normalized_weights())
+            .map(|(model, w)| model.predict(x) * w)
+            .reduce(|acc, y| acc + y)
+            .expect("Ensemble: no models")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    /// 不管输入是什么都返回固定输出的模型
+    struct FixedModel(Array2<f64>);
+
+    impl Model for FixedModel {
+        fn predict(&self, _x: &Array2<f64>) -> Array2<f64> {
+            self.0.clone()
+        }
+    }
+
+    #[test]
+    fn test_weighted_mean_of_members() {
+        let a = array![[0.8, 0.2], [0.4, 0.6]];
+        let b = array![[0.2, 0.8], [0.0, 1.0]];
+        let ensemble = Ensemble::new(
+            vec![
+                Box::new(FixedModel(a.clone())),
+                Box::new(FixedModel(b.clone())),
+            ],
+            vec![3.0, 1.0],
+        );
+
+        assert_eq!(ensemble.normalized_weights(), vec![0.75, 0.25]);
+
+        let y = ensemble.predict(&Array2::zeros((2, 1)));
+        let expected = &a * 0.75 + &b * 0.25;
+        for (got, want) in y.iter().zip(expected.iter()) {
+            assert!((got - want).abs() < 1e-12);
+        }
+        // 成员输出是概率分布，加权平均后每行仍然和为 1
+        for row in y.rows() {
+            assert!((row.sum() - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "one weight per model")]
+    fn test_weight_count_mismatch_panics() {
+        Ensemble::new(vec![Box::new(FixedModel(array![[1.0]]))], vec![0.5, 0.5]);
+    }
+}
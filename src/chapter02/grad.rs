@@ -1,5 +1,10 @@
 // src/chapter02/grad.rs
-use ndarray::{Array, Dimension, NdIndex};
+use super::matrix::Matrix;
+use super::model::Trainable;
+use super::network::{SimpleNet, SimpleNetGrads};
+use ndarray::{Array, Array1, Array2, Dimension, NdIndex};
+use num_complex::Complex;
+use std::fmt;
 
 #[cfg(test)]
 use ndarray::{Ix1, Ix2, arr1, arr2};
@@ -15,6 +20,26 @@ where
     (f(x + H) - f(x - H)) / (2.0 * H)
 }
 
+/// 用中心差分求 f64 标量函数的二阶导数：(f(x+h) - 2f(x) + f(x-h)) / h²
+pub fn numerical_diff2<F>(f: F, x: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    (f(x + H) - 2.0 * f(x) + f(x - H)) / (H * H)
+}
+
+/// 复步长求导：f'(x) ≈ Im(f(x + ih)) / h。
+///
+/// 只要 f 是解析函数（sigmoid、exp、tanh 等），这个公式没有中心差分里两个相近数相减的
+/// 抵消误差，所以 h 可以取得极小，结果精确到机器精度。f 需要用复数运算实现
+pub fn complex_step_diff<F>(f: F, x: f64) -> f64
+where
+    F: Fn(Complex<f64>) -> Complex<f64>,
+{
+    const STEP: f64 = 1e-20;
+    f(Complex::new(x, STEP)).im / STEP
+}
+
 /// 对一个函数 f(x) 计算其对参数 x 的梯度 (通用维度版本)
 pub fn numerical_gradient<F, D>(f: F, x: &Array<f64, D>) -> Array<f64, D>
 where
@@ -43,9 +68,333 @@ where
     grad
 }
 
+/// 向量值函数 f: Rⁿ → Rᵐ 的数值雅可比矩阵，结果为 m×n，第 j 列为 ∂f/∂x_j
+pub fn numerical_jacobian<F>(f: F, x: &Array1<f64>) -> Array2<f64>
+where
+    F: Fn(&Array1<f64>) -> Array1<f64>,
+{
+    let m = f(x).len();
+    let mut jacobian = Array2::zeros((m, x.len()));
+    let mut xh = x.clone();
+
+    for j in 0..x.len() {
+        let original = xh[j];
+
+        xh[j] = original + H;
+        let fxh1 = f(&xh);
+        xh[j] = original - H;
+        let fxh2 = f(&xh);
+        xh[j] = original;
+
+        jacobian.column_mut(j).assign(&((fxh1 - fxh2) / (2.0 * H)));
+    }
+
+    jacobian
+}
+
+/// 用 Richardson 外推求数值梯度：分别用步长 h 和 h/2 做中心差分得到 D(h)、D(h/2)，
+/// 再取 (4·D(h/2) - D(h)) / 3 消去 O(h²) 误差项，精度提高到 O(h⁴)。
+/// 每个元素需要 4 次函数求值，是 `numerical_gradient` 的两倍
+pub fn numerical_gradient_richardson<F, D>(f: F, x: &Array<f64, D>) -> Array<f64, D>
+where
+    F: Fn(&Array<f64, D>) -> f64,
+    D: Dimension,
+    D::Pattern: NdIndex<D> + Clone,
+{
+    let mut grad = Array::zeros(x.raw_dim());
+    let mut xh = x.clone();
+
+    let mut central = |i: &D::Pattern, h: f64| {
+        let original = xh[i.clone()];
+        xh[i.clone()] = original + h;
+        let fxh1 = f(&xh);
+        xh[i.clone()] = original - h;
+        let fxh2 = f(&xh);
+        xh[i.clone()] = original;
+        (fxh1 - fxh2) / (2.0 * h)
+    };
+
+    for (i, g) in grad.indexed_iter_mut() {
+        let d_h = central(&i, H);
+        let d_half = central(&i, H / 2.0);
+        *g = (4.0 * d_half - d_h) / 3.0;
+    }
+
+    grad
+}
+
+/// 只在指定的下标处计算数值梯度，用于对大矩阵抽查少量元素的梯度
+pub fn numerical_gradient_partial<F, D>(f: F, x: &Array<f64, D>, indices: &[D::Pattern]) -> Vec<f64>
+where
+    F: Fn(&Array<f64, D>) -> f64,
+    D: Dimension,
+    D::Pattern: NdIndex<D> + Clone,
+{
+    // 只复制一次，每个下标扰动后再还原
+    let mut xh = x.clone();
+
+    indices
+        .iter()
+        .map(|i| {
+            let original = xh[i.clone()];
+
+            xh[i.clone()] = original + H;
+            let fxh1 = f(&xh);
+            xh[i.clone()] = original - H;
+            let fxh2 = f(&xh);
+            xh[i.clone()] = original;
+
+            (fxh1 - fxh2) / (2.0 * H)
+        })
+        .collect()
+}
+
+/// Matrix 版本的数值梯度
+pub fn numerical_gradient_matrix<F>(f: F, x: &Matrix) -> Matrix
+where
+    F: Fn(&Matrix) -> f64,
+{
+    let mut grad = Matrix::new(x.rows, x.cols, 0.0);
+    let mut xh = x.clone();
+
+    for i in 0..x.rows {
+        for j in 0..x.cols {
+            let original = xh.data[i][j];
+
+            xh.data[i][j] = original + H;
+            let fxh1 = f(&xh);
+            xh.data[i][j] = original - H;
+            let fxh2 = f(&xh);
+            xh.data[i][j] = original;
+
+            grad.data[i][j] = (fxh1 - fxh2) / (2.0 * H);
+        }
+    }
+
+    grad
+}
+
+/// 对网络输出的任意标量目标 objective(predict(x)) 求所有参数的数值梯度，
+/// 用来试验自定义损失而不必手写反向传播。不使用偏置时 b1 / b2 的梯度为 0
+pub fn numerical_gradient_output<F>(
+    net: &SimpleNet,
+    x: &Array2<f64>,
+    objective: F,
+) -> SimpleNetGrads
+where
+    F: Fn(&Array2<f64>) -> f64,
+{
+    let param_gradient = |param: fn(&mut SimpleNet) -> &mut Array2<f64>| {
+        let current = param(&mut net.clone()).clone();
+        numerical_gradient(
+            |p| {
+                let mut cloned = net.clone();
+                *param(&mut cloned) = p.clone();
+                objective(&cloned.predict(x))
+            },
+            &current,
+        )
+    };
+
+    let (b1, b2) = if net.use_bias {
+        (param_gradient(|n| &mut n.b1), param_gradient(|n| &mut n.b2))
+    } else {
+        (
+            Array2::zeros(net.b1.raw_dim()),
+            Array2::zeros(net.b2.raw_dim()),
+        )
+    };
+    SimpleNetGrads {
+        w1: param_gradient(|n| &mut n.w1),
+        b1,
+        w2: param_gradient(|n| &mut n.w2),
+        b2,
+    }
+}
+
+/// `check_gradients` 发现解析梯度与数值梯度不一致时的错误
+#[derive(Debug, Clone, PartialEq)]
+pub enum GradCheckError {
+    /// `gradient` 返回的梯度个数或形状与 `params_mut` 不一致
+    ShapeMismatch { param: usize },
+    /// 某个参数的最大相对误差超过容差
+    Mismatch {
+        /// 误差最大的参数（在 `params_mut` 中的下标）
+        param: usize,
+        /// 该参数中误差最大的元素
+        index: (usize, usize),
+        analytic: f64,
+        numeric: f64,
+        /// 每个参数各自的最大相对误差
+        max_rel_errors: Vec<f64>,
+    },
+}
+
+impl fmt::Display for GradCheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GradCheckError::ShapeMismatch { param } => write!(
+                f,
+                "gradient {} does not match the shape of its parameter",
+                param
+            ),
+            GradCheckError::Mismatch {
+                param,
+                index,
+                analytic,
+                numeric,
+                max_rel_errors,
+            } => write!(
+                f,
+                "parameter {} at {:?}: analytic {:e} vs numeric {:e} (relative error {:e}); \
+                 max relative error per parameter: {:?}",
+                param, index, analytic, numeric, max_rel_errors[*param], max_rel_errors
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GradCheckError {}
+
+/// 相对误差 |a - n| / max(|a|, |n|, 1)：梯度很小时退化为绝对误差，避免 0 附近的噪声被放大
+fn relative_error(a: f64, n: f64) -> f64 {
+    (a - n).abs() / a.abs().max(n.abs()).max(1.0)
+}
+
+/// 两个梯度整体的相对误差 ‖a - n‖ / (‖a‖ + ‖n‖)（L2 范数），两者都为 0 时返回 0。
+///
+/// 结果与梯度的尺度无关：反向传播正确时一般在 1e-7 以下，超过 1e-4 基本可以确定有错
+pub fn gradient_check(analytical: &Array2<f64>, numerical: &Array2<f64>) -> f64 {
+    assert_eq!(
+        analytical.shape(),
+        numerical.shape(),
+        "gradient_check: shape mismatch"
+    );
+    let norm = |a: &Array2<f64>| a.iter().map(|v| v * v).sum::<f64>().sqrt();
+    let denom = norm(analytical) + norm(numerical);
+    if denom == 0.0 {
+        return 0.0;
+    }
+    norm(&(analytical - numerical)) / denom
+}
+
+/// 逐个参数比较模型的解析梯度与数值梯度，返回每个参数的 `gradient_check` 相对误差，
+/// 顺序与 `params_mut` 一致。结束后模型参数保持不变
+pub fn gradient_report<M: Trainable>(
+    model: &mut M,
+    x: &Array2<f64>,
+    t: &Array2<f64>,
+) -> Result<Vec<f64>, GradCheckError> {
+    let analytic = checked_analytic_gradient(model, x, t)?;
+    let numeric = model_numerical_gradient(model, x, t);
+    Ok(analytic
+        .iter()
+        .zip(&numeric)
+        .map(|(a, n)| gradient_check(a, n))
+        .collect())
+}
+
+/// 梯度检查：用模型的 `gradient`（一般是误差反向传播）求解析梯度，
+/// 再逐个扰动 `params_mut` 中的元素求数值梯度，比较两者。
+///
+/// 任一参数的最大相对误差超过 `tol` 时返回错误，错误信息包含误差最大的参数和元素位置。
+/// 检查结束后模型参数保持不变
+pub fn check_gradients<M: Trainable>(
+    model: &mut M,
+    x: &Array2<f64>,
+    t: &Array2<f64>,
+    tol: f64,
+) -> Result<(), GradCheckError> {
+    let analytic = checked_analytic_gradient(model, x, t)?;
+    let numeric = model_numerical_gradient(model, x, t);
+
+    let mut max_rel_errors = Vec::with_capacity(analytic.len());
+    // (参数下标, 元素下标, 解析梯度, 数值梯度) 以及对应的相对误差
+    let mut worst = (0, (0, 0), 0.0, 0.0);
+    let mut worst_err = 0.0;
+
+    for (k, (grad, num)) in analytic.iter().zip(&numeric).enumerate() {
+        let mut max_err: f64 = 0.0;
+        for ((idx, &a), &n) in grad.indexed_iter().zip(num.iter()) {
+            let err = relative_error(a, n);
+            max_err = max_err.max(err);
+            if err > worst_err {
+                worst = (k, idx, a, n);
+                worst_err = err;
+            }
+        }
+        max_rel_errors.push(max_err);
+    }
+
+    if worst_err > tol {
+        let (param, index, analytic, numeric) = worst;
+        return Err(GradCheckError::Mismatch {
+            param,
+            index,
+            analytic,
+            numeric,
+            max_rel_errors,
+        });
+    }
+    Ok(())
+}
+
+/// 求模型的解析梯度，并确认梯度个数和形状与参数一致
+fn checked_analytic_gradient<M: Trainable>(
+    model: &mut M,
+    x: &Array2<f64>,
+    t: &Array2<f64>,
+) -> Result<Vec<Array2<f64>>, GradCheckError> {
+    let analytic = model.gradient(x, t);
+    let params = model.params();
+    if analytic.len() != params.len() {
+        return Err(GradCheckError::ShapeMismatch {
+            param: analytic.len().min(params.len()),
+        });
+    }
+    for (k, (grad, param)) in analytic.iter().zip(&params).enumerate() {
+        if grad.shape() != param.shape() {
+            return Err(GradCheckError::ShapeMismatch { param: k });
+        }
+    }
+    Ok(analytic)
+}
+
+/// 逐个扰动 `params_mut` 中的元素，用中心差分求损失对每个参数的梯度，结束后恢复原值
+fn model_numerical_gradient<M: Trainable>(
+    model: &mut M,
+    x: &Array2<f64>,
+    t: &Array2<f64>,
+) -> Vec<Array2<f64>> {
+    let mut grads: Vec<Array2<f64>> = model
+        .params()
+        .iter()
+        .map(|p| Array2::zeros(p.raw_dim()))
+        .collect();
+
+    for (k, grad) in grads.iter_mut().enumerate() {
+        for (idx, g) in grad.indexed_iter_mut() {
+            let original = model.params()[k][idx];
+
+            model.params_mut()[k][idx] = original + H;
+            let fxh1 = model.loss(x, t);
+            model.params_mut()[k][idx] = original - H;
+            let fxh2 = model.loss(x, t);
+            model.params_mut()[k][idx] = original;
+
+            *g = (fxh1 - fxh2) / (2.0 * H);
+        }
+    }
+    grads
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::chapter02::init::HeInit;
+    use crate::chapter02::model::Model;
+    use crate::chapter05::two_layer_net::TwoLayerNet;
+    use ndarray::array;
 
     #[test]
     fn test_numerical_diff() {
@@ -54,6 +403,13 @@ mod tests {
         assert!((dx - 6.0).abs() < 1e-3);
     }
 
+    #[test]
+    fn test_numerical_diff2() {
+        let f = |x: f64| x.powi(3);
+        let d2 = numerical_diff2(f, 2.0);
+        assert!((d2 - 12.0).abs() < 1e-3);
+    }
+
     #[test]
     fn test_matrix_gradient() {
         // 测试二维数组
@@ -74,4 +430,193 @@ mod tests {
         assert!((grad[1] - 8.0).abs() < 1e-3);
         assert!((grad[2] - 10.0).abs() < 1e-3);
     }
+
+    #[test]
+    fn test_partial_gradient_matches_full() {
+        let f = |x: &Array<f64, Ix2>| {
+            x.iter()
+                .enumerate()
+                .map(|(k, v)| (k + 1) as f64 * v.powi(3))
+                .sum()
+        };
+        let x = arr2(&[[1.0, -2.0, 0.5], [3.0, 0.1, -1.5]]);
+
+        let full = numerical_gradient(f, &x);
+        let indices = [(0, 1), (1, 0), (1, 2)];
+        let partial = numerical_gradient_partial(f, &x, &indices);
+
+        assert_eq!(partial.len(), indices.len());
+        for (g, &i) in partial.iter().zip(indices.iter()) {
+            assert!((g - full[i]).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_numerical_gradient_matrix() {
+        let f = |m: &Matrix| m.data.iter().flatten().map(|v| v.powi(2)).sum();
+        let x = Matrix::from_vec(vec![vec![3.0, 4.0], vec![-1.0, 0.5]]);
+        let grad = numerical_gradient_matrix(f, &x);
+        let expected = [6.0, 8.0, -2.0, 1.0];
+        for (g, e) in grad.data.iter().flatten().zip(expected.iter()) {
+            assert!((g - e).abs() < 1e-3);
+        }
+    }
+
+    fn data() -> (Array2<f64>, Array2<f64>) {
+        let x = array![[0.6, 0.9, -0.3], [0.1, -0.5, 0.8], [-0.7, 0.2, 0.4]];
+        let t = array![[0.0, 1.0], [1.0, 0.0], [0.0, 1.0]];
+        (x, t)
+    }
+
+    /// 故意把 W2 的梯度放大一倍的错误实现
+    struct BrokenNet(TwoLayerNet);
+
+    impl Model for BrokenNet {
+        fn predict(&self, x: &Array2<f64>) -> Array2<f64> {
+            self.0.predict(x)
+        }
+    }
+
+    impl Trainable for BrokenNet {
+        fn loss(&self, x: &Array2<f64>, t: &Array2<f64>) -> f64 {
+            self.0.loss(x, t)
+        }
+
+        fn gradient(&mut self, x: &Array2<f64>, t: &Array2<f64>) -> Vec<Array2<f64>> {
+            let mut grads = Trainable::gradient(&mut self.0, x, t);
+            grads[2] *= 2.0;
+            grads
+        }
+
+        fn params(&self) -> Vec<&Array2<f64>> {
+            self.0.params()
+        }
+
+        fn params_mut(&mut self) -> Vec<&mut Array2<f64>> {
+            self.0.params_mut()
+        }
+    }
+
+    #[test]
+    fn test_check_gradients_two_layer_net() {
+        let (x, t) = data();
+        let mut net = TwoLayerNet::with_initializer(3, 4, 2, &HeInit, 5);
+        let before = net.affine1.w.value.clone();
+
+        assert_eq!(check_gradients(&mut net, &x, &t, 1e-6), Ok(()));
+        assert_eq!(net.affine1.w.value, before);
+    }
+
+    #[test]
+    fn test_check_gradients_broken_model() {
+        let (x, t) = data();
+        let mut net = BrokenNet(TwoLayerNet::with_initializer(3, 4, 2, &HeInit, 5));
+
+        let err = check_gradients(&mut net, &x, &t, 1e-6).unwrap_err();
+        match &err {
+            GradCheckError::Mismatch {
+                param,
+                max_rel_errors,
+                ..
+            } => {
+                assert_eq!(*param, 2);
+                assert!(max_rel_errors[0] < 1e-6);
+                assert!(max_rel_errors[2] > 1e-3);
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+        assert!(err.to_string().starts_with("parameter 2 at"));
+    }
+
+    #[test]
+    fn test_gradient_check_relative_error() {
+        let a = array![[1.0, -2.0], [0.5, 0.0]];
+        assert_eq!(gradient_check(&a, &a), 0.0);
+        assert_eq!(
+            gradient_check(&Array2::zeros((2, 2)), &Array2::zeros((2, 2))),
+            0.0
+        );
+        // n = 2a 时 ‖a - n‖ / (‖a‖ + ‖n‖) = 1 / 3，与 a 的尺度无关
+        assert!((gradient_check(&a, &(&a * 2.0)) - 1.0 / 3.0).abs() < 1e-12);
+        assert!((gradient_check(&(&a * 1e-6), &(&a * 2e-6)) - 1.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_gradient_report() {
+        let (x, t) = data();
+        let mut net = TwoLayerNet::with_initializer(3, 4, 2, &HeInit, 5);
+        let report = gradient_report(&mut net, &x, &t).unwrap();
+        assert_eq!(report.len(), 4);
+        assert!(report.iter().all(|&e| e < 1e-6));
+
+        let mut broken = BrokenNet(TwoLayerNet::with_initializer(3, 4, 2, &HeInit, 5));
+        let report = gradient_report(&mut broken, &x, &t).unwrap();
+        assert!(report[0] < 1e-6 && report[1] < 1e-6 && report[3] < 1e-6);
+        assert!((report[2] - 1.0 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_numerical_gradient_output_matches_loss_gradient() {
+        use crate::chapter02::loss::cross_entropy_error;
+
+        let net = SimpleNet::new(3, 4, 2);
+        let x = array![[0.6, 0.9, -0.2], [0.1, -0.5, 0.3]];
+        let t = array![[0.0, 1.0], [1.0, 0.0]];
+
+        let custom = numerical_gradient_output(&net, &x, |y| cross_entropy_error(y, &t));
+        let standard = net.numerical_gradient(&x, &t);
+
+        for (a, b) in [
+            (&custom.w1, &standard.w1),
+            (&custom.b1, &standard.b1),
+            (&custom.w2, &standard.w2),
+            (&custom.b2, &standard.b2),
+        ] {
+            assert!(a.iter().zip(b.iter()).all(|(u, v)| (u - v).abs() < 1e-10));
+        }
+    }
+
+    #[test]
+    fn test_numerical_jacobian_linear_map() {
+        let a = array![[1.0, 2.0, 0.0], [-1.0, 0.5, 3.0]];
+        let jacobian = numerical_jacobian(|x| a.dot(x), &arr1(&[0.2, -0.4, 1.0]));
+        assert_eq!(jacobian.shape(), &[2, 3]);
+        assert!(
+            jacobian
+                .iter()
+                .zip(a.iter())
+                .all(|(j, a)| (j - a).abs() < 1e-8)
+        );
+    }
+
+    #[test]
+    fn test_richardson_beats_central_difference_on_cubic() {
+        // f(x) = Σ x³，解析梯度为 3x²；中心差分的截断误差为 h²·f'''/6 = h²
+        let f = |x: &Array<f64, Ix1>| x.mapv(|v| v.powi(3)).sum();
+        let x = arr1(&[2.0, -1.5, 0.5]);
+        let analytic = x.mapv(|v| 3.0 * v * v);
+
+        let err = |g: &Array<f64, Ix1>| (g - &analytic).mapv(f64::abs).sum();
+        let plain = err(&numerical_gradient(f, &x));
+        let richardson = err(&numerical_gradient_richardson(f, &x));
+
+        assert!(richardson < plain);
+        assert!(richardson < 1e-9);
+    }
+
+    #[test]
+    fn test_complex_step_diff_sigmoid() {
+        let sigmoid = |x: f64| 1.0 / (1.0 + (-x).exp());
+        let complex_sigmoid = |z: Complex<f64>| 1.0 / (1.0 + (-z).exp());
+
+        for x in [-3.0, -0.5, 0.0, 0.7, 4.0] {
+            let s = sigmoid(x);
+            let analytic = s * (1.0 - s);
+            let complex_err = (complex_step_diff(complex_sigmoid, x) - analytic).abs();
+            let central_err = (numerical_diff(sigmoid, x) - analytic).abs();
+
+            assert!(complex_err < 1e-15, "x = {x}: error {complex_err}");
+            assert!(complex_err < central_err);
+        }
+    }
 }
@@ -0,0 +1,108 @@
+// src/chapter02/init.rs
+use ndarray::Array2;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand_distr::{Distribution, Normal};
+
+/// 权重初始化器：根据形状 (输入数, 输出数) 和随机种子生成初始权重
+pub trait Initializer {
+    fn init(&self, shape: (usize, usize), seed: u64) -> Array2<f64>;
+}
+
+/// 固定标准差的正态分布初始化 N(0, std²)
+pub struct NormalInit {
+    pub std: f64,
+}
+
+/// Xavier 初始化：标准差为 sqrt(1 / n_in)，适合 sigmoid / tanh
+pub struct XavierInit;
+
+/// He 初始化：标准差为 sqrt(2 / n_in)，适合 ReLU
+pub struct HeInit;
+
+/// 全零初始化（一般只用于偏置）
+pub struct ZeroInit;
+
+impl Initializer for NormalInit {
+    fn init(&self, shape: (usize, usize), seed: u64) -> Array2<f64> {
+        normal_array(shape, self.std, seed)
+    }
+}
+
+impl Initializer for XavierInit {
+    fn init(&self, shape: (usize, usize), seed: u64) -> Array2<f64> {
+        let std = (1.0 / shape.0.max(1) as f64).sqrt();
+        normal_array(shape, std, seed)
+    }
+}
+
+impl Initializer for HeInit {
+    fn init(&self, shape: (usize, usize), seed: u64) -> Array2<f64> {
+        let std = (2.0 / shape.0.max(1) as f64).sqrt();
+        normal_array(shape, std, seed)
+    }
+}
+
+impl Initializer for ZeroInit {
+    fn init(&self, shape: (usize, usize), _seed: u64) -> Array2<f64> {
+        Array2::zeros(shape)
+    }
+}
+
+fn normal_array(shape: (usize, usize), std: f64, seed: u64) -> Array2<f64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(0.0, std).unwrap();
+    Array2::from_shape_fn(shape, |_| normal.sample(&mut rng))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mean_std(a: &Array2<f64>) -> (f64, f64) {
+        let mean = a.mean().unwrap();
+        let var = a.mapv(|v| (v - mean).powi(2)).mean().unwrap();
+        (mean, var.sqrt())
+    }
+
+    #[test]
+    fn test_normal_init_stats() {
+        let w = NormalInit { std: 0.5 }.init((200, 100), 42);
+        let (mean, std) = mean_std(&w);
+        assert_eq!(w.shape(), &[200, 100]);
+        assert!(mean.abs() < 0.02);
+        assert!((std - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_xavier_init_stats() {
+        let w = XavierInit.init((400, 100), 42);
+        let (mean, std) = mean_std(&w);
+        assert!(mean.abs() < 0.005);
+        assert!((std - (1.0 / 400.0_f64).sqrt()).abs() < 0.002);
+    }
+
+    #[test]
+    fn test_he_init_stats() {
+        let w = HeInit.init((400, 100), 42);
+        let (mean, std) = mean_std(&w);
+        assert!(mean.abs() < 0.005);
+        assert!((std - (2.0 / 400.0_f64).sqrt()).abs() < 0.002);
+    }
+
+    #[test]
+    fn test_zero_init() {
+        let w = ZeroInit.init((3, 4), 42);
+        assert_eq!(w.shape(), &[3, 4]);
+        assert!(w.iter().all(|&v| v == 0.0));
+    }
+
+    #[test]
+    fn test_same_seed_is_reproducible() {
+        let a = HeInit.init((5, 5), 7);
+        let b = HeInit.init((5, 5), 7);
+        let c = HeInit.init((5, 5), 8);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+}
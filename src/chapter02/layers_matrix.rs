@@ -0,0 +1,60 @@
+// src/chapter02/layers_matrix.rs
+use super::activation::softmax_matrix;
+use super::loss::cross_entropy_matrix;
+use super::matrix::Matrix;
+
+/// Matrix 版本的 Softmax-with-Loss 层：前向输出交叉熵损失，反向输出 (y - t) / batch_size
+#[derive(Default)]
+pub struct SoftmaxWithLossMatrix {
+    y: Option<Matrix>,
+    t: Option<Matrix>,
+}
+
+impl SoftmaxWithLossMatrix {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `x` 为得分（未经 softmax），`t` 为 one-hot 标签
+    pub fn forward(&mut self, x: &Matrix, t: &Matrix) -> f64 {
+        let y = softmax_matrix(x);
+        let loss = cross_entropy_matrix(&y, t);
+        self.y = Some(y);
+        self.t = Some(t.clone());
+        loss
+    }
+
+    /// 损失对输入得分的梯度（上游梯度为 1）
+    pub fn backward(&self) -> Matrix {
+        let (y, t) = match (&self.y, &self.t) {
+            (Some(y), Some(t)) => (y, t),
+            _ => panic!("SoftmaxWithLossMatrix::backward called before forward"),
+        };
+        y.sub(t).scalar_mul(1.0 / y.rows as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chapter02::grad::numerical_gradient_matrix;
+
+    #[test]
+    fn test_softmax_with_loss_matrix_gradient_check() {
+        let x = Matrix::from_vec(vec![vec![0.3, 2.9, 4.0], vec![1.0, -0.5, 0.2]]);
+        let t = Matrix::from_vec(vec![vec![0.0, 0.0, 1.0], vec![1.0, 0.0, 0.0]]);
+
+        let mut layer = SoftmaxWithLossMatrix::new();
+        let loss = layer.forward(&x, &t);
+        assert!(loss > 0.0);
+        let dx = layer.backward();
+
+        let numeric =
+            numerical_gradient_matrix(|x| SoftmaxWithLossMatrix::new().forward(x, &t), &x);
+        for i in 0..x.rows {
+            for j in 0..x.cols {
+                assert!((dx.data[i][j] - numeric.data[i][j]).abs() < 1e-4);
+            }
+        }
+    }
+}
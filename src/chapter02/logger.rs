@@ -0,0 +1,153 @@
+// src/chapter02/logger.rs
+use super::trainer::{Callback, TrainContext};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// 把每个 epoch 的指标以 NDJSON（每行一个 JSON 对象）追加写入文件，例如
+/// `{"epoch":1,"loss":0.5,"acc":0.9,"lr":0.1}`。
+///
+/// 每写一行就立即落盘，外部工具可以一边训练一边 `tail -f` 读取。
+/// 作为 `Callback` 注册到 `Trainer` 上使用；回调里写入失败时不会中断训练，
+/// 第一个错误记录在 `TrainHistory::callback_error` 中
+pub struct MetricsLogger {
+    file: File,
+    error: Option<io::Error>,
+}
+
+impl MetricsLogger {
+    /// 以追加模式打开（不存在则创建）日志文件
+    pub fn new<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { file, error: None })
+    }
+
+    /// 写入一行指标并立即刷新。`epoch` 原样写入，作为回调时从 1 开始计；
+    /// NaN、inf 不是合法的 JSON 数字，写成 null
+    pub fn log(&mut self, epoch: usize, loss: f64, acc: f64, lr: f64) -> io::Result<()> {
+        let line = format!(
+            "{{\"epoch\":{},\"loss\":{},\"acc\":{},\"lr\":{}}}\n",
+            epoch,
+            json_number(loss),
+            json_number(acc),
+            json_number(lr)
+        );
+        self.file.write_all(line.as_bytes())?;
+        self.file.flush()
+    }
+}
+
+impl Callback for MetricsLogger {
+    fn on_epoch_end(&mut self, ctx: &TrainContext) {
+        // ctx.epoch 从 0 开始，日志里的 epoch 与 TrainHistory 的摘要一样从 1 开始
+        if let Err(e) = self.log(ctx.epoch + 1, ctx.train_loss, ctx.train_acc, ctx.lr())
+            && self.error.is_none()
+        {
+            self.error = Some(e);
+        }
+    }
+
+    fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+}
+
+// JSON 不支持 NaN / inf，写成 null
+fn json_number(v: f64) -> String {
+    if v.is_finite() {
+        format!("{}", v)
+    } else {
+        "null".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chapter02::init::XavierInit;
+    use crate::chapter02::network::SimpleNet;
+    use crate::chapter02::trainer::Trainer;
+    use ndarray::array;
+
+    /// 取出形如 `"key":value` 的字段值
+    fn field<'a>(line: &'a str, key: &str) -> &'a str {
+        let start = line.find(&format!("\"{}\":", key)).unwrap() + key.len() + 3;
+        let rest = &line[start..];
+        &rest[..rest.find([',', '}']).unwrap()]
+    }
+
+    #[test]
+    fn test_logs_one_json_line_per_epoch() {
+        let path = std::env::temp_dir().join(format!("rdl_metrics_{}.ndjson", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let x = array![[0.6, 0.9], [0.9, 0.1], [0.2, 0.8], [0.8, 0.3]];
+        let t = array![[0.0, 1.0], [1.0, 0.0], [0.0, 1.0], [1.0, 0.0]];
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+        let mut trainer = Trainer::new(net, 0.5, 3, 2);
+        trainer.add_callback(Box::new(MetricsLogger::new(&path).unwrap()));
+        let history = trainer.fit(&x, &t);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 3);
+
+        for (epoch, line) in lines.iter().enumerate() {
+            assert!(line.starts_with('{') && line.ends_with('}'));
+            assert_eq!(field(line, "epoch").parse::<usize>().unwrap(), epoch + 1);
+            let loss: f64 = field(line, "loss").parse().unwrap();
+            let acc: f64 = field(line, "acc").parse().unwrap();
+            assert_eq!(loss, history.train_loss[epoch]);
+            assert_eq!(acc, history.train_acc[epoch]);
+            assert_eq!(field(line, "lr").parse::<f64>().unwrap(), 0.5);
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_records_first_write_error() {
+        // /dev/full 可以打开，但每次写入都返回 ENOSPC
+        let logger = MetricsLogger::new("/dev/full").unwrap();
+
+        let x = array![[0.6, 0.9], [0.9, 0.1]];
+        let t = array![[0.0, 1.0], [1.0, 0.0]];
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+        let mut trainer = Trainer::new(net, 0.5, 2, 2);
+        trainer.add_callback(Box::new(logger));
+        let history = trainer.fit(&x, &t);
+
+        // 写入失败不影响训练
+        assert_eq!(history.train_loss.len(), 2);
+        let error = history
+            .callback_error
+            .as_ref()
+            .expect("write error should be recorded");
+        assert_eq!(error.raw_os_error(), Some(28));
+        assert!(history.to_string().contains("warning: callback failed"));
+    }
+
+    #[test]
+    fn test_non_finite_values_are_null() {
+        assert_eq!(json_number(f64::NAN), "null");
+        assert_eq!(json_number(0.25), "0.25");
+
+        // 发散的训练写出的行仍然是合法的 JSON
+        let path =
+            std::env::temp_dir().join(format!("rdl_metrics_nan_{}.ndjson", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut logger = MetricsLogger::new(&path).unwrap();
+        logger.log(1, f64::NAN, 0.5, f64::INFINITY).unwrap();
+        logger.log(2, f64::NEG_INFINITY, 0.5, 0.1).unwrap();
+        drop(logger);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            contents,
+            "{\"epoch\":1,\"loss\":null,\"acc\":0.5,\"lr\":null}\n\
+             {\"epoch\":2,\"loss\":null,\"acc\":0.5,\"lr\":0.1}\n"
+        );
+        assert!(!contents.contains("NaN") && !contents.contains("inf"));
+    }
+}
@@ -1,5 +1,7 @@
 // src/chapter02/loss.rs
-use ndarray::{Array2, Axis};
+use super::activation::softmax;
+use super::matrix::Matrix;
+use ndarray::{Array1, Array2, Axis};
 
 pub fn mean_squared_error(y: &Array2<f64>, t: &Array2<f64>) -> f64 {
     let diff = y - t;
@@ -15,9 +17,7 @@ pub fn cross_entropy_error(y: &Array2<f64>, t: &Array2<f64>) -> f64 {
     
     // 计算 -t * log(y)，然后对每个样本求和
     let log_y = y_safe.mapv(|x| x.ln());
-    let cross_entropy = -(t * log_y).sum_axis(Axis(1)).mean().unwrap();
-    
-    cross_entropy
+    -(t * log_y).sum_axis(Axis(1)).mean().unwrap()
 }
 
 // 针对 one-hot 编码优化的交叉熵函数
@@ -38,6 +38,160 @@ pub fn cross_entropy_error_optimized(y: &Array2<f64>, t: &Array2<f64>) -> f64 {
     -sum / batch_size
 }
 
+/// 直接从 logits（softmax 之前的输出）计算交叉熵，用 log-sum-exp 保证数值稳定
+pub fn cross_entropy_from_logits(logits: &Array2<f64>, t: &Array2<f64>) -> f64 {
+    let batch_size = logits.nrows() as f64;
+    let mut sum = 0.0;
+
+    for (z_row, t_row) in logits.outer_iter().zip(t.outer_iter()) {
+        let max_val = z_row.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let log_sum_exp = z_row.iter().map(|z| (z - max_val).exp()).sum::<f64>().ln() + max_val;
+        for (z, t_val) in z_row.iter().zip(t_row.iter()) {
+            sum += t_val * (log_sum_exp - z);
+        }
+    }
+
+    sum / batch_size
+}
+
+/// softmax + 交叉熵对 logits 的梯度：(softmax(logits) - t) / batch_size
+pub fn softmax_cross_entropy_backward(logits: &Array2<f64>, t: &Array2<f64>) -> Array2<f64> {
+    let batch_size = logits.nrows() as f64;
+    (softmax(logits) - t) / batch_size
+}
+
+/// 多类别 hinge 损失（SVM 损失）：每个样本对所有错误类别 j 求 max(0, s_j - s_y + margin) 之和，
+/// 再对批次取平均。t 为整数标签，scores 为未经 softmax 的得分
+pub fn hinge_loss(scores: &Array2<f64>, t: &Array1<usize>, margin: f64) -> f64 {
+    assert_eq!(scores.nrows(), t.len());
+    let mut sum = 0.0;
+
+    for (row, &y) in scores.outer_iter().zip(t.iter()) {
+        for (j, &s) in row.iter().enumerate() {
+            if j != y {
+                sum += (s - row[y] + margin).max(0.0);
+            }
+        }
+    }
+
+    sum / scores.nrows() as f64
+}
+
+/// hinge 损失对 scores 的梯度：违反间隔的错误类别 j 处为 +1，
+/// 正确类别处为 -(违反间隔的类别数)，最后除以 batch_size
+pub fn hinge_loss_backward(scores: &Array2<f64>, t: &Array1<usize>, margin: f64) -> Array2<f64> {
+    assert_eq!(scores.nrows(), t.len());
+    let batch_size = scores.nrows() as f64;
+    let mut grad = Array2::zeros(scores.raw_dim());
+
+    for ((row, mut g), &y) in scores.outer_iter().zip(grad.outer_iter_mut()).zip(t.iter()) {
+        for (j, &s) in row.iter().enumerate() {
+            if j != y && s - row[y] + margin > 0.0 {
+                g[j] += 1.0;
+                g[y] -= 1.0;
+            }
+        }
+    }
+
+    grad / batch_size
+}
+
+/// 损失函数对象：`forward` 计算损失值，`backward` 返回损失对网络输出 y 的梯度
+pub trait Loss {
+    fn forward(&self, y: &Array2<f64>, t: &Array2<f64>) -> f64;
+    fn backward(&self, y: &Array2<f64>, t: &Array2<f64>) -> Array2<f64>;
+}
+
+/// 均方误差，对所有元素取平均（与 `mean_squared_error` 一致）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MeanSquaredError;
+
+impl Loss for MeanSquaredError {
+    fn forward(&self, y: &Array2<f64>, t: &Array2<f64>) -> f64 {
+        mean_squared_error(y, t)
+    }
+
+    fn backward(&self, y: &Array2<f64>, t: &Array2<f64>) -> Array2<f64> {
+        (y - t) * (2.0 / y.len() as f64)
+    }
+}
+
+/// softmax + 交叉熵，y 为 softmax 之前的 logits
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SoftmaxCrossEntropy;
+
+impl Loss for SoftmaxCrossEntropy {
+    fn forward(&self, y: &Array2<f64>, t: &Array2<f64>) -> f64 {
+        cross_entropy_from_logits(y, t)
+    }
+
+    fn backward(&self, y: &Array2<f64>, t: &Array2<f64>) -> Array2<f64> {
+        softmax_cross_entropy_backward(y, t)
+    }
+}
+
+/// 多个损失的加权和（多任务 / 辅助损失），如 `CE + 0.1 * MSE`。
+/// 各损失作用在同一个输出 y 上，反向传播时梯度按相同权重相加
+#[derive(Default)]
+pub struct CombinedLoss {
+    pub losses: Vec<(Box<dyn Loss>, f64)>,
+}
+
+impl CombinedLoss {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 追加一项权重为 `weight` 的损失
+    pub fn with(mut self, loss: impl Loss + 'static, weight: f64) -> Self {
+        self.losses.push((Box::new(loss), weight));
+        self
+    }
+}
+
+impl Loss for CombinedLoss {
+    fn forward(&self, y: &Array2<f64>, t: &Array2<f64>) -> f64 {
+        self.losses
+            .iter()
+            .map(|(loss, w)| w * loss.forward(y, t))
+            .sum()
+    }
+
+    fn backward(&self, y: &Array2<f64>, t: &Array2<f64>) -> Array2<f64> {
+        let mut grad = Array2::zeros(y.raw_dim());
+        for (loss, w) in &self.losses {
+            grad.scaled_add(*w, &loss.backward(y, t));
+        }
+        grad
+    }
+}
+
+// Matrix 版本的交叉熵（t 为 one-hot，只计算真实标签位置）
+pub fn cross_entropy_matrix(y: &Matrix, t: &Matrix) -> f64 {
+    let losses = cross_entropy_matrix_per_sample(y, t);
+    losses.iter().sum::<f64>() / y.rows as f64
+}
+
+// 返回每个样本各自的交叉熵损失
+pub fn cross_entropy_matrix_per_sample(y: &Matrix, t: &Matrix) -> Vec<f64> {
+    assert_eq!(y.shape(), t.shape());
+    let delta = 1e-7;
+
+    y.data
+        .iter()
+        .zip(t.data.iter())
+        .map(|(y_row, t_row)| {
+            let mut sum = 0.0;
+            for (y_val, t_val) in y_row.iter().zip(t_row.iter()) {
+                if *t_val == 1.0 {
+                    sum += (*y_val + delta).ln();
+                }
+            }
+            -sum
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,4 +224,95 @@ mod tests {
         let loss_standard = cross_entropy_error(&y, &t);
         assert!((loss - loss_standard).abs() < 1e-10);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_cross_entropy_matrix_matches_ndarray() {
+        let y = array![[0.1, 0.7, 0.2], [0.6, 0.3, 0.1], [0.05, 0.05, 0.9]];
+        let t = array![[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]];
+        let expected = cross_entropy_error(&y, &t);
+
+        let loss = cross_entropy_matrix(&Matrix::from(&y), &Matrix::from(&t));
+        assert!((loss - expected).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_cross_entropy_matrix_per_sample() {
+        let y = array![[0.1, 0.9], [0.8, 0.2]];
+        let t = array![[0.0, 1.0], [1.0, 0.0]];
+        let losses = cross_entropy_matrix_per_sample(&Matrix::from(&y), &Matrix::from(&t));
+
+        assert_eq!(losses.len(), 2);
+        assert!((losses[0] + (0.9_f64 + 1e-7).ln()).abs() < 1e-10);
+        assert!((losses[1] + (0.8_f64 + 1e-7).ln()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_cross_entropy_from_logits_matches_softmax() {
+        let logits = array![[2.0, 1.0, 0.1], [0.5, 2.5, -1.0]];
+        let t = array![[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        let expected = cross_entropy_error(&softmax(&logits), &t);
+        assert!((cross_entropy_from_logits(&logits, &t) - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_softmax_cross_entropy_backward_gradient_check() {
+        use crate::chapter02::grad::numerical_gradient;
+
+        let logits = array![[2.0, 1.0, 0.1], [0.5, 2.5, -1.0]];
+        let t = array![[1.0, 0.0, 0.0], [0.0, 0.0, 1.0]];
+
+        let analytic = softmax_cross_entropy_backward(&logits, &t);
+        let numeric = numerical_gradient(|z| cross_entropy_from_logits(z, &t), &logits);
+
+        for (a, n) in analytic.iter().zip(numeric.iter()) {
+            assert!((a - n).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_hinge_loss_value() {
+        let scores = array![[3.2, 5.1, -1.7], [1.3, 4.9, 2.0]];
+        let t = array![0, 1];
+
+        // 样本 0：max(0, 5.1 - 3.2 + 1) + max(0, -1.7 - 3.2 + 1) = 2.9
+        // 样本 1：max(0, 1.3 - 4.9 + 1) + max(0, 2.0 - 4.9 + 1) = 0
+        let loss = hinge_loss(&scores, &t, 1.0);
+        assert!((loss - 2.9 / 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_hinge_loss_backward_gradient_check() {
+        use crate::chapter02::grad::numerical_gradient;
+
+        // 避开 s_j - s_y + margin = 0 的不可导点
+        let scores = array![[3.2, 5.1, -1.7], [1.3, 4.9, 2.0], [0.2, 0.4, 0.1]];
+        let t = array![0, 1, 2];
+
+        let analytic = hinge_loss_backward(&scores, &t, 1.0);
+        let numeric = numerical_gradient(|s| hinge_loss(s, &t, 1.0), &scores);
+
+        for (a, n) in analytic.iter().zip(numeric.iter()) {
+            assert!((a - n).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_combined_loss() {
+        use crate::chapter02::grad::numerical_gradient;
+
+        let y = array![[2.0, 1.0, 0.1], [0.5, 2.5, -1.0]];
+        let t = array![[1.0, 0.0, 0.0], [0.0, 0.0, 1.0]];
+        let combined = CombinedLoss::new()
+            .with(MeanSquaredError, 0.5)
+            .with(SoftmaxCrossEntropy, 0.5);
+
+        let expected = 0.5 * mean_squared_error(&y, &t) + 0.5 * cross_entropy_from_logits(&y, &t);
+        assert!((combined.forward(&y, &t) - expected).abs() < 1e-12);
+
+        let analytic = combined.backward(&y, &t);
+        let numeric = numerical_gradient(|z| combined.forward(z, &t), &y);
+        for (a, n) in analytic.iter().zip(numeric.iter()) {
+            assert!((a - n).abs() < 1e-6);
+        }
+    }
+}
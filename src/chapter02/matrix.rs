@@ -1,4 +1,6 @@
 // src/chapter02/matrix.rs
+use ndarray::{Array1, Array2};
+
 #[derive(Debug, Clone)]
 pub struct Matrix {
     pub data: Vec<Vec<f64>>,
@@ -21,13 +23,75 @@ impl Matrix {
         Self { data, rows, cols }
     }
 
+    /// 把一维数组转成 1×n 的行矩阵（例如偏置）
+    pub fn from_row(v: &Array1<f64>) -> Self {
+        Self {
+            data: vec![v.to_vec()],
+            rows: 1,
+            cols: v.len(),
+        }
+    }
+
+    /// 把一维数组转成 n×1 的列矩阵
+    pub fn from_col(v: &Array1<f64>) -> Self {
+        Self {
+            data: v.iter().map(|&x| vec![x]).collect(),
+            rows: v.len(),
+            cols: 1,
+        }
+    }
+
+    /// 由按行展开的一维数据构造 rows×cols 矩阵，是 `to_flat` 的逆操作
+    pub fn from_flat(v: Vec<f64>, rows: usize, cols: usize) -> Self {
+        assert_eq!(v.len(), rows * cols, "Matrix from_flat: length mismatch");
+        Self {
+            data: (0..rows)
+                .map(|i| v[i * cols..(i + 1) * cols].to_vec())
+                .collect(),
+            rows,
+            cols,
+        }
+    }
+
+    /// 按行展开成一维数据，便于在不依赖 ndarray 的情况下做序列化
+    pub fn to_flat(&self) -> Vec<f64> {
+        self.data.iter().flatten().copied().collect()
+    }
+
+    /// 矩阵乘法。先把 other 转置，使内层循环变成两段连续内存的点积，
+    /// 并累加到局部变量 sum 里最后写入一次，这样 LLVM 才能把内层循环向量化
     pub fn dot(&self, other: &Matrix) -> Matrix {
         assert_eq!(self.cols, other.rows);
+        let other_t = other.transpose();
         let mut result = Matrix::new(self.rows, other.cols, 0.0);
+        for (row, out) in self.data.iter().zip(result.data.iter_mut()) {
+            for (col, r) in other_t.data.iter().zip(out.iter_mut()) {
+                let sum: f64 = row.iter().zip(col).map(|(a, b)| a * b).sum();
+                *r = sum;
+            }
+        }
+        result
+    }
+
+    pub fn transpose(&self) -> Matrix {
+        let mut result = Matrix::new(self.cols, self.rows, 0.0);
         for i in 0..self.rows {
-            for j in 0..other.cols {
-                for k in 0..self.cols {
-                    result.data[i][j] += self.data[i][k] * other.data[k][j];
+            for j in 0..self.cols {
+                result.data[j][i] = self.data[i][j];
+            }
+        }
+        result
+    }
+
+    /// 直接计算 selfᵀ · other，不生成转置矩阵（如 Affine 反向传播中的 dW = xᵀ · dout）
+    pub fn transpose_dot(&self, other: &Matrix) -> Matrix {
+        assert_eq!(self.rows, other.rows);
+        let mut result = Matrix::new(self.cols, other.cols, 0.0);
+        for k in 0..self.rows {
+            for i in 0..self.cols {
+                let a = self.data[k][i];
+                for j in 0..other.cols {
+                    result.data[i][j] += a * other.data[k][j];
                 }
             }
         }
@@ -64,6 +128,56 @@ impl Matrix {
         }
     }
 
+    /// 逐元素相减，要求形状一致
+    pub fn sub(&self, other: &Matrix) -> Matrix {
+        assert_eq!(self.shape(), other.shape(), "Matrix sub: shape mismatch");
+        let mut result = self.clone();
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                result.data[i][j] -= other.data[i][j];
+            }
+        }
+        result
+    }
+
+    /// 纵向拼接：other 的各行接在 self 下面，要求列数一致
+    pub fn concat_rows(&self, other: &Matrix) -> Matrix {
+        assert_eq!(
+            self.cols, other.cols,
+            "Matrix concat_rows: column count mismatch"
+        );
+        let mut data = self.data.clone();
+        data.extend(other.data.iter().cloned());
+        Matrix {
+            data,
+            rows: self.rows + other.rows,
+            cols: self.cols,
+        }
+    }
+
+    /// 横向拼接：每一行后面接上 other 对应的行，要求行数一致
+    pub fn concat_cols(&self, other: &Matrix) -> Matrix {
+        assert_eq!(
+            self.rows, other.rows,
+            "Matrix concat_cols: row count mismatch"
+        );
+        Matrix {
+            data: self
+                .data
+                .iter()
+                .zip(&other.data)
+                .map(|(a, b)| [a.as_slice(), b.as_slice()].concat())
+                .collect(),
+            rows: self.rows,
+            cols: self.cols + other.cols,
+        }
+    }
+
+    /// 每个元素乘以标量 k
+    pub fn scalar_mul(&self, k: f64) -> Matrix {
+        self.map(|x| x * k)
+    }
+
     pub fn map<F>(&self, func: F) -> Matrix
     where
         F: Fn(f64) -> f64,
@@ -76,7 +190,153 @@ impl Matrix {
         )
     }
 
+    /// 逐元素截断到 [min, max]
+    pub fn clip(&self, min: f64, max: f64) -> Matrix {
+        self.map(|x| x.clamp(min, max))
+    }
+
     pub fn shape(&self) -> (usize, usize) {
         (self.rows, self.cols)
     }
 }
+
+// 与 ndarray 互相转换，方便两种实现混用
+impl From<&Array2<f64>> for Matrix {
+    fn from(a: &Array2<f64>) -> Self {
+        Matrix {
+            data: a.outer_iter().map(|row| row.to_vec()).collect(),
+            rows: a.nrows(),
+            cols: a.ncols(),
+        }
+    }
+}
+
+impl From<&Matrix> for Array2<f64> {
+    fn from(m: &Matrix) -> Self {
+        Array2::from_shape_fn((m.rows, m.cols), |(i, j)| m.data[i][j])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clip() {
+        let m = Matrix::from_vec(vec![vec![-2.0, 0.5], vec![1.0, 3.0]]);
+        let clipped = m.clip(0.0, 1.0);
+        assert_eq!(clipped.data, vec![vec![0.0, 0.5], vec![1.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_from_row_and_col() {
+        let v = Array1::from(vec![1.0, 2.0, 3.0]);
+
+        let row = Matrix::from_row(&v);
+        assert_eq!(row.shape(), (1, 3));
+        assert_eq!(row.data, vec![vec![1.0, 2.0, 3.0]]);
+
+        let col = Matrix::from_col(&v);
+        assert_eq!(col.shape(), (3, 1));
+        assert_eq!(col.data, vec![vec![1.0], vec![2.0], vec![3.0]]);
+    }
+
+    #[test]
+    fn test_sub_and_scalar_mul() {
+        let a = Matrix::from_vec(vec![vec![3.0, 4.0], vec![5.0, 6.0]]);
+        let b = Matrix::from_vec(vec![vec![1.0, 1.0], vec![2.0, 2.0]]);
+        assert_eq!(a.sub(&b).data, vec![vec![2.0, 3.0], vec![3.0, 4.0]]);
+        assert_eq!(a.scalar_mul(0.5).data, vec![vec![1.5, 2.0], vec![2.5, 3.0]]);
+    }
+
+    #[test]
+    fn test_transpose_dot() {
+        let a = Matrix::from_vec(vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]]);
+        let b = Matrix::from_vec(vec![
+            vec![1.0, 0.0, -1.0, 2.0],
+            vec![0.5, 1.0, 0.0, -2.0],
+            vec![2.0, -1.0, 1.0, 0.0],
+        ]);
+
+        let fused = a.transpose_dot(&b);
+        assert_eq!(fused.shape(), (2, 4));
+        assert_eq!(fused.data, a.transpose().dot(&b).data);
+    }
+
+    #[test]
+    fn test_flat_round_trip() {
+        let m = Matrix::from_vec(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+        let flat = m.to_flat();
+        assert_eq!(flat, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        let back = Matrix::from_flat(flat, 2, 3);
+        assert_eq!(back.shape(), (2, 3));
+        assert_eq!(back.data, m.data);
+    }
+
+    #[test]
+    fn test_concat_rows_and_cols() {
+        let a = Matrix::from_vec(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+        let b = Matrix::from_vec(vec![vec![5.0, 6.0]]);
+        let c = Matrix::from_vec(vec![vec![7.0], vec![8.0]]);
+
+        let rows = a.concat_rows(&b);
+        assert_eq!(rows.shape(), (3, 2));
+        assert_eq!(
+            rows.data,
+            vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]]
+        );
+
+        let cols = a.concat_cols(&c);
+        assert_eq!(cols.shape(), (2, 3));
+        assert_eq!(cols.data, vec![vec![1.0, 2.0, 7.0], vec![3.0, 4.0, 8.0]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "concat_rows: column count mismatch")]
+    fn test_concat_rows_mismatch() {
+        let a = Matrix::new(2, 2, 0.0);
+        a.concat_rows(&Matrix::new(1, 3, 0.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "concat_cols: row count mismatch")]
+    fn test_concat_cols_mismatch() {
+        let a = Matrix::new(2, 2, 0.0);
+        a.concat_cols(&Matrix::new(3, 1, 0.0));
+    }
+
+    /// 原来的三重循环实现，作为 `dot` 的对照
+    fn naive_dot(a: &Matrix, b: &Matrix) -> Matrix {
+        let mut result = Matrix::new(a.rows, b.cols, 0.0);
+        for i in 0..a.rows {
+            for j in 0..b.cols {
+                for k in 0..a.cols {
+                    result.data[i][j] += a.data[i][k] * b.data[k][j];
+                }
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_dot_matches_naive() {
+        let a = Matrix::from_vec(
+            (0..7)
+                .map(|i| (0..13).map(|k| ((i * 13 + k) as f64).sin()).collect())
+                .collect(),
+        );
+        let b = Matrix::from_vec(
+            (0..13)
+                .map(|k| (0..5).map(|j| ((k * 5 + j) as f64).cos()).collect())
+                .collect(),
+        );
+
+        let fast = a.dot(&b);
+        let expected = naive_dot(&a, &b);
+        assert_eq!(fast.shape(), (7, 5));
+        for (x, y) in fast.to_flat().iter().zip(expected.to_flat()) {
+            assert!((x - y).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,342 @@
+// src/chapter02/metrics.rs
+use ndarray::{Array1, Array2, ArrayView1};
+
+/// 返回每一行最大值所在的列下标（即每个样本的预测类别）
+pub fn argmax_rows(y: &Array2<f64>) -> Array1<usize> {
+    y.outer_iter().map(argmax).collect()
+}
+
+/// 只需要预测类别时直接对 logits 取 argmax。
+///
+/// softmax 对每一行是严格单调的（exp 单调递增，再除以同一个正数），
+/// 所以结果与 `argmax_rows(&softmax(logits))` 相同，但省去了 exp 和归一化，
+/// 适合在大批量评估时使用
+pub fn predict_argmax(logits: &Array2<f64>) -> Array1<usize> {
+    argmax_rows(logits)
+}
+
+fn argmax(row: ArrayView1<f64>) -> usize {
+    row.iter()
+        .enumerate()
+        .fold((0, f64::NEG_INFINITY), |(best_i, best_v), (i, &v)| {
+            if v > best_v { (i, v) } else { (best_i, best_v) }
+        })
+        .0
+}
+
+/// 把整数标签编码为 one-hot 矩阵（f64，可直接传给损失函数），形状为 (样本数, num_classes)
+pub fn to_one_hot(labels: &Array1<usize>, num_classes: usize) -> Array2<f64> {
+    let mut one_hot = Array2::zeros((labels.len(), num_classes));
+    for (i, &label) in labels.iter().enumerate() {
+        assert!(
+            label < num_classes,
+            "to_one_hot: label {} out of range for {} classes",
+            label,
+            num_classes
+        );
+        one_hot[[i, label]] = 1.0;
+    }
+    one_hot
+}
+
+/// 预测类别与真实类别一致的比例
+pub fn accuracy(pred: &Array1<usize>, truth: &Array1<usize>) -> f64 {
+    assert_eq!(pred.len(), truth.len());
+    if pred.is_empty() {
+        return 0.0;
+    }
+
+    let correct = pred
+        .iter()
+        .zip(truth.iter())
+        .filter(|(p, t)| p == t)
+        .count();
+    correct as f64 / pred.len() as f64
+}
+
+/// 直接由得分（或概率）矩阵和整数标签计算准确率，逐行求 argmax 并比较，不生成中间数组
+pub fn accuracy_from_logits(logits: &Array2<f64>, labels: &Array1<usize>) -> f64 {
+    assert_eq!(logits.nrows(), labels.len());
+    if labels.is_empty() {
+        return 0.0;
+    }
+
+    let correct = logits
+        .outer_iter()
+        .zip(labels.iter())
+        .filter(|(row, label)| argmax(row.view()) == **label)
+        .count();
+    correct as f64 / labels.len() as f64
+}
+
+/// 计算准确率，但跳过真实标签等于 `ignore_label` 的样本（分子分母都不计入）。
+/// 如果所有样本都被忽略，返回 0.0
+pub fn accuracy_ignoring(pred: &Array1<usize>, truth: &Array1<usize>, ignore_label: usize) -> f64 {
+    assert_eq!(pred.len(), truth.len());
+
+    let (correct, total) = pred
+        .iter()
+        .zip(truth.iter())
+        .filter(|&(_, &t)| t != ignore_label)
+        .fold((0usize, 0usize), |(correct, total), (p, t)| {
+            (correct + (p == t) as usize, total + 1)
+        });
+
+    if total == 0 {
+        0.0
+    } else {
+        correct as f64 / total as f64
+    }
+}
+
+/// 可靠性图（reliability diagram）的数据：把 [0, 1] 等宽分成 `bins` 个区间，
+/// 返回每个区间的 (平均置信度, 准确率, 样本数)。
+///
+/// 区间左闭右开，置信度为 1.0 的样本计入最后一个区间；空区间返回 (0.0, 0.0, 0)
+pub fn reliability_bins(
+    confidences: &Array1<f64>,
+    correct: &Array1<bool>,
+    bins: usize,
+) -> Vec<(f64, f64, usize)> {
+    assert_eq!(confidences.len(), correct.len());
+    assert!(bins > 0, "reliability_bins: bins must be positive");
+
+    let mut sums = vec![(0.0, 0usize, 0usize); bins];
+    for (&conf, &ok) in confidences.iter().zip(correct.iter()) {
+        let idx = ((conf * bins as f64).floor() as usize).min(bins - 1);
+        let (conf_sum, hits, count) = &mut sums[idx];
+        *conf_sum += conf;
+        *hits += ok as usize;
+        *count += 1;
+    }
+
+    sums.into_iter()
+        .map(|(conf_sum, hits, count)| {
+            if count == 0 {
+                (0.0, 0.0, 0)
+            } else {
+                (conf_sum / count as f64, hits as f64 / count as f64, count)
+            }
+        })
+        .collect()
+}
+
+/// 风险-覆盖曲线（选择性分类）：置信度阈值 τ 从 0 到 1 均匀取 `steps` 个值，
+/// 只对置信度 >= τ 的样本作答、其余放弃，返回每个阈值下的 (覆盖率, 作答样本上的准确率)。
+///
+/// 阈值升高时覆盖率单调不增；如果置信度有意义，准确率会随之上升。
+/// 没有样本被覆盖时准确率记为 0.0（与 `accuracy` 对空输入的约定一致）
+pub fn risk_coverage_curve(
+    confidences: &Array1<f64>,
+    correct: &Array1<bool>,
+    steps: usize,
+) -> Vec<(f64, f64)> {
+    assert_eq!(confidences.len(), correct.len());
+    assert!(steps >= 2, "risk_coverage_curve: steps must be at least 2");
+    let n = confidences.len();
+
+    (0..steps)
+        .map(|k| {
+            let threshold = k as f64 / (steps - 1) as f64;
+            let (covered, hits) = confidences
+                .iter()
+                .zip(correct.iter())
+                .filter(|&(&conf, _)| conf >= threshold)
+                .fold((0usize, 0usize), |(covered, hits), (_, &ok)| {
+                    (covered + 1, hits + ok as usize)
+                });
+            if covered == 0 {
+                (0.0, 0.0)
+            } else {
+                (covered as f64 / n as f64, hits as f64 / covered as f64)
+            }
+        })
+        .collect()
+}
+
+/// 期望校准误差（ECE）：各区间 |准确率 - 平均置信度| 按样本数加权的平均值。
+/// 完美校准的模型 ECE 为 0，越大说明置信度与实际准确率偏差越大
+pub fn expected_calibration_error(
+    confidences: &Array1<f64>,
+    correct: &Array1<bool>,
+    bins: usize,
+) -> f64 {
+    if confidences.is_empty() {
+        return 0.0;
+    }
+
+    let total = confidences.len() as f64;
+    reliability_bins(confidences, correct, bins)
+        .into_iter()
+        .map(|(conf, acc, count)| (acc - conf).abs() * count as f64 / total)
+        .sum()
+}
+
+/// 死亡 ReLU 的比例：activations 形状为 (batch, hidden)，某一列（隐藏单元）
+/// 在整个批次上都输出 0 即视为死亡。没有隐藏单元时返回 0
+pub fn dead_relu_fraction(activations: &Array2<f64>) -> f64 {
+    if activations.ncols() == 0 {
+        return 0.0;
+    }
+    let dead = activations
+        .columns()
+        .into_iter()
+        .filter(|col| col.iter().all(|&v| v == 0.0))
+        .count();
+    dead as f64 / activations.ncols() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_to_one_hot() {
+        let one_hot = to_one_hot(&array![0, 2, 1], 3);
+        assert_eq!(
+            one_hot,
+            array![[1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]]
+        );
+        assert_eq!(argmax_rows(&one_hot), array![0, 2, 1]);
+    }
+
+    #[test]
+    fn test_argmax_rows() {
+        let y = array![[0.1, 0.7, 0.2], [0.5, 0.3, 0.2], [0.0, 0.0, 1.0]];
+        assert_eq!(argmax_rows(&y), array![1, 0, 2]);
+    }
+
+    #[test]
+    fn test_accuracy() {
+        let truth = array![0, 1, 2, 1];
+        let pred = array![0, 2, 2, 1];
+        assert!((accuracy(&pred, &truth) - 0.75).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_accuracy_from_logits() {
+        // 第一行 argmax 为 2（正确），第二行 argmax 为 0（标签是 1，错误）
+        let logits = array![[-1.0, 0.5, 3.0], [2.0, 1.5, -0.5]];
+        let labels = array![2, 1];
+        assert_eq!(accuracy_from_logits(&logits, &labels), 0.5);
+        assert_eq!(
+            accuracy_from_logits(&logits, &labels),
+            accuracy(&argmax_rows(&logits), &labels)
+        );
+    }
+
+    #[test]
+    fn test_accuracy_ignoring() {
+        // 标签 255 表示"未知"，不参与统计
+        let truth = array![0, 1, 255, 2, 255, 1];
+        let pred = array![0, 2, 1, 2, 0, 1];
+        // 有效样本: (0,0) (2,1) (2,2) (1,1) -> 3/4
+        assert!((accuracy_ignoring(&pred, &truth, 255) - 0.75).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_accuracy_ignoring_all_ignored() {
+        let truth = array![3, 3];
+        let pred = array![3, 0];
+        assert_eq!(accuracy_ignoring(&pred, &truth, 3), 0.0);
+    }
+
+    #[test]
+    fn test_reliability_bins() {
+        let confidences = array![0.05, 0.15, 0.55, 0.65, 0.95, 1.0];
+        let correct = array![false, false, true, false, true, true];
+        let bins = reliability_bins(&confidences, &correct, 2);
+
+        assert_eq!(bins.len(), 2);
+        // [0, 0.5): 0.05, 0.15 都错
+        assert_eq!(bins[0].2, 2);
+        assert!((bins[0].0 - 0.1).abs() < 1e-12);
+        assert_eq!(bins[0].1, 0.0);
+        // [0.5, 1]: 0.55 0.65 0.95 1.0 中 3 个正确
+        assert_eq!(bins[1].2, 4);
+        assert!((bins[1].0 - 0.7875).abs() < 1e-12);
+        assert!((bins[1].1 - 0.75).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_reliability_bins_empty_bin() {
+        let bins = reliability_bins(&array![0.9], &array![true], 4);
+        assert_eq!(bins[0], (0.0, 0.0, 0));
+        assert_eq!(bins[3], (0.9, 1.0, 1));
+    }
+
+    #[test]
+    fn test_ece_perfectly_calibrated() {
+        // 每个区间的置信度取区间中点 c，20 个样本中恰好 20c 个正确
+        let mut confidences = Vec::new();
+        let mut correct = Vec::new();
+        for i in 0..10 {
+            let c = (i as f64 + 0.5) / 10.0;
+            let hits = (c * 20.0).round() as usize;
+            for j in 0..20 {
+                confidences.push(c);
+                correct.push(j < hits);
+            }
+        }
+        let ece =
+            expected_calibration_error(&Array1::from(confidences), &Array1::from(correct), 10);
+        assert!(ece < 1e-12);
+    }
+
+    #[test]
+    fn test_ece_overconfident() {
+        // 置信度 0.95，但只有一半正确
+        let confidences = Array1::from_elem(100, 0.95);
+        let correct = Array1::from_shape_fn(100, |i| i % 2 == 0);
+        let ece = expected_calibration_error(&confidences, &correct, 10);
+        assert!((ece - 0.45).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_risk_coverage_curve() {
+        // 置信度 0.00 .. 0.99；置信度高于 0.5 的样本全部正确，其余只有三分之一正确
+        let confidences = Array1::from_shape_fn(100, |i| i as f64 / 100.0);
+        let correct = Array1::from_shape_fn(100, |i| i >= 50 || i % 3 == 0);
+
+        let curve = risk_coverage_curve(&confidences, &correct, 11);
+        assert_eq!(curve.len(), 11);
+        assert_eq!(
+            curve[0],
+            (1.0, correct.iter().filter(|&&c| c).count() as f64 / 100.0)
+        );
+
+        for pair in curve.windows(2) {
+            assert!(pair[1].0 <= pair[0].0);
+        }
+        // τ = 0.5 之后只剩全部正确的样本
+        assert!(curve[5].1 > curve[0].1);
+        assert_eq!(curve[5], (0.5, 1.0));
+        // τ = 1.0 时没有样本被覆盖
+        assert_eq!(curve[10], (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_dead_relu_fraction() {
+        // 下标 1、4 两列在整个批次上都为 0；下标 2 列只有部分样本为 0，不算死亡
+        let activations = array![
+            [0.5, 0.0, 0.0, 1.2, 0.0],
+            [0.1, 0.0, 0.3, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 2.0, 0.0],
+        ];
+        assert_eq!(dead_relu_fraction(&activations), 0.4);
+        assert_eq!(dead_relu_fraction(&Array2::ones((2, 3))), 0.0);
+        assert_eq!(dead_relu_fraction(&Array2::zeros((2, 0))), 0.0);
+    }
+
+    #[test]
+    fn test_predict_argmax_matches_softmax() {
+        use crate::chapter02::activation::softmax;
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let logits = Array2::from_shape_fn((200, 10), |_| rng.random_range(-20.0..20.0));
+        assert_eq!(predict_argmax(&logits), argmax_rows(&softmax(&logits)));
+    }
+}
@@ -1,6 +1,18 @@
 pub mod activation;
+pub mod early_stopping;
+pub mod ensemble;
 pub mod grad;
+pub mod init;
+pub mod layers_matrix;
+pub mod logger;
 pub mod loss;
 pub mod matrix;
+pub mod metrics;
+pub mod model;
 pub mod network;
+pub mod optimizer;
+pub mod scheduler;
+pub mod serialize;
 pub mod train_simple;
+pub mod trainer;
+pub mod visualize;
@@ -0,0 +1,378 @@
+// src/chapter02/model.rs
+use super::metrics::argmax_rows;
+use super::network::{SimpleNet, SimpleNetMatrix};
+use ndarray::{Array1, Array2};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::ops::Index;
+
+/// 统一的推理接口：无论内部用 ndarray 还是 Matrix 实现，对外都以 Array2 作为输入输出，
+/// 这样绘图 / 评估代码只需要依赖 `Model`，不必关心具体是哪个后端
+pub trait Model {
+    fn predict(&self, x: &Array2<f64>) -> Array2<f64>;
+}
+
+impl Model for SimpleNet {
+    fn predict(&self, x: &Array2<f64>) -> Array2<f64> {
+        SimpleNet::predict(self, x)
+    }
+}
+
+impl Model for SimpleNetMatrix {
+    fn predict(&self, x: &Array2<f64>) -> Array2<f64> {
+        self.predict_ndarray(x)
+    }
+}
+
+/// 可训练的模型：在 `Model` 的基础上提供损失和梯度，供 `Trainer` 使用
+pub trait Trainable: Model {
+    fn loss(&self, x: &Array2<f64>, t: &Array2<f64>) -> f64;
+
+    /// 计算损失对每个参数的梯度，顺序与 `params_mut` 一致
+    fn gradient(&mut self, x: &Array2<f64>, t: &Array2<f64>) -> Vec<Array2<f64>>;
+
+    /// 全部可训练参数，顺序与 `params_mut` 一致
+    fn params(&self) -> Vec<&Array2<f64>>;
+
+    fn params_mut(&mut self) -> Vec<&mut Array2<f64>>;
+
+    /// 每个参数的名字（如 "w1"），顺序与 `params` 一致；默认为 "param0"、"param1"……
+    fn param_names(&self) -> Vec<String> {
+        (0..self.params().len())
+            .map(|i| format!("param{}", i))
+            .collect()
+    }
+
+    /// 带名字的参数，供日志、序列化等按名字统一处理所有模型，例如 `net.named_params()["w1"]`
+    fn named_params(&self) -> Params<&Array2<f64>> {
+        self.param_names().into_iter().zip(self.params()).collect()
+    }
+
+    /// 带名字的梯度，名字与 `named_params` 一致
+    fn named_gradient(&mut self, x: &Array2<f64>, t: &Array2<f64>) -> Params<Array2<f64>> {
+        let grads = self.gradient(x, t);
+        self.param_names().into_iter().zip(grads).collect()
+    }
+}
+
+/// 按名字查找的参数表，保持 `Trainable::params` 的顺序。
+///
+/// `params` / `params_mut` 仍然返回按下标排列的列表：优化器按下标保存每个参数的状态，
+/// 不需要名字；按名字访问（日志、序列化、调试）时用 `named_params` 得到这个表
+#[derive(Debug, Clone, PartialEq)]
+pub struct Params<T> {
+    entries: Vec<(String, T)>,
+}
+
+impl<T> Params<T> {
+    pub fn get(&self, name: &str) -> Option<&T> {
+        self.entries.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut T> {
+        self.entries
+            .iter_mut()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(n, _)| n.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.entries.iter().map(|(n, v)| (n.as_str(), v))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// 名字必须唯一
+impl<T> FromIterator<(String, T)> for Params<T> {
+    fn from_iter<I: IntoIterator<Item = (String, T)>>(iter: I) -> Self {
+        let entries: Vec<(String, T)> = iter.into_iter().collect();
+        for (i, (name, _)) in entries.iter().enumerate() {
+            assert!(
+                entries[..i].iter().all(|(n, _)| n != name),
+                "duplicate parameter name {:?}",
+                name
+            );
+        }
+        Self { entries }
+    }
+}
+
+impl<T> IntoIterator for Params<T> {
+    type Item = (String, T);
+    type IntoIter = std::vec::IntoIter<(String, T)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<T> Index<&str> for Params<T> {
+    type Output = T;
+
+    fn index(&self, name: &str) -> &T {
+        self.get(name)
+            .unwrap_or_else(|| panic!("no parameter named {:?}", name))
+    }
+}
+
+impl Trainable for SimpleNet {
+    fn loss(&self, x: &Array2<f64>, t: &Array2<f64>) -> f64 {
+        SimpleNet::loss(self, x, t)
+    }
+
+    fn gradient(&mut self, x: &Array2<f64>, t: &Array2<f64>) -> Vec<Array2<f64>> {
+        let grads = self.numerical_gradient(x, t);
+        if self.use_bias {
+            vec![grads.w1, grads.b1, grads.w2, grads.b2]
+        } else {
+            vec![grads.w1, grads.w2]
+        }
+    }
+
+    fn params(&self) -> Vec<&Array2<f64>> {
+        if self.use_bias {
+            vec![&self.w1, &self.b1, &self.w2, &self.b2]
+        } else {
+            vec![&self.w1, &self.w2]
+        }
+    }
+
+    fn params_mut(&mut self) -> Vec<&mut Array2<f64>> {
+        if self.use_bias {
+            vec![&mut self.w1, &mut self.b1, &mut self.w2, &mut self.b2]
+        } else {
+            vec![&mut self.w1, &mut self.w2]
+        }
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        let names: &[&str] = if self.use_bias {
+            &["w1", "b1", "w2", "b2"]
+        } else {
+            &["w1", "w2"]
+        };
+        names.iter().map(|name| name.to_string()).collect()
+    }
+}
+
+/// 对任意后端的模型做预测，并返回每个样本概率最大的类别
+pub fn argmax_predictions<M: Model + ?Sized>(model: &M, x: &Array2<f64>) -> Array1<usize> {
+    argmax_rows(&model.predict(x))
+}
+
+/// 带缓存的模型：对完全相同的输入直接返回上次的预测结果，
+/// 适合交互式演示中反复对同一网格求值、而模型本身又比较慢的情况。
+///
+/// 以输入的形状和所有元素的二进制表示的哈希为键，命中时还会比较输入本身以排除哈希冲突；
+/// 最多缓存 `capacity` 个结果，超出时淘汰最久未使用的一个（LRU）
+pub struct CachedModel<M: Model> {
+    pub inner: M,
+    capacity: usize,
+    // 队首为最近使用的条目
+    entries: RefCell<VecDeque<CacheEntry>>,
+}
+
+struct CacheEntry {
+    key: u64,
+    input: Array2<f64>,
+    output: Array2<f64>,
+}
+
+impl<M: Model> CachedModel<M> {
+    pub fn new(inner: M, capacity: usize) -> Self {
+        assert!(capacity > 0, "CachedModel: capacity must be positive");
+        Self {
+            inner,
+            capacity,
+            entries: RefCell::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// 当前缓存的结果个数
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 清空缓存（例如修改了 `inner` 的参数之后）
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+    }
+}
+
+fn input_key(x: &Array2<f64>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    x.shape().hash(&mut hasher);
+    for v in x.iter() {
+        v.to_bits().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+impl<M: Model> Model for CachedModel<M> {
+    fn predict(&self, x: &Array2<f64>) -> Array2<f64> {
+        let key = input_key(x);
+        let mut entries = self.entries.borrow_mut();
+
+        if let Some(pos) = entries.iter().position(|e| e.key == key && e.input == *x) {
+            let entry = entries.remove(pos).unwrap();
+            let output = entry.output.clone();
+            entries.push_front(entry);
+            return output;
+        }
+
+        let output = self.inner.predict(x);
+        if entries.len() == self.capacity {
+            entries.pop_back();
+        }
+        entries.push_front(CacheEntry {
+            key,
+            input: x.clone(),
+            output: output.clone(),
+        });
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chapter02::matrix::Matrix;
+    use ndarray::array;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_argmax_predictions_both_backends() {
+        let net = SimpleNet::new(3, 4, 2);
+        let net_matrix = SimpleNetMatrix {
+            w1: Matrix::from(&net.w1),
+            b1: Matrix::from(&net.b1),
+            w2: Matrix::from(&net.w2),
+            b2: Matrix::from(&net.b2),
+        };
+        let x = array![[1.0, 0.5, -1.2], [0.0, 0.1, 0.2], [-2.0, 3.0, 0.5]];
+
+        let pred = argmax_predictions(&net, &x);
+        let pred_matrix = argmax_predictions(&net_matrix, &x);
+
+        assert_eq!(pred.len(), 3);
+        assert_eq!(pred, pred_matrix);
+    }
+
+    #[test]
+    fn test_model_trait_object() {
+        let models: Vec<Box<dyn Model>> = vec![
+            Box::new(SimpleNet::new(2, 3, 4)),
+            Box::new(SimpleNetMatrix::new(2, 3, 4)),
+        ];
+        let x = array![[0.6, 0.9]];
+        for model in &models {
+            let y = model.predict(&x);
+            assert_eq!(y.shape(), &[1, 4]);
+            assert!((y.sum() - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_trainable_without_bias_skips_biases() {
+        let mut net = SimpleNet::new(2, 3, 2).without_bias();
+        let x = array![[0.6, 0.9]];
+        let t = array![[0.0, 1.0]];
+        let grads = Trainable::gradient(&mut net, &x, &t);
+        assert_eq!(grads.len(), 2);
+        assert_eq!(grads[0].shape(), &[2, 3]);
+        assert_eq!(net.params_mut().len(), 2);
+    }
+
+    /// 记录 predict 被调用次数的模型
+    struct CountingModel {
+        calls: Cell<usize>,
+    }
+
+    impl Model for CountingModel {
+        fn predict(&self, x: &Array2<f64>) -> Array2<f64> {
+            self.calls.set(self.calls.get() + 1);
+            x * 2.0
+        }
+    }
+
+    #[test]
+    fn test_named_params() {
+        let mut net = SimpleNet::new(3, 4, 2);
+        let x = array![[0.6, 0.9, -0.3]];
+        let t = array![[0.0, 1.0]];
+
+        let named = net.named_params();
+        assert_eq!(named.names().collect::<Vec<_>>(), ["w1", "b1", "w2", "b2"]);
+        assert_eq!(named["w2"], &net.w2);
+        assert_eq!(named.get("w3"), None);
+
+        let mut grads = net.named_gradient(&x, &t);
+        assert_eq!(grads["w2"].shape(), net.w2.shape());
+        grads.get_mut("b1").unwrap().fill(0.0);
+        assert_eq!(grads["b1"].sum(), 0.0);
+        let (first, _) = grads.into_iter().next().unwrap();
+        assert_eq!(first, "w1");
+
+        let no_bias = SimpleNet::new(3, 4, 2).without_bias();
+        assert_eq!(no_bias.param_names(), ["w1", "w2"]);
+    }
+
+    #[test]
+    fn test_cached_model_reuses_result() {
+        let model = CachedModel::new(
+            CountingModel {
+                calls: Cell::new(0),
+            },
+            2,
+        );
+        let x = array![[1.0, 2.0]];
+
+        let first = model.predict(&x);
+        let second = model.predict(&x);
+        assert_eq!(first, second);
+        assert_eq!(model.inner.calls.get(), 1);
+
+        model.predict(&array![[3.0, 4.0]]);
+        assert_eq!(model.inner.calls.get(), 2);
+        assert_eq!(model.len(), 2);
+    }
+
+    #[test]
+    fn test_cached_model_evicts_least_recently_used() {
+        let model = CachedModel::new(
+            CountingModel {
+                calls: Cell::new(0),
+            },
+            2,
+        );
+        let (a, b, c) = (array![[1.0]], array![[2.0]], array![[3.0]]);
+
+        model.predict(&a);
+        model.predict(&b);
+        model.predict(&a); // a 变为最近使用，b 成为最久未使用
+        model.predict(&c); // 淘汰 b
+        assert_eq!(model.inner.calls.get(), 3);
+
+        model.predict(&a);
+        assert_eq!(model.inner.calls.get(), 3);
+        model.predict(&b);
+        assert_eq!(model.inner.calls.get(), 4);
+    }
+}
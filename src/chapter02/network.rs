@@ -1,9 +1,13 @@
 // src/chapter02/network.rs
 use super::activation::{sigmoid, sigmoid_matrix, softmax, softmax_matrix};
+use super::grad::numerical_gradient;
+use super::init::{Initializer, NormalInit};
+use super::loss::cross_entropy_error;
 use super::matrix::Matrix;
-use ndarray::{Array, Array2};
-use ndarray_rand::RandomExt;
-use ndarray_rand::rand_distr::Normal;
+use super::serialize::save_npy;
+use ndarray::{Array2, Axis, concatenate};
+use std::io;
+use std::path::Path;
 
 #[derive(Clone)]
 pub struct SimpleNet {
@@ -11,8 +15,77 @@ pub struct SimpleNet {
     pub b1: Array2<f64>,
     pub w2: Array2<f64>,
     pub b2: Array2<f64>,
+    /// 为 false 时前向传播不加偏置，偏置也不参与训练
+    pub use_bias: bool,
 }
 
+/// SimpleNet 各参数的梯度，形状与对应参数一致
+#[derive(Clone, Debug)]
+pub struct SimpleNetGrads {
+    pub w1: Array2<f64>,
+    pub b1: Array2<f64>,
+    pub w2: Array2<f64>,
+    pub b2: Array2<f64>,
+}
+
+/// 单个参数数组的统计量，用于监控训练过程中权重是否发散
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParamStats {
+    /// L2 范数 sqrt(Σ w²)
+    pub norm: f64,
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl ParamStats {
+    pub fn of(a: &Array2<f64>) -> Self {
+        Self {
+            norm: a.iter().map(|v| v * v).sum::<f64>().sqrt(),
+            mean: a.mean().unwrap_or(0.0),
+            min: a.iter().copied().fold(f64::INFINITY, f64::min),
+            max: a.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+}
+
+/// SimpleNet 各参数的统计量
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WeightStats {
+    pub w1: ParamStats,
+    pub b1: ParamStats,
+    pub w2: ParamStats,
+    pub b2: ParamStats,
+}
+
+/// 前向传播各阶段的中间结果：a1 = x·W1 + b1, z1 = sigmoid(a1), a2 = z1·W2 + b2, y = softmax(a2)
+#[derive(Clone, Debug)]
+pub struct ForwardCache {
+    pub a1: Array2<f64>,
+    pub z1: Array2<f64>,
+    pub a2: Array2<f64>,
+    pub y: Array2<f64>,
+}
+
+/// 网络推理时的错误
+#[derive(Debug, PartialEq)]
+pub enum NetworkError {
+    /// 输入特征数与第一层权重的行数不一致
+    FeatureMismatch { expected: usize, got: usize },
+}
+
+impl std::fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NetworkError::FeatureMismatch { expected, got } => {
+                write!(f, "expected {} input features, got {}", expected, got)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
 // 向后兼容的 Matrix 版本
 pub struct SimpleNetMatrix {
     pub w1: Matrix,
@@ -23,21 +96,173 @@ pub struct SimpleNetMatrix {
 
 impl SimpleNet {
     pub fn new(input_size: usize, hidden_size: usize, output_size: usize) -> Self {
-        let normal = Normal::new(0.0, 1.0).unwrap();
+        Self::with_initializer(
+            input_size,
+            hidden_size,
+            output_size,
+            &NormalInit { std: 1.0 },
+            rand::random(),
+        )
+    }
 
-        let w1 = Array::random((input_size, hidden_size), normal);
+    /// 用指定的初始化器和随机种子创建网络（偏置始终初始化为 0）
+    pub fn with_initializer(
+        input_size: usize,
+        hidden_size: usize,
+        output_size: usize,
+        init: &dyn Initializer,
+        seed: u64,
+    ) -> Self {
+        let w1 = init.init((input_size, hidden_size), seed);
         let b1 = Array2::zeros((1, hidden_size));
-        let w2 = Array::random((hidden_size, output_size), normal);
+        let w2 = init.init((hidden_size, output_size), seed.wrapping_add(1));
         let b2 = Array2::zeros((1, output_size));
 
-        Self { w1, b1, w2, b2 }
+        Self {
+            w1,
+            b1,
+            w2,
+            b2,
+            use_bias: true,
+        }
     }
 
+    /// 去掉偏置项（偏置清零，且前向传播和训练中都不再使用）
+    pub fn without_bias(mut self) -> Self {
+        self.b1.fill(0.0);
+        self.b2.fill(0.0);
+        self.use_bias = false;
+        self
+    }
+
+    /// 前向推理；输入特征数不匹配时给出清晰的 panic 信息
     pub fn predict(&self, x: &Array2<f64>) -> Array2<f64> {
-        let a1 = x.dot(&self.w1) + &self.b1;
+        self.try_predict(x)
+            .unwrap_or_else(|e| panic!("SimpleNet::predict: {}", e))
+    }
+
+    /// 前向推理；输入特征数不匹配时返回错误而不是 panic
+    pub fn try_predict(&self, x: &Array2<f64>) -> Result<Array2<f64>, NetworkError> {
+        if x.ncols() != self.w1.nrows() {
+            return Err(NetworkError::FeatureMismatch {
+                expected: self.w1.nrows(),
+                got: x.ncols(),
+            });
+        }
+
+        Ok(self.forward_with_activations(x).y)
+    }
+
+    /// 把参数分别保存为 `dir` 下的 `w1.npy`、`b1.npy`、`w2.npy`、`b2.npy`（目录不存在时创建），
+    /// 方便在 Python 中用 `np.load` 读取，与书中的 Python 实现对照
+    pub fn save_npy(&self, dir: &Path) -> io::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        for (name, param) in [
+            ("w1", &self.w1),
+            ("b1", &self.b1),
+            ("w2", &self.w2),
+            ("b2", &self.b2),
+        ] {
+            save_npy(dir.join(format!("{}.npy", name)), param)?;
+        }
+        Ok(())
+    }
+
+    /// 所有参数合在一起的 L2 范数
+    pub fn l2_norm(&self) -> f64 {
+        [&self.w1, &self.b1, &self.w2, &self.b2]
+            .iter()
+            .flat_map(|p| p.iter())
+            .map(|v| v * v)
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// 每个参数的 L2 范数、均值、最小值和最大值
+    pub fn weight_stats(&self) -> WeightStats {
+        WeightStats {
+            w1: ParamStats::of(&self.w1),
+            b1: ParamStats::of(&self.b1),
+            w2: ParamStats::of(&self.w2),
+            b2: ParamStats::of(&self.b2),
+        }
+    }
+
+    /// 按 `batch_size` 行分块推理再拼接结果，避免一次性为整个输入分配中间数组
+    pub fn predict_batched(&self, x: &Array2<f64>, batch_size: usize) -> Array2<f64> {
+        assert!(
+            batch_size > 0,
+            "predict_batched: batch_size must be positive"
+        );
+        if x.nrows() == 0 {
+            return self.predict(x);
+        }
+
+        let outputs: Vec<Array2<f64>> = x
+            .axis_chunks_iter(Axis(0), batch_size)
+            .map(|chunk| self.predict(&chunk.to_owned()))
+            .collect();
+        let views: Vec<_> = outputs.iter().map(|y| y.view()).collect();
+        concatenate(Axis(0), &views).expect("predict_batched: chunk outputs share column count")
+    }
+
+    /// 前向传播并保留每一层的中间结果，便于观察隐藏层或手工推导反向传播
+    pub fn forward_with_activations(&self, x: &Array2<f64>) -> ForwardCache {
+        let mut a1 = x.dot(&self.w1);
+        if self.use_bias {
+            a1 += &self.b1;
+        }
         let z1 = sigmoid(&a1);
-        let a2 = z1.dot(&self.w2) + &self.b2;
-        softmax(&a2)
+        let mut a2 = z1.dot(&self.w2);
+        if self.use_bias {
+            a2 += &self.b2;
+        }
+        let y = softmax(&a2);
+        ForwardCache { a1, z1, a2, y }
+    }
+
+    /// 交叉熵损失
+    pub fn loss(&self, x: &Array2<f64>, t: &Array2<f64>) -> f64 {
+        cross_entropy_error(&self.predict(x), t)
+    }
+
+    /// 用数值微分求损失对所有参数的梯度（每个参数要做两次前向传播，只适合小网络）。
+    /// 不使用偏置时 b1 / b2 的梯度为 0
+    pub fn numerical_gradient(&self, x: &Array2<f64>, t: &Array2<f64>) -> SimpleNetGrads {
+        let (b1, b2) = if self.use_bias {
+            (
+                self.param_gradient(x, t, |net| &mut net.b1),
+                self.param_gradient(x, t, |net| &mut net.b2),
+            )
+        } else {
+            (
+                Array2::zeros(self.b1.raw_dim()),
+                Array2::zeros(self.b2.raw_dim()),
+            )
+        };
+        SimpleNetGrads {
+            w1: self.param_gradient(x, t, |net| &mut net.w1),
+            b1,
+            w2: self.param_gradient(x, t, |net| &mut net.w2),
+            b2,
+        }
+    }
+
+    fn param_gradient(
+        &self,
+        x: &Array2<f64>,
+        t: &Array2<f64>,
+        param: fn(&mut SimpleNet) -> &mut Array2<f64>,
+    ) -> Array2<f64> {
+        let current = param(&mut self.clone()).clone();
+        numerical_gradient(
+            |p| {
+                let mut cloned = self.clone();
+                *param(&mut cloned) = p.clone();
+                cloned.loss(x, t)
+            },
+            &current,
+        )
     }
 }
 
@@ -74,6 +299,12 @@ impl SimpleNetMatrix {
         let a2 = z1.dot(&self.w2).add(&self.b2);
         softmax_matrix(&a2)
     }
+
+    /// 接受 ndarray 输入并返回 ndarray 输出，内部仍用 Matrix 计算，
+    /// 方便在基于 ndarray 的示例 / 绘图代码中直接使用
+    pub fn predict_ndarray(&self, x: &Array2<f64>) -> Array2<f64> {
+        Array2::from(&self.predict(&Matrix::from(x)))
+    }
 }
 
 #[cfg(test)]
@@ -124,6 +355,126 @@ mod tests {
         assert!((sum - 1.0).abs() < 1e-6);
     }
 
+    #[test]
+    fn test_try_predict_feature_mismatch() {
+        let net = SimpleNet::new(784, 10, 10);
+        let x = Array2::zeros((2, 100));
+        let err = net.try_predict(&x).unwrap_err();
+        assert_eq!(
+            err,
+            NetworkError::FeatureMismatch {
+                expected: 784,
+                got: 100
+            }
+        );
+        assert_eq!(err.to_string(), "expected 784 input features, got 100");
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 784 input features, got 100")]
+    fn test_predict_feature_mismatch_panics_clearly() {
+        let net = SimpleNet::new(784, 10, 10);
+        net.predict(&Array2::zeros((1, 100)));
+    }
+
+    #[test]
+    fn test_numerical_gradient_shapes() {
+        let net = SimpleNet::new(2, 3, 2);
+        let x = array![[0.6, 0.9]];
+        let t = array![[0.0, 1.0]];
+        let grads = net.numerical_gradient(&x, &t);
+        assert_eq!(grads.w1.shape(), net.w1.shape());
+        assert_eq!(grads.b1.shape(), net.b1.shape());
+        assert_eq!(grads.w2.shape(), net.w2.shape());
+        assert_eq!(grads.b2.shape(), net.b2.shape());
+
+        // 沿负梯度方向走一小步，损失应该下降
+        let mut stepped = net.clone();
+        stepped.w2 = &net.w2 - &(&grads.w2 * 0.1);
+        assert!(stepped.loss(&x, &t) < net.loss(&x, &t));
+    }
+
+    #[test]
+    fn test_with_initializer() {
+        use crate::chapter02::init::{XavierInit, ZeroInit};
+
+        let net = SimpleNet::with_initializer(3, 4, 2, &ZeroInit, 0);
+        assert!(net.w1.iter().chain(net.w2.iter()).all(|&v| v == 0.0));
+
+        let a = SimpleNet::with_initializer(3, 4, 2, &XavierInit, 42);
+        let b = SimpleNet::with_initializer(3, 4, 2, &XavierInit, 42);
+        assert_eq!(a.w1, b.w1);
+        assert_eq!(a.w2, b.w2);
+        assert_eq!(a.w1.shape(), &[3, 4]);
+        assert_eq!(a.w2.shape(), &[4, 2]);
+    }
+
+    #[test]
+    fn test_weight_stats() {
+        use crate::chapter02::init::ZeroInit;
+
+        let zero = SimpleNet::with_initializer(3, 4, 2, &ZeroInit, 0).weight_stats();
+        let all_zero = ParamStats {
+            norm: 0.0,
+            mean: 0.0,
+            min: 0.0,
+            max: 0.0,
+        };
+        assert_eq!(zero.w1, all_zero);
+        assert_eq!(zero.b1, all_zero);
+        assert_eq!(zero.w2, all_zero);
+        assert_eq!(zero.b2, all_zero);
+
+        // N(0, σ²) 初始化的 n 个权重，范数约为 σ·sqrt(n)
+        let net = SimpleNet::with_initializer(100, 50, 10, &NormalInit { std: 0.1 }, 7);
+        let stats = net.weight_stats();
+        let expected = 0.1 * (100.0 * 50.0_f64).sqrt();
+        assert!((stats.w1.norm - expected).abs() / expected < 0.05);
+        assert!(stats.w1.mean.abs() < 0.01);
+        assert!(stats.w1.min < 0.0 && stats.w1.max > 0.0);
+        assert_eq!(stats.b1.norm, 0.0);
+
+        let total = (stats.w1.norm.powi(2) + stats.w2.norm.powi(2)).sqrt();
+        assert!((net.l2_norm() - total).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_predict_batched_matches_predict() {
+        let net = SimpleNet::new(3, 5, 2);
+        let x = Array2::from_shape_fn((37, 3), |(i, j)| ((i * 3 + j) as f64 * 0.37).sin());
+
+        let batched = net.predict_batched(&x, 16);
+        let full = net.predict(&x);
+        assert_eq!(batched.shape(), full.shape());
+        for (a, b) in batched.iter().zip(full.iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_forward_with_activations() {
+        let net = SimpleNet::new(3, 4, 2);
+        let x = array![[1.0, 0.5, -1.2], [0.0, 0.1, 0.2]];
+        let cache = net.forward_with_activations(&x);
+
+        assert_eq!(cache.y, net.predict(&x));
+        assert_eq!(cache.z1, sigmoid(&cache.a1));
+        assert_eq!(cache.a1.shape(), &[2, 4]);
+        assert_eq!(cache.a2.shape(), &[2, 2]);
+    }
+
+    #[test]
+    fn test_without_bias() {
+        let mut net = SimpleNet::new(3, 4, 2).without_bias();
+        let x = array![[1.0, 0.5, -1.2]];
+        let expected = softmax(&sigmoid(&x.dot(&net.w1)).dot(&net.w2));
+        assert_eq!(net.predict(&x), expected);
+
+        // 偏置不参与前向传播
+        net.b1.fill(1.0);
+        assert_eq!(net.predict(&x), expected);
+    }
+
     // Matrix 版本的测试
     #[test]
     fn test_matrix_predict_shape() {
@@ -133,6 +484,25 @@ mod tests {
         assert_eq!(y.shape(), (2, 2)); // 2 samples, 2 outputs each
     }
 
+    #[test]
+    fn test_matrix_predict_ndarray_matches_simple_net() {
+        let net = SimpleNet::new(3, 4, 2);
+        let matrix_net = SimpleNetMatrix {
+            w1: Matrix::from(&net.w1),
+            b1: Matrix::from(&net.b1),
+            w2: Matrix::from(&net.w2),
+            b2: Matrix::from(&net.b2),
+        };
+        let x = array![[1.0, 0.5, -1.2], [0.0, 0.1, 0.2]];
+
+        let expected = net.predict(&x);
+        let actual = matrix_net.predict_ndarray(&x);
+        assert_eq!(actual.shape(), expected.shape());
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() < 1e-12);
+        }
+    }
+
     #[test]
     fn test_matrix_predict_sum_1() {
         let net = SimpleNetMatrix::new(4, 4, 3);
@@ -141,4 +511,27 @@ mod tests {
         let sum: f64 = y.data[0].iter().sum();
         assert!((sum - 1.0).abs() < 1e-6);
     }
+
+    #[test]
+    fn test_save_npy_writes_every_parameter() {
+        let net = SimpleNet::new(3, 4, 2);
+        let dir = std::env::temp_dir().join(format!("rdl_npy_{}", std::process::id()));
+        net.save_npy(&dir).unwrap();
+
+        for (name, param) in [
+            ("w1", &net.w1),
+            ("b1", &net.b1),
+            ("w2", &net.w2),
+            ("b2", &net.b2),
+        ] {
+            let bytes = std::fs::read(dir.join(format!("{}.npy", name))).unwrap();
+            assert_eq!(&bytes[..6], b"\x93NUMPY");
+            let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+            let header = std::str::from_utf8(&bytes[10..10 + header_len]).unwrap();
+            let shape = format!("'shape': ({}, {})", param.nrows(), param.ncols());
+            assert!(header.contains(&shape), "{}: {}", name, header);
+            assert_eq!(bytes.len(), 10 + header_len + param.len() * 8);
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -0,0 +1,840 @@
+// src/chapter02/optimizer.rs
+use crate::chapter05::parameter::Parameter;
+use ndarray::Array2;
+use std::io;
+
+/// 参数更新规则的统一接口。`params` 与 `grads` 按下标一一对应，
+/// 例如 `opt.update(&mut model.params_mut(), &grads)`。
+///
+/// 有状态的优化器（如 Momentum 的速度）按下标保存每个参数的状态，
+/// 所以每次调用时参数的个数和顺序必须相同
+pub trait Optimizer {
+    /// 用梯度就地更新参数
+    fn update(&mut self, params: &mut [&mut Array2<f64>], grads: &[Array2<f64>]) {
+        self.update_with_lr_scale(params, grads, None);
+    }
+
+    /// 与 `update` 相同，但第 i 个参数使用学习率 `lr * lr_scale[i]`，
+    /// 可以让不同层以不同速度学习；`None` 表示所有倍率都为 1.0
+    fn update_with_lr_scale(
+        &mut self,
+        params: &mut [&mut Array2<f64>],
+        grads: &[Array2<f64>],
+        lr_scale: Option<&[f64]>,
+    );
+
+    /// 当前的学习率
+    fn lr(&self) -> f64;
+
+    /// 修改学习率（`Trainer` 借此让学习率调度回调对任意优化器生效）
+    fn set_lr(&mut self, lr: f64);
+
+    /// 内部状态（速度、梯度平方和等），`Trainer::save_checkpoint` 借此保存优化器。
+    /// 无状态的优化器返回空
+    fn state(&self) -> Vec<Array2<f64>> {
+        Vec::new()
+    }
+
+    /// 恢复 `state` 返回的状态，`params` 是之后要更新的参数。
+    /// 状态的个数或形状与 `params` 不对应时返回 `InvalidData` 错误且不修改优化器
+    fn load_state(&mut self, _params: &[&Array2<f64>], state: Vec<Array2<f64>>) -> io::Result<()> {
+        if state.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid_state("this optimizer has no state".to_string()))
+        }
+    }
+
+    /// 优化器的名称，保存在训练检查点中，避免用另一种优化器的状态继续训练
+    fn name(&self) -> &'static str;
+
+    /// 用每个 `Parameter` 自带的梯度更新其参数值（例如 `Sequential::params_mut` 的结果）
+    fn step(&mut self, params: &mut [&mut Parameter]) {
+        let grads: Vec<Array2<f64>> = params.iter().map(|p| p.grad.clone()).collect();
+        let mut values: Vec<&mut Array2<f64>> = params.iter_mut().map(|p| &mut p.value).collect();
+        self.update(&mut values, &grads);
+    }
+}
+
+/// 随机梯度下降：param -= lr * grad
+#[derive(Debug, Clone)]
+pub struct Sgd {
+    pub lr: f64,
+}
+
+impl Sgd {
+    pub fn new(lr: f64) -> Self {
+        Self { lr }
+    }
+}
+
+impl Optimizer for Sgd {
+    fn update_with_lr_scale(
+        &mut self,
+        params: &mut [&mut Array2<f64>],
+        grads: &[Array2<f64>],
+        lr_scale: Option<&[f64]>,
+    ) {
+        assert_eq!(params.len(), grads.len());
+        let scales = lr_scales(lr_scale, params.len());
+        for ((param, grad), scale) in params.iter_mut().zip(grads).zip(scales) {
+            param.scaled_add(-self.lr * scale, grad);
+        }
+    }
+
+    fn lr(&self) -> f64 {
+        self.lr
+    }
+
+    fn set_lr(&mut self, lr: f64) {
+        self.lr = lr;
+    }
+
+    fn name(&self) -> &'static str {
+        "Sgd"
+    }
+}
+
+/// Momentum 优化器
+///
+/// 普通动量：v = momentum * v - lr * grad，param += v
+///
+/// Nesterov 动量（`nesterov = true`）：在"先按动量前进一步"的位置上看梯度，
+/// 等价的实现形式为 param += momentum * v_new - lr * grad
+#[derive(Debug, Clone)]
+pub struct Momentum {
+    pub lr: f64,
+    pub momentum: f64,
+    pub nesterov: bool,
+    velocity: Vec<Array2<f64>>,
+}
+
+impl Momentum {
+    pub fn new(lr: f64, momentum: f64) -> Self {
+        Self {
+            lr,
+            momentum,
+            nesterov: false,
+            velocity: Vec::new(),
+        }
+    }
+
+    /// 创建使用 Nesterov 加速梯度的 Momentum 优化器
+    pub fn nesterov(lr: f64, momentum: f64) -> Self {
+        Self {
+            nesterov: true,
+            ..Self::new(lr, momentum)
+        }
+    }
+
+    /// 清空累积的速度，之后的更新如同新建的优化器（例如从新的起点重新优化时）
+    pub fn reset(&mut self) {
+        self.velocity.clear();
+    }
+
+    // 第一次调用时按参数形状初始化速度
+    fn init_velocity<'a>(&mut self, params: impl Iterator<Item = &'a Array2<f64>>) {
+        if self.velocity.is_empty() {
+            self.velocity = params.map(|p| Array2::zeros(p.raw_dim())).collect();
+        }
+    }
+
+    fn apply(
+        lr: f64,
+        momentum: f64,
+        nesterov: bool,
+        param: &mut Array2<f64>,
+        grad: &Array2<f64>,
+        v: &mut Array2<f64>,
+    ) {
+        *v = &*v * momentum - grad * lr;
+        if nesterov {
+            *param += &(&*v * momentum - grad * lr);
+        } else {
+            *param += &*v;
+        }
+    }
+}
+
+impl Optimizer for Momentum {
+    fn update_with_lr_scale(
+        &mut self,
+        params: &mut [&mut Array2<f64>],
+        grads: &[Array2<f64>],
+        lr_scale: Option<&[f64]>,
+    ) {
+        assert_eq!(params.len(), grads.len());
+        let scales = lr_scales(lr_scale, params.len());
+        self.init_velocity(params.iter().map(|p| &**p));
+
+        for (((param, grad), v), scale) in params
+            .iter_mut()
+            .zip(grads)
+            .zip(self.velocity.iter_mut())
+            .zip(scales)
+        {
+            Self::apply(
+                self.lr * scale,
+                self.momentum,
+                self.nesterov,
+                param,
+                grad,
+                v,
+            );
+        }
+    }
+
+    fn lr(&self) -> f64 {
+        self.lr
+    }
+
+    fn set_lr(&mut self, lr: f64) {
+        self.lr = lr;
+    }
+
+    fn state(&self) -> Vec<Array2<f64>> {
+        self.velocity.clone()
+    }
+
+    fn load_state(&mut self, params: &[&Array2<f64>], state: Vec<Array2<f64>>) -> io::Result<()> {
+        if !state.is_empty() {
+            check_state_shapes(params, &state)?;
+        }
+        self.velocity = state;
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "Momentum"
+    }
+}
+
+fn invalid_state(msg: String) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("optimizer state mismatch: {}", msg),
+    )
+}
+
+/// 每个参数一份状态、形状与参数相同
+fn check_state_shapes(params: &[&Array2<f64>], state: &[Array2<f64>]) -> io::Result<()> {
+    if state.len() != params.len() {
+        return Err(invalid_state(format!(
+            "expected state for {} parameters, got {}",
+            params.len(),
+            state.len()
+        )));
+    }
+    if let Some(i) = (0..state.len()).find(|&i| state[i].shape() != params[i].shape()) {
+        return Err(invalid_state(format!(
+            "parameter {} has shape {:?}, state has {:?}",
+            i,
+            params[i].shape(),
+            state[i].shape()
+        )));
+    }
+    Ok(())
+}
+
+/// 分母里防止除以 0 的小常数（与书中相同）
+const EPS: f64 = 1e-7;
+
+/// AdaGrad：累积梯度平方和 h += grad²，param -= lr * grad / (sqrt(h) + eps)。
+/// 更新过越多的参数学习率越小，但 h 只增不减，训练后期几乎不再更新
+#[derive(Debug, Clone)]
+pub struct AdaGrad {
+    pub lr: f64,
+    h: Vec<Array2<f64>>,
+}
+
+impl AdaGrad {
+    pub fn new(lr: f64) -> Self {
+        Self { lr, h: Vec::new() }
+    }
+
+    /// 清空累积的梯度平方和
+    pub fn reset(&mut self) {
+        self.h.clear();
+    }
+}
+
+impl Optimizer for AdaGrad {
+    fn update_with_lr_scale(
+        &mut self,
+        params: &mut [&mut Array2<f64>],
+        grads: &[Array2<f64>],
+        lr_scale: Option<&[f64]>,
+    ) {
+        assert_eq!(params.len(), grads.len());
+        let scales = lr_scales(lr_scale, params.len());
+        init_state(&mut self.h, params);
+        for (((param, grad), h), scale) in params
+            .iter_mut()
+            .zip(grads)
+            .zip(self.h.iter_mut())
+            .zip(scales)
+        {
+            *h += &(grad * grad);
+            **param -= &(grad * (self.lr * scale) / h.mapv(|v| v.sqrt() + EPS));
+        }
+    }
+
+    fn lr(&self) -> f64 {
+        self.lr
+    }
+
+    fn set_lr(&mut self, lr: f64) {
+        self.lr = lr;
+    }
+
+    fn state(&self) -> Vec<Array2<f64>> {
+        self.h.clone()
+    }
+
+    fn load_state(&mut self, params: &[&Array2<f64>], state: Vec<Array2<f64>>) -> io::Result<()> {
+        if !state.is_empty() {
+            check_state_shapes(params, &state)?;
+        }
+        self.h = state;
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "AdaGrad"
+    }
+}
+
+/// RMSProp：用指数移动平均代替 AdaGrad 的累加，h = decay * h + (1 - decay) * grad²，
+/// 逐渐遗忘过去的梯度，学习率不会一直衰减下去
+#[derive(Debug, Clone)]
+pub struct RmsProp {
+    pub lr: f64,
+    pub decay_rate: f64,
+    h: Vec<Array2<f64>>,
+}
+
+impl RmsProp {
+    /// 衰减率默认为 0.99
+    pub fn new(lr: f64) -> Self {
+        Self {
+            lr,
+            decay_rate: 0.99,
+            h: Vec::new(),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.h.clear();
+    }
+}
+
+impl Optimizer for RmsProp {
+    fn update_with_lr_scale(
+        &mut self,
+        params: &mut [&mut Array2<f64>],
+        grads: &[Array2<f64>],
+        lr_scale: Option<&[f64]>,
+    ) {
+        assert_eq!(params.len(), grads.len());
+        let scales = lr_scales(lr_scale, params.len());
+        init_state(&mut self.h, params);
+        let decay = self.decay_rate;
+        for (((param, grad), h), scale) in params
+            .iter_mut()
+            .zip(grads)
+            .zip(self.h.iter_mut())
+            .zip(scales)
+        {
+            h.zip_mut_with(grad, |h, &g| *h = decay * *h + (1.0 - decay) * g * g);
+            **param -= &(grad * (self.lr * scale) / h.mapv(|v| v.sqrt() + EPS));
+        }
+    }
+
+    fn lr(&self) -> f64 {
+        self.lr
+    }
+
+    fn set_lr(&mut self, lr: f64) {
+        self.lr = lr;
+    }
+
+    fn state(&self) -> Vec<Array2<f64>> {
+        self.h.clone()
+    }
+
+    fn load_state(&mut self, params: &[&Array2<f64>], state: Vec<Array2<f64>>) -> io::Result<()> {
+        if !state.is_empty() {
+            check_state_shapes(params, &state)?;
+        }
+        self.h = state;
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "RmsProp"
+    }
+}
+
+/// Adam：结合 Momentum（梯度的一阶矩 m）和 RMSProp（二阶矩 v），
+/// 并对两者做偏差修正，使初始几步的步长不会因 m、v 从 0 开始而偏小。
+///
+/// 按书中的写法把修正并入学习率：lr_t = lr * sqrt(1 - beta2^t) / (1 - beta1^t)，
+/// param -= lr_t * m / (sqrt(v) + eps)
+#[derive(Debug, Clone)]
+pub struct Adam {
+    pub lr: f64,
+    pub beta1: f64,
+    pub beta2: f64,
+    iter: i32,
+    m: Vec<Array2<f64>>,
+    v: Vec<Array2<f64>>,
+}
+
+impl Adam {
+    /// beta1 = 0.9，beta2 = 0.999
+    pub fn new(lr: f64) -> Self {
+        Self {
+            lr,
+            beta1: 0.9,
+            beta2: 0.999,
+            iter: 0,
+            m: Vec::new(),
+            v: Vec::new(),
+        }
+    }
+
+    /// 清空一阶、二阶矩和步数
+    pub fn reset(&mut self) {
+        self.iter = 0;
+        self.m.clear();
+        self.v.clear();
+    }
+}
+
+impl Optimizer for Adam {
+    fn update_with_lr_scale(
+        &mut self,
+        params: &mut [&mut Array2<f64>],
+        grads: &[Array2<f64>],
+        lr_scale: Option<&[f64]>,
+    ) {
+        assert_eq!(params.len(), grads.len());
+        let scales = lr_scales(lr_scale, params.len());
+        init_state(&mut self.m, params);
+        init_state(&mut self.v, params);
+        self.iter += 1;
+        let (b1, b2) = (self.beta1, self.beta2);
+        let lr_t = self.lr * (1.0 - b2.powi(self.iter)).sqrt() / (1.0 - b1.powi(self.iter));
+
+        for ((((param, grad), m), v), scale) in params
+            .iter_mut()
+            .zip(grads)
+            .zip(self.m.iter_mut())
+            .zip(self.v.iter_mut())
+            .zip(scales)
+        {
+            m.zip_mut_with(grad, |m, &g| *m += (1.0 - b1) * (g - *m));
+            v.zip_mut_with(grad, |v, &g| *v += (1.0 - b2) * (g * g - *v));
+            **param -= &(&*m * (lr_t * scale) / v.mapv(|v| v.sqrt() + EPS));
+        }
+    }
+
+    fn lr(&self) -> f64 {
+        self.lr
+    }
+
+    fn set_lr(&mut self, lr: f64) {
+        self.lr = lr;
+    }
+
+    /// [步数（1×1）, m..., v...]，尚未更新过时为空
+    fn state(&self) -> Vec<Array2<f64>> {
+        if self.m.is_empty() {
+            return Vec::new();
+        }
+        let mut state = vec![Array2::from_elem((1, 1), self.iter as f64)];
+        state.extend(self.m.iter().cloned());
+        state.extend(self.v.iter().cloned());
+        state
+    }
+
+    fn load_state(&mut self, params: &[&Array2<f64>], state: Vec<Array2<f64>>) -> io::Result<()> {
+        if state.is_empty() {
+            self.reset();
+            return Ok(());
+        }
+        if state.len() != 1 + 2 * params.len() || state[0].dim() != (1, 1) {
+            return Err(invalid_state(format!(
+                "expected [iter, m..., v...] for {} parameters",
+                params.len()
+            )));
+        }
+        check_state_shapes(params, &state[1..=params.len()])?;
+        check_state_shapes(params, &state[params.len() + 1..])?;
+
+        let mut rest = state.into_iter();
+        self.iter = rest.next().unwrap()[[0, 0]] as i32;
+        let mut moments: Vec<Array2<f64>> = rest.collect();
+        self.v = moments.split_off(params.len());
+        self.m = moments;
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "Adam"
+    }
+}
+
+/// 梯度裁剪方式，在优化器更新之前作用于整组梯度（见 `Trainer::with_grad_clip`）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GradClip {
+    /// 所有梯度合起来的 L2 范数不超过该值（`clip_grads_by_norm`）
+    Norm(f64),
+    /// 每个元素截断到 [-v, v]（`clip_grads_by_value`）
+    Value(f64),
+}
+
+impl GradClip {
+    pub fn apply(&self, grads: &mut [Array2<f64>]) {
+        match *self {
+            GradClip::Norm(max_norm) => {
+                clip_grads_by_norm(grads, max_norm);
+            }
+            GradClip::Value(clip_value) => clip_grads_by_value(grads, clip_value),
+        }
+    }
+}
+
+/// 按总范数裁剪：把所有梯度看成一个向量，其 L2 范数超过 `max_norm` 时
+/// 整体乘以 max_norm / norm，只缩短步长而不改变更新方向。返回裁剪前的总范数，
+/// 可以用来观察梯度爆炸
+pub fn clip_grads_by_norm(grads: &mut [Array2<f64>], max_norm: f64) -> f64 {
+    assert!(
+        max_norm > 0.0,
+        "clip_grads_by_norm: max_norm must be positive"
+    );
+    let total_norm = grads
+        .iter()
+        .flat_map(|g| g.iter())
+        .map(|v| v * v)
+        .sum::<f64>()
+        .sqrt();
+    if total_norm > max_norm {
+        let scale = max_norm / total_norm;
+        for grad in grads.iter_mut() {
+            grad.mapv_inplace(|v| v * scale);
+        }
+    }
+    total_norm
+}
+
+/// 按元素裁剪：每个梯度元素截断到 [-clip_value, clip_value]。
+/// 实现简单，但各元素截断程度不同，会改变更新方向
+pub fn clip_grads_by_value(grads: &mut [Array2<f64>], clip_value: f64) {
+    assert!(
+        clip_value > 0.0,
+        "clip_grads_by_value: clip_value must be positive"
+    );
+    for grad in grads.iter_mut() {
+        grad.mapv_inplace(|v| v.clamp(-clip_value, clip_value));
+    }
+}
+
+// 第一次调用时按参数形状把状态初始化为 0
+/// 每个参数的学习率倍率，`lr_scale` 为 None 时都为 1.0
+fn lr_scales(lr_scale: Option<&[f64]>, n: usize) -> Vec<f64> {
+    match lr_scale {
+        Some(scale) => {
+            assert_eq!(scale.len(), n, "need one lr_scale per parameter");
+            scale.to_vec()
+        }
+        None => vec![1.0; n],
+    }
+}
+
+fn init_state(state: &mut Vec<Array2<f64>>, params: &[&mut Array2<f64>]) {
+    if state.is_empty() {
+        *state = params.iter().map(|p| Array2::zeros(p.raw_dim())).collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    // f(x, y) = x²/20 + y²
+    fn f(p: &Array2<f64>) -> f64 {
+        p[[0, 0]].powi(2) / 20.0 + p[[0, 1]].powi(2)
+    }
+
+    fn grad_f(p: &Array2<f64>) -> Array2<f64> {
+        array![[p[[0, 0]] / 10.0, 2.0 * p[[0, 1]]]]
+    }
+
+    fn run(mut opt: impl Optimizer, steps: usize) -> f64 {
+        let mut p = array![[-7.0, 2.0]];
+        for _ in 0..steps {
+            let grads = [grad_f(&p)];
+            opt.update(&mut [&mut p], &grads);
+        }
+        f(&p)
+    }
+
+    #[test]
+    fn test_sgd_update_rule() {
+        let mut p = array![[1.0, -2.0]];
+        Sgd::new(0.5).update(&mut [&mut p], &[array![[0.2, -0.4]]]);
+        assert_eq!(p, array![[0.9, -1.8]]);
+    }
+
+    #[test]
+    fn test_sgd_converges_on_quadratic() {
+        // x 方向每步缩小为 (1 - 0.1 / 10) 倍，y 方向为 (1 - 0.1 * 2) 倍
+        assert!(run(Sgd::new(0.1), 2000) < 1e-8);
+    }
+
+    #[test]
+    fn test_sgd_diverges_when_lr_too_large() {
+        // y 方向的曲率为 2，lr > 1 时每步都越过极小值且振幅变大
+        let start = f(&array![[-7.0, 2.0]]);
+        assert!(run(Sgd::new(1.05), 50) > start);
+    }
+
+    #[test]
+    fn test_momentum_converges_faster_than_sgd() {
+        // 沿平缓的 x 方向，动量不断累积速度，同样步数下比 SGD 更接近极小值
+        let sgd = run(Sgd::new(0.1), 100);
+        let momentum = run(Momentum::new(0.1, 0.9), 100);
+        assert!(momentum < sgd, "momentum {} >= sgd {}", momentum, sgd);
+        assert!(run(Momentum::new(0.1, 0.9), 1000) < 1e-8);
+    }
+
+    #[test]
+    fn test_adaptive_optimizers_converge_on_quadratic() {
+        let start = f(&array![[-7.0, 2.0]]);
+        assert!(run(AdaGrad::new(1.5), 200) < 1e-6 * start);
+        assert!(run(RmsProp::new(0.05), 500) < 1e-3 * start);
+        assert!(run(Adam::new(0.3), 500) < 1e-4 * start);
+    }
+
+    #[test]
+    fn test_adagrad_first_step_and_decay() {
+        let mut opt = AdaGrad::new(0.1);
+        let mut p = array![[0.0, 0.0]];
+        // 第一步 h = g²，步长约为 lr，与梯度大小无关
+        opt.update(&mut [&mut p], &[array![[100.0, 0.01]]]);
+        assert!((p[[0, 0]] + 0.1).abs() < 1e-6);
+        assert!((p[[0, 1]] + 0.1).abs() < 1e-4);
+
+        // 同样的梯度再来一次，步长缩小为 lr / sqrt(2)
+        let before = p.clone();
+        opt.update(&mut [&mut p], &[array![[100.0, 0.01]]]);
+        let step = before[[0, 0]] - p[[0, 0]];
+        assert!((step - 0.1 / 2f64.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_rmsprop_forgets_old_gradients() {
+        // 经历一段大梯度后换成小梯度：AdaGrad 的步长停留在很小的值，RMSProp 会逐渐恢复
+        let step_after = |opt: &mut dyn Optimizer| {
+            let mut p = array![[0.0]];
+            for _ in 0..100 {
+                opt.update(&mut [&mut p], &[array![[10.0]]]);
+            }
+            for _ in 0..2000 {
+                opt.update(&mut [&mut p], &[array![[0.1]]]);
+            }
+            let before = p[[0, 0]];
+            opt.update(&mut [&mut p], &[array![[0.1]]]);
+            before - p[[0, 0]]
+        };
+        let adagrad = step_after(&mut AdaGrad::new(0.01));
+        let rmsprop = step_after(&mut RmsProp::new(0.01));
+        assert!(adagrad < 1e-3);
+        assert!((rmsprop - 0.01).abs() < 1e-3, "rmsprop step {}", rmsprop);
+    }
+
+    #[test]
+    fn test_adam_bias_correction() {
+        // 有偏差修正时第一步 m / sqrt(v) = g / |g|，每个元素都移动约 lr（梯度很小时 eps 的影响才明显）
+        let mut opt = Adam::new(0.01);
+        let mut p = array![[1.0, 1.0, 1.0]];
+        opt.update(&mut [&mut p], &[array![[1e3, -2.0, 1e-3]]]);
+        let expected = [0.99, 1.01, 0.99];
+        for (v, e) in p.iter().zip(expected) {
+            assert!((v - e).abs() < 1e-4, "{} vs {}", v, e);
+        }
+
+        opt.reset();
+        let mut q = array![[1.0, 1.0, 1.0]];
+        opt.update(&mut [&mut q], &[array![[1e3, -2.0, 1e-3]]]);
+        assert_eq!(q, p);
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        // 把中途的状态装进新的优化器后继续更新，结果与不中断时完全相同
+        fn resumed(mut a: impl Optimizer, mut b: impl Optimizer) {
+            let mut p = array![[-7.0, 2.0]];
+            for _ in 0..3 {
+                let grads = [grad_f(&p)];
+                a.update(&mut [&mut p], &grads);
+            }
+            b.load_state(&[&p], a.state()).unwrap();
+            let mut q = p.clone();
+            for _ in 0..3 {
+                let (gp, gq) = ([grad_f(&p)], [grad_f(&q)]);
+                a.update(&mut [&mut p], &gp);
+                b.update(&mut [&mut q], &gq);
+            }
+            assert_eq!(p, q);
+        }
+        resumed(Momentum::new(0.1, 0.9), Momentum::new(0.1, 0.9));
+        resumed(AdaGrad::new(1.5), AdaGrad::new(1.5));
+        resumed(RmsProp::new(0.1), RmsProp::new(0.1));
+        resumed(Adam::new(0.3), Adam::new(0.3));
+
+        assert!(Sgd::new(0.1).state().is_empty());
+        let p = array![[1.0, 2.0]];
+        assert!(
+            Sgd::new(0.1)
+                .load_state(&[&p], vec![array![[1.0]]])
+                .is_err()
+        );
+        assert!(
+            Adam::new(0.1)
+                .load_state(&[&p], vec![array![[1.0]], array![[1.0]]])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_load_state_checks_shapes() {
+        let (p, q) = (array![[1.0, 2.0]], array![[3.0]]);
+        let mut adam = Adam::new(0.1);
+        adam.update(
+            &mut [&mut p.clone(), &mut q.clone()],
+            &[p.clone(), q.clone()],
+        );
+        let state = adam.state();
+        assert_eq!(state.len(), 5);
+
+        // Adam 的 [iter, m..., v...] 交给 Momentum：个数不对
+        let mut momentum = Momentum::new(0.1, 0.9);
+        assert!(momentum.load_state(&[&p, &q], state.clone()).is_err());
+        assert!(momentum.state().is_empty());
+
+        // 参数形状不同
+        let mut other = Adam::new(0.1);
+        assert!(other.load_state(&[&q, &p], state.clone()).is_err());
+        assert!(other.load_state(&[&p, &q], state).is_ok());
+
+        let mut adagrad = AdaGrad::new(0.1);
+        assert!(adagrad.load_state(&[&p], vec![array![[1.0]]]).is_err());
+    }
+
+    #[test]
+    fn test_clip_grads_by_norm() {
+        let mut grads = vec![array![[3.0]], array![[0.0, 4.0]]];
+        let norm = clip_grads_by_norm(&mut grads, 1.0);
+        assert_eq!(norm, 5.0);
+        // 方向不变，总范数变为 max_norm
+        assert!((grads[0][[0, 0]] - 0.6).abs() < 1e-12);
+        assert!((grads[1][[0, 1]] - 0.8).abs() < 1e-12);
+
+        // 范数没有超过上限时不做修改
+        let mut small = vec![array![[0.3, -0.4]]];
+        assert!((clip_grads_by_norm(&mut small, 1.0) - 0.5).abs() < 1e-12);
+        assert_eq!(small, vec![array![[0.3, -0.4]]]);
+    }
+
+    #[test]
+    fn test_clip_grads_by_value() {
+        let mut grads = vec![array![[-5.0, 0.5]], array![[2.0]]];
+        GradClip::Value(1.0).apply(&mut grads);
+        assert_eq!(grads, vec![array![[-1.0, 0.5]], array![[1.0]]]);
+    }
+
+    #[test]
+    fn test_momentum_decreases_loss() {
+        let start = f(&array![[-7.0, 2.0]]);
+        assert!(run(Momentum::new(0.1, 0.9), 50) < start);
+    }
+
+    #[test]
+    fn test_nesterov_beats_plain_momentum() {
+        let plain = run(Momentum::new(0.1, 0.9), 30);
+        let nesterov = run(Momentum::nesterov(0.1, 0.9), 30);
+        assert!(nesterov < plain, "nesterov {} >= plain {}", nesterov, plain);
+    }
+
+    #[test]
+    fn test_step_matches_update() {
+        let mut a = Momentum::new(0.1, 0.9);
+        let mut b = Momentum::new(0.1, 0.9);
+        let mut p = array![[-7.0, 2.0]];
+        let mut param = Parameter::new(array![[-7.0, 2.0]]);
+
+        for _ in 0..5 {
+            let grads = [grad_f(&p)];
+            a.update(&mut [&mut p], &grads);
+
+            param.grad = grad_f(&param.value);
+            b.step(&mut [&mut param]);
+        }
+        assert_eq!(param.value, p);
+    }
+
+    #[test]
+    fn test_lr_scale() {
+        let mut sgd = Sgd::new(0.1);
+        let (mut p1, mut p2) = (array![[1.0]], array![[1.0]]);
+        let grads = vec![array![[0.5]], array![[0.5]]];
+
+        sgd.update_with_lr_scale(&mut [&mut p1, &mut p2], &grads, Some(&[1.0, 2.0]));
+        let moved_1 = 1.0 - p1[[0, 0]];
+        let moved_2 = 1.0 - p2[[0, 0]];
+        assert!((moved_1 - 0.05).abs() < 1e-12);
+        assert!((moved_2 - 2.0 * moved_1).abs() < 1e-12);
+
+        // 倍率为 0 的参数不更新；倍率全为 1 与 update 相同，对每种优化器都成立
+        let optimizers: Vec<fn() -> Box<dyn Optimizer>> = vec![
+            || Box::new(Sgd::new(0.1)),
+            || Box::new(Momentum::new(0.1, 0.9)),
+            || Box::new(AdaGrad::new(0.1)),
+            || Box::new(RmsProp::new(0.1)),
+            || Box::new(Adam::new(0.1)),
+        ];
+        for make in optimizers {
+            let (mut a, mut b) = (array![[1.0, -1.0]], array![[2.0]]);
+            let grads = vec![array![[0.3, -0.2]], array![[0.4]]];
+            let mut opt = make();
+            opt.update_with_lr_scale(&mut [&mut a, &mut b], &grads, Some(&[1.0, 0.0]));
+            assert_eq!(b, array![[2.0]], "{}", opt.name());
+
+            let (mut c, mut d) = (array![[1.0, -1.0]], array![[2.0]]);
+            make().update(&mut [&mut c, &mut d], &grads);
+            assert_eq!(a, c, "{}", opt.name());
+        }
+    }
+
+    #[test]
+    fn test_dyn_optimizer_on_model_params() {
+        use crate::chapter02::init::HeInit;
+        use crate::chapter02::model::Trainable;
+        use crate::chapter02::network::SimpleNet;
+
+        let mut net = SimpleNet::with_initializer(2, 4, 2, &HeInit, 1);
+        let x = array![[0.6, 0.9], [-0.3, 0.4]];
+        let t = array![[0.0, 1.0], [1.0, 0.0]];
+        let before = net.loss(&x, &t);
+
+        let mut opt: Box<dyn Optimizer> = Box::new(Momentum::new(0.1, 0.9));
+        for _ in 0..20 {
+            let grads = net.gradient(&x, &t);
+            opt.update(&mut net.params_mut(), &grads);
+        }
+        assert!(net.loss(&x, &t) < before);
+    }
+}
@@ -0,0 +1,123 @@
+// src/chapter02/scheduler.rs
+use std::f64::consts::PI;
+
+/// 学习率调度：给出第 `step` 步（从 0 开始）使用的学习率
+pub trait LrScheduler {
+    fn lr(&self, step: usize) -> f64;
+}
+
+/// 余弦退火：在 `t_max` 步内按半个余弦周期从 `base_lr` 降到 `min_lr`，之后保持 `min_lr`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CosineAnnealing {
+    pub base_lr: f64,
+    pub min_lr: f64,
+    pub t_max: usize,
+}
+
+impl LrScheduler for CosineAnnealing {
+    fn lr(&self, step: usize) -> f64 {
+        if step >= self.t_max {
+            return self.min_lr;
+        }
+        cosine(self.base_lr, self.min_lr, step, self.t_max)
+    }
+}
+
+/// 带热重启的余弦退火（SGDR）：每个周期内做一次余弦退火，周期结束时学习率跳回 `base_lr`。
+///
+/// 第一个周期长 `t_0` 步，之后每个周期的长度是上一个的 `t_mult` 倍。
+/// 两者为 0 时计算周期会死循环，所以只能通过 `new` 设置
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CosineWarmRestarts {
+    pub base_lr: f64,
+    pub min_lr: f64,
+    t_0: usize,
+    t_mult: usize,
+}
+
+impl CosineWarmRestarts {
+    pub fn new(base_lr: f64, min_lr: f64, t_0: usize, t_mult: usize) -> Self {
+        assert!(t_0 > 0, "CosineWarmRestarts: t_0 must be positive");
+        assert!(t_mult > 0, "CosineWarmRestarts: t_mult must be positive");
+        Self {
+            base_lr,
+            min_lr,
+            t_0,
+            t_mult,
+        }
+    }
+
+    /// 第一个周期的步数
+    pub fn t_0(&self) -> usize {
+        self.t_0
+    }
+
+    /// 相邻周期长度的倍数
+    pub fn t_mult(&self) -> usize {
+        self.t_mult
+    }
+
+    /// 第 `step` 步所在周期内的位置和该周期的长度
+    fn cycle_position(&self, step: usize) -> (usize, usize) {
+        let (mut t, mut len) = (step, self.t_0);
+        while t >= len {
+            t -= len;
+            len = len.saturating_mul(self.t_mult);
+        }
+        (t, len)
+    }
+}
+
+impl LrScheduler for CosineWarmRestarts {
+    fn lr(&self, step: usize) -> f64 {
+        let (t, len) = self.cycle_position(step);
+        cosine(self.base_lr, self.min_lr, t, len)
+    }
+}
+
+/// min_lr + (base_lr - min_lr) * (1 + cos(π t / len)) / 2
+fn cosine(base_lr: f64, min_lr: f64, t: usize, len: usize) -> f64 {
+    min_lr + 0.5 * (base_lr - min_lr) * (1.0 + (PI * t as f64 / len as f64).cos())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cosine_annealing() {
+        let sched = CosineAnnealing {
+            base_lr: 0.1,
+            min_lr: 0.0,
+            t_max: 10,
+        };
+        assert_eq!(sched.lr(0), 0.1);
+        assert!((sched.lr(5) - 0.05).abs() < 1e-12);
+        assert_eq!(sched.lr(10), 0.0);
+        assert_eq!(sched.lr(100), 0.0);
+    }
+
+    #[test]
+    fn test_warm_restarts() {
+        let sched = CosineWarmRestarts::new(0.1, 0.001, 4, 2);
+
+        // 周期长度 4, 8, 16：重启点在第 0、4、12、28 步
+        for restart in [0, 4, 12, 28] {
+            assert_eq!(sched.lr(restart), 0.1);
+            if restart > 0 {
+                assert!(sched.lr(restart - 1) < 0.02);
+            }
+        }
+
+        // 每个周期内学习率单调下降，且周期长度按 t_mult 增长
+        let lrs: Vec<f64> = (0..28).map(|s| sched.lr(s)).collect();
+        let restarts: Vec<usize> = (1..lrs.len()).filter(|&i| lrs[i] > lrs[i - 1]).collect();
+        assert_eq!(restarts, vec![4, 12]);
+        assert!(lrs.iter().all(|&lr| (0.001..=0.1).contains(&lr)));
+
+        // 周期长度不会溢出
+        let lr = sched.lr(usize::MAX);
+        assert!((0.001..=0.1).contains(&lr));
+        assert_eq!((sched.t_0(), sched.t_mult()), (4, 2));
+    }
+}
@@ -0,0 +1,208 @@
+// src/chapter02/serialize.rs
+use super::model::Trainable;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use ndarray::Array2;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// `.model` 文件的魔数
+const MAGIC: &[u8; 4] = b"RDLM";
+const VERSION: u32 = 1;
+
+/// 按顺序写出一组参数。格式：魔数、版本号、参数个数，然后每个参数为
+/// 行数、列数（u32）和按行优先排列的 f64 数据，全部为小端序
+pub fn write_params<W: Write>(writer: &mut W, params: &[&Array2<f64>]) -> io::Result<()> {
+    writer.write_all(MAGIC)?;
+    writer.write_u32::<LittleEndian>(VERSION)?;
+    writer.write_u32::<LittleEndian>(params.len() as u32)?;
+    for param in params {
+        writer.write_u32::<LittleEndian>(param.nrows() as u32)?;
+        writer.write_u32::<LittleEndian>(param.ncols() as u32)?;
+        for &v in param.iter() {
+            writer.write_f64::<LittleEndian>(v)?;
+        }
+    }
+    Ok(())
+}
+
+/// 读取 `write_params` 写出的参数
+pub fn read_params<R: Read>(reader: &mut R) -> io::Result<Vec<Array2<f64>>> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(invalid_data("not a .model file (bad magic number)"));
+    }
+    let version = reader.read_u32::<LittleEndian>()?;
+    if version != VERSION {
+        return Err(invalid_data(format!(
+            "unsupported .model version {}",
+            version
+        )));
+    }
+
+    let count = reader.read_u32::<LittleEndian>()? as usize;
+    (0..count)
+        .map(|_| {
+            let rows = reader.read_u32::<LittleEndian>()? as usize;
+            let cols = reader.read_u32::<LittleEndian>()? as usize;
+            let mut data = vec![0.0; rows * cols];
+            reader.read_f64_into::<LittleEndian>(&mut data)?;
+            Array2::from_shape_vec((rows, cols), data).map_err(|e| invalid_data(e.to_string()))
+        })
+        .collect()
+}
+
+pub fn save_params<P: AsRef<Path>>(path: P, params: &[&Array2<f64>]) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_params(&mut writer, params)?;
+    writer.flush()
+}
+
+pub fn load_params<P: AsRef<Path>>(path: P) -> io::Result<Vec<Array2<f64>>> {
+    read_params(&mut BufReader::new(File::open(path)?))
+}
+
+/// 把模型的全部参数保存到 `.model` 文件
+pub fn save_model<M: Trainable, P: AsRef<Path>>(model: &M, path: P) -> io::Result<()> {
+    save_params(path, &model.params())
+}
+
+/// 从 `.model` 文件恢复模型参数；参数个数或形状与模型不一致时返回错误且不修改模型
+pub fn load_model<M: Trainable, P: AsRef<Path>>(model: &mut M, path: P) -> io::Result<()> {
+    assign_params(model.params_mut(), load_params(path)?)
+}
+
+/// 把读出的参数赋给模型；个数或形状不一致时返回错误且不修改任何参数
+pub(crate) fn assign_params(
+    mut params: Vec<&mut Array2<f64>>,
+    loaded: Vec<Array2<f64>>,
+) -> io::Result<()> {
+    let shapes: Vec<&Array2<f64>> = params.iter().map(|p| &**p).collect();
+    check_param_shapes(&shapes, &loaded)?;
+    for (param, value) in params.iter_mut().zip(loaded) {
+        **param = value;
+    }
+    Ok(())
+}
+
+/// 检查读出的参数与模型参数的个数和形状是否一致
+pub(crate) fn check_param_shapes(
+    params: &[&Array2<f64>],
+    loaded: &[Array2<f64>],
+) -> io::Result<()> {
+    if loaded.len() != params.len() {
+        return Err(invalid_data(format!(
+            "expected {} parameters, file has {}",
+            params.len(),
+            loaded.len()
+        )));
+    }
+    if let Some(i) = (0..loaded.len()).find(|&i| loaded[i].shape() != params[i].shape()) {
+        return Err(invalid_data(format!(
+            "parameter {} has shape {:?}, file has {:?}",
+            i,
+            params[i].shape(),
+            loaded[i].shape()
+        )));
+    }
+    Ok(())
+}
+
+/// 以 NumPy `.npy`（1.0 版）格式写出一个矩阵，Python 端可直接 `np.load` 读取。
+///
+/// 格式：魔数 `\x93NUMPY`、版本号 1.0、u16 小端的头部长度，然后是 Python 字典字面量形式的
+/// 头部（用空格补齐并以换行结尾，使数据从 64 字节对齐处开始），最后是按行优先排列的小端 f64
+pub fn write_npy<W: Write>(writer: &mut W, a: &Array2<f64>) -> io::Result<()> {
+    let mut header = format!(
+        "{{'descr': '<f8', 'fortran_order': False, 'shape': ({}, {}), }}",
+        a.nrows(),
+        a.ncols()
+    );
+    // 魔数 6 字节 + 版本 2 字节 + 头部长度 2 字节
+    let unpadded = 10 + header.len() + 1;
+    header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+    header.push('\n');
+
+    writer.write_all(b"\x93NUMPY")?;
+    writer.write_all(&[1, 0])?;
+    writer.write_u16::<LittleEndian>(header.len() as u16)?;
+    writer.write_all(header.as_bytes())?;
+    for &v in a.iter() {
+        writer.write_f64::<LittleEndian>(v)?;
+    }
+    Ok(())
+}
+
+pub fn save_npy<P: AsRef<Path>>(path: P, a: &Array2<f64>) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_npy(&mut writer, a)?;
+    writer.flush()
+}
+
+pub(crate) fn invalid_data<E: Into<Box<dyn std::error::Error + Send + Sync>>>(msg: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chapter02::init::XavierInit;
+    use crate::chapter02::network::SimpleNet;
+    use ndarray::array;
+
+    #[test]
+    fn test_params_round_trip() {
+        let a = array![[1.0, -2.5], [3.25, 0.0]];
+        let b = array![[0.125, 7.0, -1e-9]];
+        let mut buf = Vec::new();
+        write_params(&mut buf, &[&a, &b]).unwrap();
+
+        let loaded = read_params(&mut buf.as_slice()).unwrap();
+        assert_eq!(loaded, vec![a, b]);
+    }
+
+    #[test]
+    fn test_bad_magic_is_rejected() {
+        let err = read_params(&mut &b"NOPE\x01\x00\x00\x00"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_save_and_load_model() {
+        let path = std::env::temp_dir().join(format!("rdl_serialize_{}.model", std::process::id()));
+        let net = SimpleNet::with_initializer(3, 4, 2, &XavierInit, 1);
+        save_model(&net, &path).unwrap();
+
+        let mut restored = SimpleNet::with_initializer(3, 4, 2, &XavierInit, 2);
+        load_model(&mut restored, &path).unwrap();
+        assert_eq!(restored.w1, net.w1);
+        assert_eq!(restored.w2, net.w2);
+
+        let mut wrong_shape = SimpleNet::with_initializer(3, 5, 2, &XavierInit, 2);
+        assert!(load_model(&mut wrong_shape, &path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_write_npy_header() {
+        let a = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let mut buf = Vec::new();
+        write_npy(&mut buf, &a).unwrap();
+
+        assert_eq!(&buf[..6], b"\x93NUMPY");
+        assert_eq!(&buf[6..8], &[1, 0]);
+        let header_len = u16::from_le_bytes([buf[8], buf[9]]) as usize;
+        assert_eq!((10 + header_len) % 64, 0);
+
+        let header = std::str::from_utf8(&buf[10..10 + header_len]).unwrap();
+        assert!(header.contains("'descr': '<f8'"));
+        assert!(header.contains("'fortran_order': False"));
+        assert!(header.contains("'shape': (2, 3)"));
+        assert!(header.ends_with('\n'));
+
+        let data = &buf[10 + header_len..];
+        assert_eq!(data.len(), 6 * 8);
+        assert_eq!(f64::from_le_bytes(data[8..16].try_into().unwrap()), 2.0);
+    }
+}
@@ -1,7 +1,9 @@
 // src/chapter02/train_simple.rs
 use crate::chapter02::grad::numerical_gradient;
 use crate::chapter02::loss::cross_entropy_error;
+use crate::chapter02::model::Trainable;
 use crate::chapter02::network::SimpleNet;
+use crate::chapter02::optimizer::{Optimizer, Sgd};
 use ndarray::{Array2, array};
 
 pub fn loss_fn(net: &SimpleNet, x: &Array2<f64>, t: &Array2<f64>) -> f64 {
@@ -14,6 +16,7 @@ pub fn train_example() {
     let t = array![[0.0, 1.0]]; // 正确答案是第2类
 
     let mut net = SimpleNet::new(2, 3, 2); // 2输入 → 3隐藏 → 2输出
+    let mut optimizer = Sgd::new(0.1);
 
     for step in 0..5 {
         let loss_before = loss_fn(&net, &x, &t);
@@ -57,11 +60,7 @@ pub fn train_example() {
         );
 
         // 更新参数
-        let lr = 0.1;
-        net.w1 = &net.w1 + &grad_w1.mapv(|v| -lr * v);
-        net.b1 = &net.b1 + &grad_b1.mapv(|v| -lr * v);
-        net.w2 = &net.w2 + &grad_w2.mapv(|v| -lr * v);
-        net.b2 = &net.b2 + &grad_b2.mapv(|v| -lr * v);
+        optimizer.update(&mut net.params_mut(), &[grad_w1, grad_b1, grad_w2, grad_b2]);
     }
 
     let final_loss = loss_fn(&net, &x, &t);
@@ -0,0 +1,1237 @@
+// src/chapter02/trainer.rs
+use super::early_stopping::EarlyStopping;
+use super::metrics::{accuracy, argmax_rows};
+use super::model::Trainable;
+use super::optimizer::{GradClip, Optimizer, Sgd};
+use super::serialize::{
+    assign_params, check_param_shapes, invalid_data, read_params, save_model, write_params,
+};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use ndarray::{Array2, Axis};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// 一次训练运行的随机种子配置。
+///
+/// 权重初始化、数据打乱和 dropout 各自使用独立的随机数生成器，
+/// 这里用 splitmix64 从同一个 `seed` 派生出互不相关的子种子，
+/// 只要记录 `seed` 就能完整复现一次训练。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunConfig {
+    pub seed: u64,
+}
+
+impl RunConfig {
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// 权重初始化用的种子（传给 `SimpleNet::with_initializer` 等）
+    pub fn weight_seed(&self) -> u64 {
+        self.sub_seed(0)
+    }
+
+    /// 每个 epoch 打乱训练数据用的种子
+    pub fn shuffle_seed(&self) -> u64 {
+        self.sub_seed(1)
+    }
+
+    /// dropout 掩码用的种子
+    pub fn dropout_seed(&self) -> u64 {
+        self.sub_seed(2)
+    }
+
+    fn sub_seed(&self, stream: u64) -> u64 {
+        splitmix64(
+            self.seed
+                .wrapping_add(stream.wrapping_mul(0x9E37_79B9_7F4A_7C15)),
+        )
+    }
+}
+
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// 训练数据的打乱时机（需要通过 `with_run_config` 提供种子才会打乱）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShuffleMode {
+    /// 始终按原顺序取小批量
+    Never,
+    /// 训练开始时打乱一次，之后每个 epoch 复用同一顺序
+    Once,
+    /// 每个 epoch 都重新打乱（随机数生成器持续推进，每轮顺序不同）
+    #[default]
+    EveryEpoch,
+}
+
+/// 每个 epoch 结束时传给回调的训练状态
+pub struct TrainContext {
+    pub epoch: usize,
+    pub train_loss: f64,
+    pub train_acc: f64,
+    /// 测试集上的损失和准确率，只有通过 `train` 提供了测试集时才有
+    pub test_loss: Option<f64>,
+    pub test_acc: Option<f64>,
+    lr: Cell<f64>,
+}
+
+impl TrainContext {
+    pub fn lr(&self) -> f64 {
+        self.lr.get()
+    }
+
+    /// 修改之后的 epoch 使用的学习率（用于学习率调度等）
+    pub fn set_lr(&self, lr: f64) {
+        self.lr.set(lr);
+    }
+}
+
+/// 训练回调：在每个 epoch 结束后执行自定义逻辑（日志、学习率调度、保存等）
+pub trait Callback {
+    fn on_epoch_end(&mut self, ctx: &TrainContext);
+
+    /// 取出回调自身遇到的错误（例如写日志失败）。`Trainer` 在每次 `on_epoch_end` 之后调用，
+    /// 把第一个错误记录在 `TrainHistory::callback_error` 中，训练照常继续
+    fn take_error(&mut self) -> Option<io::Error> {
+        None
+    }
+}
+
+/// 训练过程中每个 epoch 的指标
+#[derive(Debug, Clone, Default)]
+pub struct TrainHistory {
+    pub train_loss: Vec<f64>,
+    pub train_acc: Vec<f64>,
+    /// 每个 epoch 结束时测试集上的损失和准确率，没有测试集（`fit`、`fit_generator`）时为空
+    pub test_loss: Vec<f64>,
+    pub test_acc: Vec<f64>,
+    /// 每个 epoch 的耗时（秒），包含参数更新和 epoch 末的评估
+    pub epoch_secs: Vec<f64>,
+    /// 启用早停时，被观察的指标最好的 epoch（从 0 开始）
+    pub best_epoch: Option<usize>,
+    /// 第一条记录对应的 epoch（从 0 开始），用 `resume_from` 继续训练时不为 0
+    pub first_epoch: usize,
+    /// 保存检查点失败时的错误；出错后训练立即停止，已记录的 epoch 不受影响
+    pub checkpoint_error: Option<Arc<io::Error>>,
+    /// 回调报告的第一个错误（见 `Callback::take_error`），不会中断训练
+    pub callback_error: Option<Arc<io::Error>>,
+}
+
+/// 训练摘要：每个 epoch 一行，最后一行是总耗时
+impl fmt::Display for TrainHistory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (epoch, ((loss, acc), secs)) in self
+            .train_loss
+            .iter()
+            .zip(&self.train_acc)
+            .zip(&self.epoch_secs)
+            .enumerate()
+        {
+            write!(
+                f,
+                "epoch {}: loss {:.4}, acc {:.2}%",
+                self.first_epoch + epoch + 1,
+                loss,
+                acc * 100.0
+            )?;
+            if let (Some(loss), Some(acc)) = (self.test_loss.get(epoch), self.test_acc.get(epoch)) {
+                write!(f, ", test loss {:.4}, test acc {:.2}%", loss, acc * 100.0)?;
+            }
+            writeln!(f, ", {:.3}s", secs)?;
+        }
+        if let Some(e) = &self.checkpoint_error {
+            writeln!(f, "stopped: failed to save checkpoint: {}", e)?;
+        }
+        if let Some(e) = &self.callback_error {
+            writeln!(f, "warning: callback failed: {}", e)?;
+        }
+        write!(f, "total time: {:.3}s", self.epoch_secs.iter().sum::<f64>())
+    }
+}
+
+/// 训练检查点文件的魔数
+const CHECKPOINT_MAGIC: &[u8; 4] = b"RDLC";
+const CHECKPOINT_VERSION: u32 = 1;
+
+/// 小批量训练器：打乱数据、逐批求梯度并交给优化器更新参数，记录每个 epoch 的指标。
+///
+/// 默认使用学习率为 `lr` 的 `Sgd`，可以用 `with_optimizer` 换成 Momentum、Adam 等
+pub struct Trainer<M: Trainable> {
+    pub model: M,
+    /// 学习率，每步更新前同步给优化器，回调通过 `TrainContext::set_lr` 修改它
+    pub lr: f64,
+    pub epochs: usize,
+    pub batch_size: usize,
+    /// 每隔多少个参数更新步保存一次 `ckpt_{step}.model`，None 表示不保存
+    pub periodic_save: Option<usize>,
+    /// 周期性检查点保存的目录
+    pub checkpoint_dir: PathBuf,
+    /// 只保留最近的 K 个周期性检查点，None 表示全部保留
+    pub keep_last: Option<usize>,
+    /// 何时打乱训练数据，默认每个 epoch 都打乱
+    pub shuffle: ShuffleMode,
+    /// 每步更新前对梯度做的裁剪，None 表示不裁剪
+    pub grad_clip: Option<GradClip>,
+    /// 指标不再改善时提前结束训练，None 表示总是训练满 `epochs` 轮
+    pub early_stopping: Option<EarlyStopping>,
+    /// 每个 epoch 结束时把完整的训练状态保存到这个文件（见 `with_checkpoint`），None 表示不保存
+    pub checkpoint_path: Option<PathBuf>,
+    optimizer: Box<dyn Optimizer>,
+    callbacks: Vec<Box<dyn Callback>>,
+    run_config: Option<RunConfig>,
+    step_count: usize,
+    /// 已完成的 epoch 数
+    epoch: usize,
+    /// 由 `resume_from` 设置：下一次训练从第 `epoch` 轮继续，而不是从头开始
+    resuming: bool,
+    saved_checkpoints: VecDeque<PathBuf>,
+}
+
+impl<M: Trainable> Trainer<M> {
+    pub fn new(model: M, lr: f64, epochs: usize, batch_size: usize) -> Self {
+        Self {
+            model,
+            lr,
+            epochs,
+            batch_size,
+            periodic_save: None,
+            checkpoint_dir: PathBuf::from("."),
+            keep_last: None,
+            shuffle: ShuffleMode::default(),
+            grad_clip: None,
+            early_stopping: None,
+            checkpoint_path: None,
+            optimizer: Box::new(Sgd::new(lr)),
+            callbacks: Vec::new(),
+            run_config: None,
+            step_count: 0,
+            epoch: 0,
+            resuming: false,
+            saved_checkpoints: VecDeque::new(),
+        }
+    }
+
+    /// 每 `every` 步把模型保存到 `dir/ckpt_{step}.model`（`dir` 不存在时创建）；
+    /// `keep_last` 为 Some(k) 时删除更早的检查点，只保留最近 k 个。
+    /// 保存或删除失败时训练立即停止，错误记录在 `TrainHistory::checkpoint_error` 中
+    pub fn with_periodic_save(
+        mut self,
+        dir: impl Into<PathBuf>,
+        every: usize,
+        keep_last: Option<usize>,
+    ) -> Self {
+        assert!(every > 0, "periodic_save interval must be positive");
+        self.checkpoint_dir = dir.into();
+        // 创建失败时第一次保存会以同样的原因失败，错误在那时报告
+        let _ = std::fs::create_dir_all(&self.checkpoint_dir);
+        self.periodic_save = Some(every);
+        self.keep_last = keep_last;
+        self
+    }
+
+    /// 使用给定的种子配置：按 `shuffle_seed` 打乱数据顺序（时机由 `shuffle` 决定）。
+    /// 不设置时按原顺序取小批量
+    pub fn with_run_config(mut self, config: RunConfig) -> Self {
+        self.run_config = Some(config);
+        self
+    }
+
+    pub fn with_shuffle(mut self, mode: ShuffleMode) -> Self {
+        self.shuffle = mode;
+        self
+    }
+
+    /// 每步参数更新前按 `clip` 裁剪梯度，防止梯度爆炸时一步跳得太远
+    pub fn with_grad_clip(mut self, clip: GradClip) -> Self {
+        self.grad_clip = Some(clip);
+        self
+    }
+
+    /// 启用早停。观察测试集指标（`Monitor::TestLoss` / `TestAcc`）时需要用 `train` 训练，
+    /// 否则 `fit` / `fit_generator` 在开始训练前就会 panic
+    pub fn with_early_stopping(mut self, early_stopping: EarlyStopping) -> Self {
+        self.early_stopping = Some(early_stopping);
+        self
+    }
+
+    /// 每个 epoch 结束时调用 `save_checkpoint(path)`，中断后可用 `resume_from(path)` 继续训练。
+    /// 保存失败时训练立即停止，错误记录在 `TrainHistory::checkpoint_error` 中。
+    ///
+    /// 与 `with_periodic_save` 的区别：这里只维护一个可以继续训练的完整检查点；
+    /// `with_periodic_save` 按步数保存一系列只含模型参数的 `.model` 快照，用于事后挑选或评估。
+    /// 想让长时间的训练在中断后能继续，用这个
+    pub fn with_checkpoint(mut self, path: impl Into<PathBuf>) -> Self {
+        self.checkpoint_path = Some(path.into());
+        self
+    }
+
+    /// 用 `optimizer` 更新参数，`lr` 改为该优化器的学习率
+    pub fn with_optimizer(mut self, optimizer: impl Optimizer + 'static) -> Self {
+        self.lr = optimizer.lr();
+        self.optimizer = Box::new(optimizer);
+        self
+    }
+
+    pub fn add_callback(&mut self, callback: Box<dyn Callback>) {
+        self.callbacks.push(callback);
+    }
+
+    /// 在 (x, t) 上训练 `epochs` 轮，t 为 one-hot 标签
+    pub fn fit(&mut self, x: &Array2<f64>, t: &Array2<f64>) -> TrainHistory {
+        self.run(x, t, None)
+    }
+
+    /// 与 `fit` 相同，另外在每个 epoch 结束时评估 `test` 上的准确率，
+    /// 记录到 `TrainHistory::test_acc` 并通过 `TrainContext::test_acc` 传给回调。
+    /// 两个参数都是 `(x, t)`，t 为 one-hot 标签
+    pub fn train(
+        &mut self,
+        train: (&Array2<f64>, &Array2<f64>),
+        test: (&Array2<f64>, &Array2<f64>),
+    ) -> TrainHistory {
+        assert_eq!(test.0.nrows(), test.1.nrows());
+        self.run(train.0, train.1, Some(test))
+    }
+
+    /// 保存继续训练所需的全部状态：模型参数、优化器类型和状态、学习率、已完成的 epoch 数、
+    /// 参数更新步数和打乱数据用的随机种子。
+    ///
+    /// 打乱数据的随机数生成器由种子和已完成的 epoch 数完全确定，恢复时重新播种并重放即可，
+    /// 所以只保存种子。模型内部的随机状态（如 Dropout 的掩码生成器）和早停的记录不在其中，
+    /// 早停在继续训练后重新计数。
+    /// 先写入临时文件再重命名，保存过程中被中断也不会损坏已有的检查点
+    pub fn save_checkpoint<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+
+        let mut writer = BufWriter::new(File::create(&tmp)?);
+        writer.write_all(CHECKPOINT_MAGIC)?;
+        writer.write_u32::<LittleEndian>(CHECKPOINT_VERSION)?;
+        let name = self.optimizer.name().as_bytes();
+        writer.write_u8(name.len() as u8)?;
+        writer.write_all(name)?;
+        writer.write_u64::<LittleEndian>(self.epoch as u64)?;
+        writer.write_u64::<LittleEndian>(self.step_count as u64)?;
+        writer.write_f64::<LittleEndian>(self.lr)?;
+        writer.write_u8(self.run_config.is_some() as u8)?;
+        writer.write_u64::<LittleEndian>(self.run_config.map_or(0, |config| config.seed))?;
+        write_params(&mut writer, &self.model.params())?;
+        let state = self.optimizer.state();
+        write_params(&mut writer, &state.iter().collect::<Vec<_>>())?;
+        writer.flush()?;
+        drop(writer);
+        std::fs::rename(&tmp, path)
+    }
+
+    /// 从 `save_checkpoint` 保存的文件恢复训练状态，之后的 `fit` / `train` 从中断的 epoch 继续，
+    /// 训练结果与不中断时一致（`TrainHistory` 只包含继续训练的那几轮）。
+    ///
+    /// 训练器需要用与保存时相同的模型结构、优化器类型和数据创建；参数个数或形状不一致、
+    /// 优化器类型不同或其状态与参数不对应时返回错误且不修改训练器
+    pub fn resume_from<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != CHECKPOINT_MAGIC {
+            return Err(invalid_data("not a training checkpoint (bad magic number)"));
+        }
+        let version = reader.read_u32::<LittleEndian>()?;
+        if version != CHECKPOINT_VERSION {
+            return Err(invalid_data(format!(
+                "unsupported checkpoint version {}",
+                version
+            )));
+        }
+        let mut name = vec![0u8; reader.read_u8()? as usize];
+        reader.read_exact(&mut name)?;
+        if name != self.optimizer.name().as_bytes() {
+            return Err(invalid_data(format!(
+                "checkpoint was saved with {}, but the trainer uses {}",
+                String::from_utf8_lossy(&name),
+                self.optimizer.name()
+            )));
+        }
+        let epoch = reader.read_u64::<LittleEndian>()? as usize;
+        let step_count = reader.read_u64::<LittleEndian>()? as usize;
+        let lr = reader.read_f64::<LittleEndian>()?;
+        let has_seed = reader.read_u8()? != 0;
+        let seed = reader.read_u64::<LittleEndian>()?;
+        let params = read_params(&mut reader)?;
+        let state = read_params(&mut reader)?;
+
+        // 先检查完所有可能出错的地方再修改，避免出错时训练器处于半恢复的状态
+        check_param_shapes(&self.model.params(), &params)?;
+        self.optimizer.load_state(&self.model.params(), state)?;
+        assign_params(self.model.params_mut(), params)?;
+        self.epoch = epoch;
+        self.step_count = step_count;
+        self.lr = lr;
+        self.run_config = has_seed.then(|| RunConfig::new(seed));
+        self.resuming = true;
+        Ok(())
+    }
+
+    fn run(
+        &mut self,
+        x: &Array2<f64>,
+        t: &Array2<f64>,
+        test: Option<(&Array2<f64>, &Array2<f64>)>,
+    ) -> TrainHistory {
+        assert_eq!(x.nrows(), t.nrows());
+        let mut history = TrainHistory::default();
+        let first_epoch = self.start_run(test.is_some());
+        history.first_epoch = first_epoch;
+        let mut shuffle_rng = match self.shuffle {
+            ShuffleMode::Never => None,
+            ShuffleMode::Once | ShuffleMode::EveryEpoch => self
+                .run_config
+                .map(|config| StdRng::seed_from_u64(config.shuffle_seed())),
+        };
+        let mut order: Vec<usize> = (0..x.nrows()).collect();
+        if self.shuffle == ShuffleMode::Once
+            && let Some(rng) = shuffle_rng.as_mut()
+        {
+            order.shuffle(rng);
+        }
+        // 恢复训练时重放之前各轮的打乱，使数据顺序和随机数生成器的状态与中断前一致
+        if self.shuffle == ShuffleMode::EveryEpoch
+            && let Some(rng) = shuffle_rng.as_mut()
+        {
+            for _ in 0..first_epoch {
+                order.shuffle(rng);
+            }
+        }
+
+        'epochs: for epoch in first_epoch..self.epochs {
+            let start = Instant::now();
+            if self.shuffle == ShuffleMode::EveryEpoch
+                && let Some(rng) = shuffle_rng.as_mut()
+            {
+                order.shuffle(rng);
+            }
+            for batch in order.chunks(self.batch_size.max(1)) {
+                let x_batch = x.select(Axis(0), batch);
+                let t_batch = t.select(Axis(0), batch);
+                if let Err(e) = self.step(&x_batch, &t_batch) {
+                    history.checkpoint_error = Some(Arc::new(e));
+                    break 'epochs;
+                }
+            }
+
+            let ctx = TrainContext {
+                epoch,
+                train_loss: self.model.loss(x, t),
+                train_acc: self.accuracy(x, t),
+                test_loss: test.map(|(x, t)| self.model.loss(x, t)),
+                test_acc: test.map(|(x, t)| self.accuracy(x, t)),
+                lr: Cell::new(self.lr),
+            };
+            let secs = start.elapsed().as_secs_f64();
+            if self.end_epoch(ctx, secs, &mut history) {
+                break;
+            }
+        }
+
+        self.finish_run(&mut history);
+        history
+    }
+
+    /// 从闭包中逐个取小批量 (x, t) 训练，每个 epoch 取 `steps_per_epoch` 个，共 `epochs` 轮，
+    /// 适合无法表示成 `Dataset` 的自定义数据管道。闭包返回 None 时提前结束训练。
+    ///
+    /// 没有完整的训练集可供评估，所以每个 epoch 记录的损失和准确率是
+    /// 该 epoch 内各个小批量在参数更新前的平均值
+    pub fn fit_generator(
+        &mut self,
+        mut next_batch: impl FnMut() -> Option<(Array2<f64>, Array2<f64>)>,
+        steps_per_epoch: usize,
+        epochs: usize,
+    ) -> TrainHistory {
+        let mut history = TrainHistory::default();
+        let first_epoch = self.start_run(false);
+        history.first_epoch = first_epoch;
+
+        for epoch in first_epoch..epochs {
+            let start = Instant::now();
+            let (mut loss_sum, mut acc_sum, mut steps) = (0.0, 0.0, 0);
+            for _ in 0..steps_per_epoch {
+                let Some((x_batch, t_batch)) = next_batch() else {
+                    break;
+                };
+                loss_sum += self.model.loss(&x_batch, &t_batch);
+                acc_sum += self.accuracy(&x_batch, &t_batch);
+                steps += 1;
+                if let Err(e) = self.step(&x_batch, &t_batch) {
+                    history.checkpoint_error = Some(Arc::new(e));
+                    break;
+                }
+            }
+            if history.checkpoint_error.is_some() {
+                break;
+            }
+            if steps == 0 {
+                break;
+            }
+
+            let n = steps as f64;
+            let ctx = TrainContext {
+                epoch,
+                train_loss: loss_sum / n,
+                train_acc: acc_sum / n,
+                test_loss: None,
+                test_acc: None,
+                lr: Cell::new(self.lr),
+            };
+            let secs = start.elapsed().as_secs_f64();
+            if self.end_epoch(ctx, secs, &mut history) || steps < steps_per_epoch {
+                break;
+            }
+        }
+
+        self.finish_run(&mut history);
+        history
+    }
+
+    fn accuracy(&self, x: &Array2<f64>, t: &Array2<f64>) -> f64 {
+        accuracy(&argmax_rows(&self.model.predict(x)), &argmax_rows(t))
+    }
+
+    /// 返回本次训练的第一个 epoch：刚调用过 `resume_from` 时从中断处继续，否则从 0 开始。
+    /// 早停观察测试集指标而本次训练没有测试集时，在训练任何一步之前 panic
+    fn start_run(&mut self, has_test_set: bool) -> usize {
+        if let Some(early_stopping) = self.early_stopping.as_mut() {
+            assert!(
+                has_test_set || !early_stopping.monitor.needs_test_set(),
+                "early stopping monitors {:?}, which needs a test set (use Trainer::train)",
+                early_stopping.monitor
+            );
+            early_stopping.reset();
+        }
+        if !std::mem::take(&mut self.resuming) {
+            self.epoch = 0;
+        }
+        self.epoch
+    }
+
+    /// 记录指标、执行回调并保存检查点，返回是否应当停止训练（早停或保存失败）
+    fn end_epoch(
+        &mut self,
+        ctx: TrainContext,
+        epoch_secs: f64,
+        history: &mut TrainHistory,
+    ) -> bool {
+        history.train_loss.push(ctx.train_loss);
+        history.train_acc.push(ctx.train_acc);
+        history.test_loss.extend(ctx.test_loss);
+        history.test_acc.extend(ctx.test_acc);
+        history.epoch_secs.push(epoch_secs);
+
+        for callback in self.callbacks.iter_mut() {
+            callback.on_epoch_end(&ctx);
+            if let Some(e) = callback.take_error()
+                && history.callback_error.is_none()
+            {
+                history.callback_error = Some(Arc::new(e));
+            }
+        }
+        self.lr = ctx.lr();
+        self.epoch = ctx.epoch + 1;
+
+        if let Some(path) = self.checkpoint_path.clone()
+            && let Err(e) = self.save_checkpoint(&path)
+        {
+            history.checkpoint_error = Some(Arc::new(checkpoint_error(&path, e)));
+            return true;
+        }
+
+        let Some(early_stopping) = self.early_stopping.as_mut() else {
+            return false;
+        };
+        // start_run 已经确认被观察的指标存在
+        let value = early_stopping
+            .monitor
+            .value(&ctx)
+            .expect("monitored metric is missing");
+        early_stopping.observe(ctx.epoch, value, &self.model.params())
+    }
+
+    /// 启用早停时记录最好的 epoch，并按需恢复那时的参数
+    fn finish_run(&mut self, history: &mut TrainHistory) {
+        let Some(early_stopping) = self.early_stopping.as_ref() else {
+            return;
+        };
+        history.best_epoch = early_stopping.best_epoch();
+        if let Some(best) = early_stopping.best_params() {
+            for (param, value) in self.model.params_mut().into_iter().zip(best) {
+                param.assign(value);
+            }
+        }
+    }
+
+    /// 一次参数更新；只有周期性保存失败时返回错误
+    fn step(&mut self, x: &Array2<f64>, t: &Array2<f64>) -> io::Result<()> {
+        let mut grads = self.model.gradient(x, t);
+        if let Some(clip) = &self.grad_clip {
+            clip.apply(&mut grads);
+        }
+        self.optimizer.set_lr(self.lr);
+        self.optimizer.update(&mut self.model.params_mut(), &grads);
+
+        self.step_count += 1;
+        if let Some(every) = self.periodic_save
+            && self.step_count.is_multiple_of(every)
+        {
+            self.save_periodic_checkpoint()?;
+        }
+        Ok(())
+    }
+
+    fn save_periodic_checkpoint(&mut self) -> io::Result<()> {
+        let path = self
+            .checkpoint_dir
+            .join(format!("ckpt_{}.model", self.step_count));
+        save_model(&self.model, &path).map_err(|e| checkpoint_error(&path, e))?;
+        self.saved_checkpoints.push_back(path);
+
+        if let Some(keep) = self.keep_last {
+            while self.saved_checkpoints.len() > keep {
+                let Some(old) = self.saved_checkpoints.pop_front() else {
+                    break;
+                };
+                // 已经被别人删掉的旧检查点不算错误
+                match std::fs::remove_file(&old) {
+                    Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                        return Err(checkpoint_error(&old, e));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 在错误信息中带上检查点路径
+fn checkpoint_error(path: &Path, e: io::Error) -> io::Error {
+    io::Error::new(e.kind(), format!("{}: {}", path.display(), e))
+}
+
+/// 学习率范围测试（LR finder）：从 `min_lr` 到 `max_lr` 按指数递增学习率，
+/// 每一步用当前学习率在 `data` 上做一次梯度下降并记录之后的损失。
+///
+/// 在模型的副本上进行，不修改传入的模型。返回 `(lrs, losses)`，画成曲线后
+/// 一般选择损失下降最快处附近、发散之前的学习率。
+pub fn lr_range_test<M: Trainable + Clone>(
+    model: &M,
+    data: (&Array2<f64>, &Array2<f64>),
+    min_lr: f64,
+    max_lr: f64,
+    steps: usize,
+) -> (Vec<f64>, Vec<f64>) {
+    assert!(
+        min_lr > 0.0 && max_lr > min_lr,
+        "lr_range_test: expected 0 < min_lr < max_lr"
+    );
+    let (x, t) = data;
+    let mut model = model.clone();
+    let ratio = max_lr / min_lr;
+
+    let mut lrs = Vec::with_capacity(steps);
+    let mut losses = Vec::with_capacity(steps);
+    for i in 0..steps {
+        let frac = if steps > 1 {
+            i as f64 / (steps - 1) as f64
+        } else {
+            0.0
+        };
+        let lr = min_lr * ratio.powf(frac);
+
+        let grads = model.gradient(x, t);
+        for (param, grad) in model.params_mut().into_iter().zip(grads.iter()) {
+            param.scaled_add(-lr, grad);
+        }
+
+        lrs.push(lr);
+        losses.push(model.loss(x, t));
+    }
+    (lrs, losses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chapter02::early_stopping::Monitor;
+    use crate::chapter02::init::XavierInit;
+    use crate::chapter02::model::Model;
+    use crate::chapter02::network::SimpleNet;
+    use crate::chapter02::optimizer::{AdaGrad, Adam, Momentum, RmsProp};
+    use ndarray::{array, s};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct CountingCallback {
+        calls: Rc<Cell<usize>>,
+    }
+
+    impl Callback for CountingCallback {
+        fn on_epoch_end(&mut self, _ctx: &TrainContext) {
+            self.calls.set(self.calls.get() + 1);
+        }
+    }
+
+    struct HalveLr;
+
+    impl Callback for HalveLr {
+        fn on_epoch_end(&mut self, ctx: &TrainContext) {
+            ctx.set_lr(ctx.lr() * 0.5);
+        }
+    }
+
+    fn toy_data() -> (Array2<f64>, Array2<f64>) {
+        let x = array![[0.6, 0.9], [0.9, 0.1], [0.2, 0.8], [0.8, 0.3]];
+        let t = array![[0.0, 1.0], [1.0, 0.0], [0.0, 1.0], [1.0, 0.0]];
+        (x, t)
+    }
+
+    #[test]
+    fn test_callback_called_once_per_epoch() {
+        let (x, t) = toy_data();
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+        let calls = Rc::new(Cell::new(0));
+
+        let mut trainer = Trainer::new(net, 0.5, 4, 2);
+        trainer.add_callback(Box::new(CountingCallback {
+            calls: Rc::clone(&calls),
+        }));
+        let history = trainer.fit(&x, &t);
+
+        assert_eq!(calls.get(), 4);
+        assert_eq!(history.train_loss.len(), 4);
+        assert!(history.train_loss[3] < history.train_loss[0]);
+    }
+
+    #[test]
+    fn test_grad_clip_limits_step_size() {
+        let (x, t) = toy_data();
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+        let before: Vec<Array2<f64>> = net.params().into_iter().cloned().collect();
+
+        // 整个数据集一个批次、只走一步：参数变化的总范数不超过 lr * max_norm
+        let mut trainer = Trainer::new(net, 0.5, 1, 4).with_grad_clip(GradClip::Norm(0.01));
+        trainer.fit(&x, &t);
+        let moved = trainer
+            .model
+            .params()
+            .iter()
+            .zip(&before)
+            .flat_map(|(after, before)| (*after - before).into_iter())
+            .map(|d| d * d)
+            .sum::<f64>()
+            .sqrt();
+        assert!(moved > 0.0);
+        assert!(moved <= 0.5 * 0.01 + 1e-12, "moved {}", moved);
+    }
+
+    #[test]
+    fn test_epoch_timing() {
+        let (x, t) = toy_data();
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+
+        let history = Trainer::new(net, 0.5, 5, 2).fit(&x, &t);
+        assert_eq!(history.epoch_secs.len(), 5);
+        assert!(history.epoch_secs.iter().all(|&s| s >= 0.0));
+
+        let summary = history.to_string();
+        assert_eq!(summary.lines().count(), 6);
+        assert!(summary.starts_with("epoch 1: loss "));
+        assert!(summary.ends_with('s') && summary.contains("total time: "));
+    }
+
+    #[test]
+    fn test_callback_can_change_lr() {
+        let (x, t) = toy_data();
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+
+        let mut trainer = Trainer::new(net, 1.0, 3, 4);
+        trainer.add_callback(Box::new(HalveLr));
+        trainer.fit(&x, &t);
+
+        assert!((trainer.lr - 0.125).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_train_tracks_test_accuracy() {
+        let (x, t) = toy_data();
+        let (x_test, t_test) = (
+            x.slice(s![..2, ..]).to_owned(),
+            t.slice(s![..2, ..]).to_owned(),
+        );
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+
+        let mut trainer = Trainer::new(net, 0.5, 3, 2);
+        let history = trainer.train((&x, &t), (&x_test, &t_test));
+        assert_eq!(history.test_acc.len(), 3);
+        let final_acc = accuracy(
+            &argmax_rows(&trainer.model.predict(&x_test)),
+            &argmax_rows(&t_test),
+        );
+        assert_eq!(history.test_acc[2], final_acc);
+        assert!(history.to_string().contains(", test acc "));
+
+        // 没有测试集时不记录
+        assert!(trainer.fit(&x, &t).test_acc.is_empty());
+    }
+
+    #[test]
+    fn test_with_optimizer() {
+        let (x, t) = toy_data();
+        let run = |trainer: Trainer<SimpleNet>| {
+            let mut trainer = trainer;
+            trainer.fit(&x, &t);
+            trainer.model.w1
+        };
+        let net = || SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+
+        // 默认就是学习率为 lr 的 SGD
+        let sgd = run(Trainer::new(net(), 0.5, 3, 2).with_optimizer(Sgd::new(0.5)));
+        assert_eq!(sgd, run(Trainer::new(net(), 0.5, 3, 2)));
+
+        let mut trainer = Trainer::new(net(), 0.5, 20, 2).with_optimizer(Adam::new(0.05));
+        assert_eq!(trainer.lr, 0.05);
+        let history = trainer.fit(&x, &t);
+        assert!(history.train_loss[19] < history.train_loss[0]);
+        assert_ne!(trainer.model.w1, sgd);
+    }
+
+    #[test]
+    fn test_callback_changes_optimizer_lr() {
+        let (x, t) = toy_data();
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+
+        struct ZeroLr;
+        impl Callback for ZeroLr {
+            fn on_epoch_end(&mut self, ctx: &TrainContext) {
+                ctx.set_lr(0.0);
+            }
+        }
+        // 回调把学习率设为 0 之后，Adam 不再移动参数
+        let mut trainer = Trainer::new(net, 0.5, 1, 2).with_optimizer(Adam::new(0.05));
+        trainer.add_callback(Box::new(ZeroLr));
+        trainer.fit(&x, &t);
+        let after_first = trainer.model.w1.clone();
+        trainer.fit(&x, &t);
+        assert_eq!(trainer.model.w1, after_first);
+    }
+
+    #[test]
+    fn test_early_stopping_restores_best_weights() {
+        let (x, t) = toy_data();
+        // 标签与训练集相反的"测试集"：训练越久测试损失越大，第 0 轮就是最好的
+        let t_flipped = t.mapv(|v| 1.0 - v);
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+
+        let mut trainer = Trainer::new(net, 0.5, 50, 2)
+            .with_early_stopping(EarlyStopping::new(Monitor::TestLoss, 3));
+        let history = trainer.train((&x, &t), (&x, &t_flipped));
+
+        assert_eq!(history.train_loss.len(), 4);
+        assert_eq!(history.best_epoch, Some(0));
+        assert!(history.test_loss[3] > history.test_loss[0]);
+        assert_eq!(trainer.model.loss(&x, &t_flipped), history.test_loss[0]);
+
+        // 关闭恢复时保留最后一轮的参数
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+        let mut trainer = Trainer::new(net, 0.5, 50, 2).with_early_stopping(
+            EarlyStopping::new(Monitor::TestLoss, 3).with_restore_best_weights(false),
+        );
+        let history = trainer.train((&x, &t), (&x, &t_flipped));
+        assert_eq!(trainer.model.loss(&x, &t_flipped), history.test_loss[3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "needs a test set")]
+    fn test_early_stopping_on_test_metric_requires_test_set() {
+        let (x, t) = toy_data();
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+        Trainer::new(net, 0.5, 5, 2)
+            .with_early_stopping(EarlyStopping::new(Monitor::TestAcc, 2))
+            .fit(&x, &t);
+    }
+
+    #[test]
+    #[should_panic(expected = "needs a test set")]
+    fn test_early_stopping_checks_test_set_before_training() {
+        // 检查发生在取第一个小批量之前
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+        Trainer::new(net, 0.5, 5, 2)
+            .with_early_stopping(EarlyStopping::new(Monitor::TestLoss, 2))
+            .fit_generator(|| panic!("a batch was requested"), 4, 5);
+    }
+
+    #[test]
+    fn test_resume_matches_uninterrupted_training() {
+        let x = Array2::from_shape_fn((8, 2), |(i, j)| ((i * 2 + j) as f64 * 0.37).sin());
+        let t = Array2::from_shape_fn((8, 2), |(i, j)| ((i + j) % 2) as f64);
+        let path = std::env::temp_dir().join(format!("rdl_resume_{}.ckpt", std::process::id()));
+        let trainer = |epochs: usize, init_seed: u64| {
+            let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, init_seed);
+            Trainer::new(net, 0.1, epochs, 3)
+                .with_run_config(RunConfig::new(11))
+                .with_optimizer(Adam::new(0.05))
+        };
+
+        let mut full = trainer(6, 0);
+        let full_history = full.fit(&x, &t);
+
+        // 训练 3 轮后"中断"，用另一个初始化的模型从检查点继续
+        let mut first = trainer(3, 0).with_checkpoint(&path);
+        first.fit(&x, &t);
+        let mut resumed = Trainer::new(
+            SimpleNet::with_initializer(2, 3, 2, &XavierInit, 99),
+            0.1,
+            6,
+            3,
+        )
+        .with_optimizer(Adam::new(0.05));
+        resumed.resume_from(&path).unwrap();
+        let history = resumed.fit(&x, &t);
+
+        assert_eq!(history.train_loss, full_history.train_loss[3..]);
+        assert_eq!(history.first_epoch, 3);
+        assert!(history.to_string().starts_with("epoch 4: "));
+        assert_eq!(resumed.model.w1, full.model.w1);
+        assert_eq!(resumed.model.b2, full.model.b2);
+        assert_eq!(resumed.step_count, full.step_count);
+
+        // 恢复只作用于下一次训练，再次 fit 从头训练 6 轮
+        assert_eq!(resumed.fit(&x, &t).train_loss.len(), 6);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_checkpoint_failure_stops_training() {
+        let (x, t) = toy_data();
+        let path = std::env::temp_dir()
+            .join(format!("rdl_missing_dir_{}", std::process::id()))
+            .join("run.ckpt");
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+
+        let mut trainer = Trainer::new(net, 0.5, 5, 2).with_checkpoint(&path);
+        let history = trainer.train((&x, &t), (&x, &t));
+
+        assert_eq!(history.train_loss.len(), 1);
+        let err = history.checkpoint_error.as_ref().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(err.to_string().contains("run.ckpt"));
+        assert!(history.to_string().contains("failed to save checkpoint"));
+    }
+
+    #[test]
+    fn test_resume_rejects_mismatched_checkpoint() {
+        let (x, t) = toy_data();
+        let path = std::env::temp_dir().join(format!("rdl_resume_bad_{}.ckpt", std::process::id()));
+        let mut trainer = Trainer::new(
+            SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0),
+            0.5,
+            2,
+            2,
+        )
+        .with_optimizer(Adam::new(0.05));
+        trainer.fit(&x, &t);
+        trainer.save_checkpoint(&path).unwrap();
+
+        // 隐藏层大小不同
+        let net = SimpleNet::with_initializer(2, 4, 2, &XavierInit, 0);
+        let before = net.w1.clone();
+        let mut other = Trainer::new(net, 0.5, 2, 2).with_optimizer(Adam::new(0.05));
+        assert!(other.resume_from(&path).is_err());
+        assert_eq!(other.model.w1, before);
+        assert!(other.optimizer.state().is_empty());
+        assert_eq!(other.fit(&x, &t).train_loss.len(), 2);
+
+        // 保存时用的是 Adam，换成 SGD 无法恢复优化器状态
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+        assert!(Trainer::new(net, 0.5, 2, 2).resume_from(&path).is_err());
+
+        // 另一种优化器的状态：Adam -> Momentum，RmsProp -> AdaGrad（两者状态的形状相同）
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+        let mut momentum = Trainer::new(net, 0.5, 2, 2).with_optimizer(Momentum::new(0.1, 0.9));
+        let err = momentum.resume_from(&path).unwrap_err();
+        assert!(err.to_string().contains("saved with Adam"), "{}", err);
+
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+        let mut rmsprop = Trainer::new(net, 0.5, 2, 2).with_optimizer(RmsProp::new(0.01));
+        rmsprop.fit(&x, &t);
+        rmsprop.save_checkpoint(&path).unwrap();
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+        let before = net.w1.clone();
+        let mut adagrad = Trainer::new(net, 0.5, 2, 2).with_optimizer(AdaGrad::new(0.01));
+        assert!(adagrad.resume_from(&path).is_err());
+        assert_eq!(adagrad.model.w1, before);
+        assert!(adagrad.optimizer.state().is_empty());
+
+        std::fs::write(&path, b"RDLM").unwrap();
+        assert!(trainer.resume_from(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_fit_generator() {
+        let (x, t) = toy_data();
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+        let batches = [(0..2), (2..4)].map(|r| {
+            (
+                x.slice(s![r.clone(), ..]).to_owned(),
+                t.slice(s![r, ..]).to_owned(),
+            )
+        });
+
+        let mut calls = 0;
+        let mut trainer = Trainer::new(net, 0.5, 0, 0);
+        let history = trainer.fit_generator(
+            || {
+                calls += 1;
+                Some(batches[calls % 2].clone())
+            },
+            2,
+            10,
+        );
+
+        assert_eq!(calls, 2 * 10);
+        assert_eq!(history.train_loss.len(), 10);
+        assert!(history.train_loss[9] < history.train_loss[0]);
+    }
+
+    #[test]
+    fn test_fit_generator_stops_when_exhausted() {
+        let (x, t) = toy_data();
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+        let mut remaining = 3;
+
+        let mut trainer = Trainer::new(net, 0.5, 0, 0);
+        let history = trainer.fit_generator(
+            || {
+                (remaining > 0).then(|| {
+                    remaining -= 1;
+                    (x.clone(), t.clone())
+                })
+            },
+            2,
+            10,
+        );
+
+        // 第 1 轮完整，第 2 轮只取到 1 个批次后结束
+        assert_eq!(history.train_loss.len(), 2);
+    }
+
+    #[test]
+    fn test_lr_range_test() {
+        let (x, t) = toy_data();
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+        let before = net.w1.clone();
+
+        let (lrs, losses) = lr_range_test(&net, (&x, &t), 1e-3, 1e4, 40);
+
+        assert_eq!(lrs.len(), 40);
+        assert_eq!(losses.len(), 40);
+        assert!((lrs[0] - 1e-3).abs() < 1e-12);
+        assert!((lrs[39] - 1e4).abs() < 1e-6);
+        assert!(lrs.windows(2).all(|w| w[1] > w[0]));
+        assert_eq!(net.w1, before);
+
+        // 学习率过大时损失发散，远高于曲线上的最低点
+        let min_loss = losses.iter().cloned().fold(f64::INFINITY, f64::min);
+        assert!(losses[39] > 2.0 * min_loss);
+    }
+
+    #[test]
+    fn test_run_config_sub_seeds_are_distinct() {
+        let config = RunConfig::new(42);
+        assert_ne!(config.weight_seed(), config.shuffle_seed());
+        assert_ne!(config.shuffle_seed(), config.dropout_seed());
+        assert_ne!(config.weight_seed(), RunConfig::new(43).weight_seed());
+        assert_eq!(config.dropout_seed(), RunConfig::new(42).dropout_seed());
+    }
+
+    #[test]
+    fn test_same_run_config_is_bit_identical() {
+        let (x, t) = toy_data();
+        let run = |seed: u64| {
+            let config = RunConfig::new(seed);
+            let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, config.weight_seed());
+            let mut trainer = Trainer::new(net, 0.5, 3, 2).with_run_config(config);
+            trainer.fit(&x, &t);
+            trainer.model
+        };
+
+        let a = run(7);
+        let b = run(7);
+        assert_eq!(a.w1, b.w1);
+        assert_eq!(a.b1, b.b1);
+        assert_eq!(a.w2, b.w2);
+        assert_eq!(a.b2, b.b2);
+        assert_ne!(a.w1, run(8).w1);
+    }
+
+    /// 记录每个训练步小批量中样本编号（x 的第 0 列）的模型
+    struct RecordingNet {
+        net: SimpleNet,
+        seen: Rc<RefCell<Vec<usize>>>,
+    }
+
+    impl Model for RecordingNet {
+        fn predict(&self, x: &Array2<f64>) -> Array2<f64> {
+            self.net.predict(x)
+        }
+    }
+
+    impl Trainable for RecordingNet {
+        fn loss(&self, x: &Array2<f64>, t: &Array2<f64>) -> f64 {
+            self.net.loss(x, t)
+        }
+
+        fn gradient(&mut self, x: &Array2<f64>, t: &Array2<f64>) -> Vec<Array2<f64>> {
+            self.seen
+                .borrow_mut()
+                .extend(x.column(0).iter().map(|&v| v as usize));
+            Trainable::gradient(&mut self.net, x, t)
+        }
+
+        fn params(&self) -> Vec<&Array2<f64>> {
+            self.net.params()
+        }
+
+        fn params_mut(&mut self) -> Vec<&mut Array2<f64>> {
+            self.net.params_mut()
+        }
+    }
+
+    /// 用 8 个样本、batch_size 1 训练 3 轮，返回每个 epoch 看到的样本顺序
+    fn epoch_orders(mode: ShuffleMode) -> Vec<Vec<usize>> {
+        let x = Array2::from_shape_fn((8, 2), |(i, j)| if j == 0 { i as f64 } else { 0.5 });
+        let t = Array2::from_shape_fn((8, 2), |(i, j)| ((i + j) % 2) as f64);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let model = RecordingNet {
+            net: SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0),
+            seen: Rc::clone(&seen),
+        };
+
+        let mut trainer = Trainer::new(model, 0.1, 3, 1)
+            .with_run_config(RunConfig::new(5))
+            .with_shuffle(mode);
+        trainer.fit(&x, &t);
+
+        let seen = seen.borrow();
+        seen.chunks(8).map(|c| c.to_vec()).collect()
+    }
+
+    #[test]
+    fn test_shuffle_modes() {
+        let identity: Vec<usize> = (0..8).collect();
+
+        let never = epoch_orders(ShuffleMode::Never);
+        assert!(never.iter().all(|order| *order == identity));
+
+        let once = epoch_orders(ShuffleMode::Once);
+        assert_ne!(once[0], identity);
+        assert!(once.iter().all(|order| *order == once[0]));
+
+        let every = epoch_orders(ShuffleMode::EveryEpoch);
+        assert_eq!(every.len(), 3);
+        assert_ne!(every[0], every[1]);
+        assert_ne!(every[1], every[2]);
+        // 第一轮的顺序与 Once 相同：都是同一个种子的第一次打乱
+        assert_eq!(every[0], once[0]);
+    }
+
+    fn checkpoint_files(dir: &std::path::Path) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_periodic_save() {
+        let (x, t) = toy_data();
+        let dir = std::env::temp_dir().join(format!("rdl_ckpt_all_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // 4 个样本、batch 1 -> 每个 epoch 4 步，共 12 步，每 3 步保存一次
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+        let mut trainer = Trainer::new(net, 0.5, 3, 1).with_periodic_save(&dir, 3, None);
+        trainer.fit(&x, &t);
+
+        assert_eq!(
+            checkpoint_files(&dir),
+            vec![
+                "ckpt_12.model",
+                "ckpt_3.model",
+                "ckpt_6.model",
+                "ckpt_9.model"
+            ]
+        );
+        let last = crate::chapter02::serialize::load_params(dir.join("ckpt_12.model")).unwrap();
+        assert_eq!(last[0], trainer.model.w1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_periodic_save_keeps_last_k() {
+        let (x, t) = toy_data();
+        let dir = std::env::temp_dir().join(format!("rdl_ckpt_rot_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+        let mut trainer = Trainer::new(net, 0.5, 3, 1).with_periodic_save(&dir, 3, Some(2));
+        trainer.fit(&x, &t);
+
+        assert_eq!(
+            checkpoint_files(&dir),
+            vec!["ckpt_12.model", "ckpt_9.model"]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_periodic_save_creates_dir_and_reports_errors() {
+        let (x, t) = toy_data();
+        let root = std::env::temp_dir().join(format!("rdl_ckpt_new_{}", std::process::id()));
+        let dir = root.join("nested");
+
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+        let mut trainer = Trainer::new(net, 0.5, 1, 1).with_periodic_save(&dir, 2, None);
+        let history = trainer.fit(&x, &t);
+        assert!(history.checkpoint_error.is_none());
+        assert_eq!(checkpoint_files(&dir), vec!["ckpt_2.model", "ckpt_4.model"]);
+
+        // 目录所在位置是一个普通文件，无法创建：第一次保存时停止训练并记录错误
+        let file = root.join("not_a_dir");
+        std::fs::write(&file, b"").unwrap();
+        let net = SimpleNet::with_initializer(2, 3, 2, &XavierInit, 0);
+        let mut trainer =
+            Trainer::new(net, 0.5, 3, 1).with_periodic_save(file.join("ckpt"), 2, None);
+        let history = trainer.fit(&x, &t);
+        assert!(history.checkpoint_error.is_some());
+        assert!(history.train_loss.is_empty());
+        assert_eq!(trainer.step_count, 2);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
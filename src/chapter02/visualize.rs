@@ -0,0 +1,314 @@
+// src/chapter02/visualize.rs
+use super::grad::numerical_gradient;
+use super::metrics::argmax_rows;
+use super::model::{Model, Trainable};
+use super::network::SimpleNet;
+use super::optimizer::{AdaGrad, Adam, Momentum, Optimizer, Sgd};
+use ndarray::{Array2, Axis, array};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// 二维优化轨迹：依次经过的 (x, y) 点
+pub type Path2d = Vec<(f64, f64)>;
+
+/// 激活最大化：从随机输入出发，沿隐藏层第 `unit` 个神经元的加权输入 a1[unit]
+/// 对输入的梯度做梯度上升，得到最能激活该神经元的输入（784 维时可以还原为 28x28 图像）
+pub fn maximize_activation(net: &SimpleNet, unit: usize, steps: usize, lr: f64) -> Array2<f64> {
+    let mut rng = rand::rng();
+    let x0 = Array2::from_shape_fn((1, net.w1.nrows()), |_| rng.random::<f64>());
+    maximize_activation_from(net, unit, &x0, steps, lr)
+}
+
+/// 同 `maximize_activation`，但从给定的输入 `x0` 开始。
+/// 每一步后把输入裁剪到 [0, 1]，保持像素值的取值范围
+pub fn maximize_activation_from(
+    net: &SimpleNet,
+    unit: usize,
+    x0: &Array2<f64>,
+    steps: usize,
+    lr: f64,
+) -> Array2<f64> {
+    assert!(unit < net.w1.ncols(), "unit {} out of range", unit);
+
+    // a1[unit] = x · w1[:, unit] + b1[unit]，对 x 的梯度就是 w1 的第 unit 列
+    let grad = net.w1.column(unit).insert_axis(Axis(0)).to_owned();
+
+    let mut x = x0.clone();
+    for _ in 0..steps {
+        x.zip_mut_with(&grad, |v, &g| *v = (*v + lr * g).clamp(0.0, 1.0));
+    }
+    x
+}
+
+/// 隐藏层第 `unit` 个神经元的加权输入 a1[unit]
+pub fn hidden_pre_activation(net: &SimpleNet, x: &Array2<f64>, unit: usize) -> f64 {
+    (x.dot(&net.w1) + &net.b1)[[0, unit]]
+}
+
+/// 一维损失地形：沿两组参数之间的直线 θ = (1 - a)·start + a·end 取 `steps` 个点
+/// （a 从 0 到 1 均匀分布），返回每个点的 (a, loss)。
+///
+/// `theta_start` / `theta_end` 的顺序与 `Trainable::params` 一致，典型用法是比较初始权重和训练后的权重
+pub fn loss_landscape_1d<M: Trainable + Clone>(
+    model: &M,
+    theta_start: &[Array2<f64>],
+    theta_end: &[Array2<f64>],
+    x: &Array2<f64>,
+    t: &Array2<f64>,
+    steps: usize,
+) -> Vec<(f64, f64)> {
+    assert_eq!(theta_start.len(), theta_end.len());
+    let mut probe = model.clone();
+    assert_eq!(probe.params().len(), theta_start.len());
+
+    (0..steps)
+        .map(|i| {
+            let a = if steps > 1 {
+                i as f64 / (steps - 1) as f64
+            } else {
+                0.0
+            };
+            for ((param, start), end) in probe
+                .params_mut()
+                .into_iter()
+                .zip(theta_start)
+                .zip(theta_end)
+            {
+                *param = start * (1.0 - a) + end * a;
+            }
+            (a, probe.loss(x, t))
+        })
+        .collect()
+}
+
+/// 在 [x_min, x_max] × [y_min, y_max] 上取 resolution × resolution 个等距网格点，
+/// 返回模型在每个点上预测的类别，用于画二维分类问题的决策区域。
+///
+/// 返回值的 `[[i, j]]` 对应坐标 (x_j, y_i)，即行沿 y 轴、列沿 x 轴
+pub fn decision_grid<M: Model + ?Sized>(
+    model: &M,
+    x_range: (f64, f64),
+    y_range: (f64, f64),
+    resolution: usize,
+) -> Array2<usize> {
+    assert!(
+        resolution >= 2,
+        "decision_grid: resolution must be at least 2"
+    );
+    let coord =
+        |(min, max): (f64, f64), k: usize| min + (max - min) * k as f64 / (resolution - 1) as f64;
+
+    let points = Array2::from_shape_fn((resolution * resolution, 2), |(p, d)| {
+        let (i, j) = (p / resolution, p % resolution);
+        if d == 0 {
+            coord(x_range, j)
+        } else {
+            coord(y_range, i)
+        }
+    });
+    argmax_rows(&model.predict(&points))
+        .into_shape_with_order((resolution, resolution))
+        .expect("one prediction per grid point")
+}
+
+/// 从 `start` 出发，用 `optimizer` 沿 `objective` 的数值梯度走 `steps` 步，
+/// 返回包含起点在内的 `steps + 1` 个点
+pub fn optimizer_path<F>(
+    objective: F,
+    start: (f64, f64),
+    optimizer: &mut dyn Optimizer,
+    steps: usize,
+) -> Path2d
+where
+    F: Fn(f64, f64) -> f64,
+{
+    let f = |p: &Array2<f64>| objective(p[[0, 0]], p[[0, 1]]);
+    let mut p = array![[start.0, start.1]];
+    let mut path = Vec::with_capacity(steps + 1);
+    path.push(start);
+    for _ in 0..steps {
+        let grad = numerical_gradient(f, &p);
+        optimizer.update(&mut [&mut p], &[grad]);
+        path.push((p[[0, 0]], p[[0, 1]]));
+    }
+    path
+}
+
+/// 书中第 6 章比较优化器的实验：在同一个函数上从同一起点出发，
+/// 按书中的学习率分别运行 SGD (0.95)、Momentum (0.1)、AdaGrad (1.5)、Adam (0.3)，
+/// 返回 (名字, 轨迹)。书中用的是 f(x, y) = x²/20 + y²、起点 (-7, 2)、30 步
+pub fn optimizer_paths<F>(
+    objective: F,
+    start: (f64, f64),
+    steps: usize,
+) -> Vec<(&'static str, Path2d)>
+where
+    F: Fn(f64, f64) -> f64,
+{
+    let optimizers: [(&str, Box<dyn Optimizer>); 4] = [
+        ("SGD", Box::new(Sgd::new(0.95))),
+        ("Momentum", Box::new(Momentum::new(0.1, 0.9))),
+        ("AdaGrad", Box::new(AdaGrad::new(1.5))),
+        ("Adam", Box::new(Adam::new(0.3))),
+    ];
+    optimizers
+        .into_iter()
+        .map(|(name, mut opt)| (name, optimizer_path(&objective, start, opt.as_mut(), steps)))
+        .collect()
+}
+
+/// 随机重启的梯度下降：在 bounds = ((x_min, x_max), (y_min, y_max)) 内随机取 `restarts` 个起点，
+/// 每个起点都用 `make_optimizer` 新建的优化器（状态从零开始）沿数值梯度走 `steps` 步，
+/// 返回终点函数值最小的 (最优点, 最优值, 每次重启的轨迹)。
+///
+/// 非凸函数上单次梯度下降只能到达起点所在的局部极小值，多次重启能提高找到全局极小值的概率
+pub fn optimize_2d_restarts<F, O>(
+    objective: F,
+    bounds: ((f64, f64), (f64, f64)),
+    restarts: usize,
+    make_optimizer: O,
+    steps: usize,
+    seed: u64,
+) -> ((f64, f64), f64, Vec<Path2d>)
+where
+    F: Fn(f64, f64) -> f64,
+    O: Fn() -> Box<dyn Optimizer>,
+{
+    assert!(
+        restarts > 0,
+        "optimize_2d_restarts: need at least one restart"
+    );
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let paths: Vec<Path2d> = (0..restarts)
+        .map(|_| {
+            let ((x_min, x_max), (y_min, y_max)) = bounds;
+            let start = (
+                rng.random_range(x_min..=x_max),
+                rng.random_range(y_min..=y_max),
+            );
+            optimizer_path(&objective, start, make_optimizer().as_mut(), steps)
+        })
+        .collect();
+
+    let (best_point, best_value) = paths
+        .iter()
+        .map(|path| {
+            let &(x, y) = path.last().unwrap();
+            ((x, y), objective(x, y))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap();
+    (best_point, best_value, paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maximize_activation_increases_unit() {
+        let net = SimpleNet::new(784, 20, 10);
+        let x0 = Array2::from_elem((1, 784), 0.5);
+
+        let x = maximize_activation_from(&net, 3, &x0, 20, 0.1);
+
+        assert_eq!(x.shape(), &[1, 784]);
+        assert!(x.iter().all(|&v| (0.0..=1.0).contains(&v)));
+        assert!(hidden_pre_activation(&net, &x, 3) > hidden_pre_activation(&net, &x0, 3));
+    }
+
+    #[test]
+    fn test_maximize_activation_random_start() {
+        let net = SimpleNet::new(4, 3, 2);
+        let x = maximize_activation(&net, 0, 5, 0.1);
+        assert_eq!(x.shape(), &[1, 4]);
+    }
+
+    #[test]
+    fn test_loss_landscape_endpoints_match() {
+        let start_net = SimpleNet::new(2, 3, 2);
+        let end_net = SimpleNet::new(2, 3, 2);
+        let x = Array2::from_shape_vec((2, 2), vec![0.6, 0.9, -0.3, 0.2]).unwrap();
+        let t = Array2::from_shape_vec((2, 2), vec![0.0, 1.0, 1.0, 0.0]).unwrap();
+
+        let start: Vec<Array2<f64>> = start_net.params().into_iter().cloned().collect();
+        let end: Vec<Array2<f64>> = end_net.params().into_iter().cloned().collect();
+        let curve = loss_landscape_1d(&start_net, &start, &end, &x, &t, 5);
+
+        assert_eq!(curve.len(), 5);
+        assert_eq!(curve[0].0, 0.0);
+        assert_eq!(curve[4].0, 1.0);
+        assert!((curve[0].1 - start_net.loss(&x, &t)).abs() < 1e-12);
+        assert!((curve[4].1 - end_net.loss(&x, &t)).abs() < 1e-12);
+    }
+
+    /// 只看第一个坐标的符号：x > 0 为类别 1，否则为类别 0
+    struct SignOfX;
+
+    impl Model for SignOfX {
+        fn predict(&self, x: &Array2<f64>) -> Array2<f64> {
+            Array2::from_shape_fn((x.nrows(), 2), |(i, k)| {
+                let positive = x[[i, 0]] > 0.0;
+                if positive == (k == 1) { 1.0 } else { 0.0 }
+            })
+        }
+    }
+
+    #[test]
+    fn test_decision_grid_layout() {
+        let grid = decision_grid(&SignOfX, (-1.0, 1.0), (-5.0, 5.0), 3);
+        assert_eq!(grid.dim(), (3, 3));
+        // 列沿 x 轴：x = -1, 0, 1
+        for row in grid.rows() {
+            assert_eq!(row.to_vec(), vec![0, 0, 1]);
+        }
+    }
+
+    #[test]
+    fn test_optimize_2d_restarts_finds_global_minimum() {
+        // 沿 x 方向有两个井：x ≈ -1 处为全局极小，x ≈ 1 处为局部极小
+        let double_well = |x: f64, y: f64| (x * x - 1.0).powi(2) + 0.3 * x + y * y;
+        let bounds = ((-2.0, 2.0), (-1.0, 1.0));
+        let momentum = || -> Box<dyn Optimizer> { Box::new(Momentum::new(0.01, 0.5)) };
+        let finds_global = |restarts, seed| {
+            let (best, _, paths) =
+                optimize_2d_restarts(double_well, bounds, restarts, momentum, 300, seed);
+            assert_eq!(paths.len(), restarts);
+            assert!(paths.iter().all(|p| p.len() == 301));
+            best.0 < 0.0
+        };
+
+        let single = (0..20).filter(|&seed| finds_global(1, seed)).count();
+        let restarted = (0..20).filter(|&seed| finds_global(16, seed)).count();
+        assert_eq!(restarted, 20);
+        assert!(single < restarted);
+
+        // 任意优化器都可以重启
+        let adam = || -> Box<dyn Optimizer> { Box::new(Adam::new(0.05)) };
+        let (best, _, _) = optimize_2d_restarts(double_well, bounds, 16, adam, 300, 0);
+        assert!(best.0 < 0.0);
+    }
+
+    #[test]
+    fn test_optimizer_paths_book_comparison() {
+        let f = |x: f64, y: f64| x * x / 20.0 + y * y;
+        let paths = optimizer_paths(f, (-7.0, 2.0), 30);
+
+        let names: Vec<&str> = paths.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["SGD", "Momentum", "AdaGrad", "Adam"]);
+        for (name, path) in &paths {
+            assert_eq!(path.len(), 31);
+            assert_eq!(path[0], (-7.0, 2.0));
+            let &(x, y) = path.last().unwrap();
+            assert!(f(x, y) < f(-7.0, 2.0), "{} did not make progress", name);
+        }
+
+        // SGD 在陡峭的 y 方向上来回振荡（书中的之字形轨迹）
+        let sgd = &paths[0].1;
+        assert!(sgd.windows(2).all(|w| w[0].1 * w[1].1 < 0.0));
+        // AdaGrad 在 y 方向的步长很快变小，不再振荡
+        let adagrad = &paths[2].1;
+        assert!(adagrad[5..].iter().all(|p| p.1.abs() < 0.1));
+    }
+}
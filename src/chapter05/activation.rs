@@ -0,0 +1,130 @@
+// src/chapter05/activation.rs
+use crate::chapter02::activation::sigmoid;
+use ndarray::Array2;
+
+/// 逐元素的激活函数对象：前向计算输出，反向把上游梯度乘以导数。
+/// 放进网络时用 `layers::ActivationLayer` 包装（`ReluLayer`、`SigmoidLayer` 即是如此）
+pub trait Activation {
+    fn forward(&self, x: &Array2<f64>) -> Array2<f64>;
+
+    /// `x` 为前向时的输入，返回 dout * f'(x)
+    fn backward(&self, x: &Array2<f64>, dout: &Array2<f64>) -> Array2<f64>;
+}
+
+/// 阶跃函数：x > 0 时输出 1，否则输出 0（第 1 章感知机使用的激活函数）。
+///
+/// 注意：阶跃函数在 0 以外处处导数为 0（在 0 处不可导），所以 `backward` 总是返回 0，
+/// 梯度无法穿过它传到前面的层。这正是感知机无法用误差反向传播训练、
+/// 神经网络改用 sigmoid / ReLU 等连续激活函数的原因
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Step;
+
+impl Activation for Step {
+    fn forward(&self, x: &Array2<f64>) -> Array2<f64> {
+        x.mapv(|v| if v > 0.0 { 1.0 } else { 0.0 })
+    }
+
+    fn backward(&self, _x: &Array2<f64>, dout: &Array2<f64>) -> Array2<f64> {
+        Array2::zeros(dout.raw_dim())
+    }
+}
+
+/// ReLU：max(0, x)，导数在 x > 0 时为 1，否则为 0
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Relu;
+
+impl Activation for Relu {
+    fn forward(&self, x: &Array2<f64>) -> Array2<f64> {
+        x.mapv(|v| v.max(0.0))
+    }
+
+    fn backward(&self, x: &Array2<f64>, dout: &Array2<f64>) -> Array2<f64> {
+        let mut dx = dout.clone();
+        dx.zip_mut_with(x, |d, &v| {
+            if v <= 0.0 {
+                *d = 0.0;
+            }
+        });
+        dx
+    }
+}
+
+/// sigmoid：1 / (1 + exp(-x))，导数为 y(1 - y)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sigmoid;
+
+impl Activation for Sigmoid {
+    fn forward(&self, x: &Array2<f64>) -> Array2<f64> {
+        sigmoid(x)
+    }
+
+    fn backward(&self, x: &Array2<f64>, dout: &Array2<f64>) -> Array2<f64> {
+        let y = sigmoid(x);
+        dout * &y.mapv(|v| v * (1.0 - v))
+    }
+}
+
+/// tanh，导数为 1 - y²
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tanh;
+
+impl Activation for Tanh {
+    fn forward(&self, x: &Array2<f64>) -> Array2<f64> {
+        x.mapv(f64::tanh)
+    }
+
+    fn backward(&self, x: &Array2<f64>, dout: &Array2<f64>) -> Array2<f64> {
+        dout * &x.mapv(|v| 1.0 - v.tanh().powi(2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chapter01::perceptron::{and_gate, nand_gate, or_gate};
+    use ndarray::array;
+
+    type Gate = fn(f64, f64) -> f64;
+
+    #[test]
+    fn test_step_matches_perceptron_gates() {
+        let inputs = array![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]];
+        // (w1, w2, b) 与第 1 章的门电路参数一致
+        let gates: [(Gate, f64, f64, f64); 3] = [
+            (and_gate, 0.5, 0.5, -0.7),
+            (nand_gate, -0.5, -0.5, 0.7),
+            (or_gate, 0.5, 0.5, -0.2),
+        ];
+
+        for (gate, w1, w2, b) in gates {
+            let weighted = inputs.dot(&array![[w1], [w2]]) + b;
+            let out = Step.forward(&weighted);
+            for (row, y) in inputs.rows().into_iter().zip(out.iter()) {
+                assert_eq!(*y, gate(row[0], row[1]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_step_backward_is_zero() {
+        let x = array![[-1.0, 0.5]];
+        let dx = Step.backward(&x, &array![[3.0, 3.0]]);
+        assert_eq!(dx, array![[0.0, 0.0]]);
+    }
+
+    #[test]
+    fn test_backward_matches_numerical_derivative() {
+        let x = array![[-1.5, -0.2, 0.3, 2.0]];
+        let dout = array![[1.0, -2.0, 0.5, 3.0]];
+        let acts: [&dyn Activation; 3] = [&Relu, &Sigmoid, &Tanh];
+        let h = 1e-6;
+
+        for act in acts {
+            let dx = act.backward(&x, &dout);
+            let numeric = (act.forward(&(&x + h)) - act.forward(&(&x - h))) / (2.0 * h) * &dout;
+            for (a, n) in dx.iter().zip(numeric.iter()) {
+                assert!((a - n).abs() < 1e-6);
+            }
+        }
+    }
+}
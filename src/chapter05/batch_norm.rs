@@ -0,0 +1,173 @@
+// src/chapter05/batch_norm.rs
+use super::layers::Layer;
+use super::parameter::Parameter;
+use super::tensor::Tensor;
+use ndarray::{Array2, Axis};
+
+/// Batch Normalization：对每个特征（列）按小批量的均值和方差做标准化，再做缩放和平移
+/// y = gamma * (x - μ) / sqrt(σ² + eps) + beta。
+///
+/// 训练时（`training = true`）使用当前批次的统计量，同时用指数移动平均更新
+/// `running_mean` / `running_var`；推理时（`training = false`）改用这两个移动平均值，
+/// 输出不再依赖同一批次里的其他样本。
+///
+/// 四维输入 (N, C, H, W) 会按样本展平，把 C * H * W 个值各当作一个特征（与书中的实现相同），
+/// 此时 `dim` 应为 C * H * W
+pub struct BatchNorm {
+    pub gamma: Parameter,
+    pub beta: Parameter,
+    /// 移动平均的系数：running = momentum * running + (1 - momentum) * batch
+    pub momentum: f64,
+    pub eps: f64,
+    pub running_mean: Array2<f64>,
+    pub running_var: Array2<f64>,
+    pub training: bool,
+    // 反向传播需要的中间结果：x - μ、sqrt(σ² + eps)、标准化后的 x，以及前向时的模式
+    xc: Array2<f64>,
+    std: Array2<f64>,
+    xn: Array2<f64>,
+    used_batch_stats: bool,
+}
+
+impl BatchNorm {
+    /// `dim` 为特征数；gamma 初始化为 1，beta 初始化为 0，处于训练模式
+    pub fn new(dim: usize) -> Self {
+        Self {
+            gamma: Parameter::new(Array2::ones((1, dim))),
+            beta: Parameter::new(Array2::zeros((1, dim))),
+            momentum: 0.9,
+            eps: 1e-7,
+            running_mean: Array2::zeros((1, dim)),
+            running_var: Array2::ones((1, dim)),
+            training: true,
+            xc: Array2::zeros((0, dim)),
+            std: Array2::ones((1, dim)),
+            xn: Array2::zeros((0, dim)),
+            used_batch_stats: true,
+        }
+    }
+}
+
+impl Layer for BatchNorm {
+    fn forward(&mut self, x: &Array2<f64>) -> Array2<f64> {
+        assert_eq!(
+            x.ncols(),
+            self.gamma.value.ncols(),
+            "BatchNorm: expected {} features, got {}",
+            self.gamma.value.ncols(),
+            x.ncols()
+        );
+
+        let (mean, var) = if self.training {
+            let mean = x
+                .mean_axis(Axis(0))
+                .expect("BatchNorm: empty batch")
+                .insert_axis(Axis(0));
+            let var = x.var_axis(Axis(0), 0.0).insert_axis(Axis(0));
+            self.running_mean = &self.running_mean * self.momentum + &mean * (1.0 - self.momentum);
+            self.running_var = &self.running_var * self.momentum + &var * (1.0 - self.momentum);
+            (mean, var)
+        } else {
+            (self.running_mean.clone(), self.running_var.clone())
+        };
+
+        self.used_batch_stats = self.training;
+        self.xc = x - &mean;
+        self.std = var.mapv(|v| (v + self.eps).sqrt());
+        self.xn = &self.xc / &self.std;
+        &self.xn * &self.gamma.value + &self.beta.value
+    }
+
+    fn backward(&mut self, dout: &Array2<f64>) -> Array2<f64> {
+        self.beta.grad = dout.sum_axis(Axis(0)).insert_axis(Axis(0));
+        self.gamma.grad = (&self.xn * dout).sum_axis(Axis(0)).insert_axis(Axis(0));
+
+        let dxn = dout * &self.gamma.value;
+        let dxc = &dxn / &self.std;
+        if !self.used_batch_stats {
+            // 推理模式下均值和方差是常数，只剩逐元素的缩放
+            return dxc;
+        }
+
+        // 均值和方差也依赖 x，需要把经由它们的梯度加回来
+        let n = dout.nrows() as f64;
+        let dstd = -(&dxn * &self.xc / (&self.std * &self.std))
+            .sum_axis(Axis(0))
+            .insert_axis(Axis(0));
+        let dvar = dstd * 0.5 / &self.std;
+        let dxc = dxc + &self.xc * &dvar * (2.0 / n);
+        let dmu = dxc.sum_axis(Axis(0)).insert_axis(Axis(0));
+        dxc - dmu / n
+    }
+
+    fn forward_tensor(&mut self, x: &Tensor) -> Tensor {
+        x.map_as_mat(|m| self.forward(m))
+    }
+
+    fn backward_tensor(&mut self, dout: &Tensor) -> Tensor {
+        dout.map_as_mat(|d| self.backward(d))
+    }
+
+    fn grads(&self) -> Vec<&Array2<f64>> {
+        vec![&self.gamma.grad, &self.beta.grad]
+    }
+
+    fn params(&self) -> Vec<&Parameter> {
+        vec![&self.gamma, &self.beta]
+    }
+
+    fn params_mut(&mut self) -> Vec<&mut Parameter> {
+        vec![&mut self.gamma, &mut self.beta]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    fn sample_batch() -> Array2<f64> {
+        array![[1.0, 10.0], [2.0, 20.0], [3.0, 60.0], [6.0, 30.0]]
+    }
+
+    #[test]
+    fn test_training_forward_normalizes_columns() {
+        let mut bn = BatchNorm::new(2);
+        let y = bn.forward(&sample_batch());
+
+        let mean = y.mean_axis(Axis(0)).unwrap();
+        let var = y.var_axis(Axis(0), 0.0);
+        for j in 0..2 {
+            assert!(mean[j].abs() < 1e-9);
+            assert!((var[j] - 1.0).abs() < 1e-6);
+        }
+
+        // 移动平均向批次统计量靠近了 (1 - momentum)
+        assert!((bn.running_mean[[0, 0]] - 0.1 * 3.0).abs() < 1e-12);
+        assert!((bn.running_var[[0, 0]] - (0.9 + 0.1 * 3.5)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_inference_uses_running_stats() {
+        let mut bn = BatchNorm::new(2);
+        bn.running_mean = array![[1.0, -2.0]];
+        bn.running_var = array![[4.0, 0.25]];
+        bn.gamma.value = array![[2.0, 1.0]];
+        bn.beta.value = array![[0.5, 0.0]];
+        bn.training = false;
+
+        let y = bn.forward(&array![[3.0, -1.0]]);
+        let expected = array![[
+            2.0 * 2.0 / (4.0f64 + 1e-7).sqrt() + 0.5,
+            1.0 / (0.25f64 + 1e-7).sqrt()
+        ]];
+        for (a, e) in y.iter().zip(expected.iter()) {
+            assert!((a - e).abs() < 1e-12);
+        }
+
+        // 推理不更新移动平均，单个样本的输出也不受同批次其他样本影响
+        assert_eq!(bn.running_mean, array![[1.0, -2.0]]);
+        let batch = bn.forward(&array![[3.0, -1.0], [100.0, 100.0]]);
+        assert_eq!(batch.row(0), y.row(0));
+    }
+}
@@ -0,0 +1,375 @@
+// src/chapter05/convolution.rs
+use super::layers::Layer;
+use super::parameter::Parameter;
+use super::tensor::{Tensor, flatten, image_input, to_images};
+use ndarray::{Array2, Array4, Axis, s};
+
+/// 把 (N, C, H, W) 输入中每个滤波器窗口展开成一行：输出形状为
+/// (N * OH * OW, C * FH * FW)，列按 (通道, 行, 列) 的顺序排列。
+/// 这样卷积就变成一次矩阵乘法
+pub fn im2col(x: &Array4<f64>, fh: usize, fw: usize, stride: usize, pad: usize) -> Array2<f64> {
+    let (n, c, h, w) = x.dim();
+    let (oh, ow) = conv_output_size(h, w, fh, fw, stride, pad);
+    let padded = pad_input(x, pad);
+
+    let mut col = Array2::zeros((n * oh * ow, c * fh * fw));
+    for b in 0..n {
+        for i in 0..oh {
+            for j in 0..ow {
+                let mut row = col.row_mut((b * oh + i) * ow + j);
+                let (y0, x0) = (i * stride, j * stride);
+                let window = padded.slice(s![b, .., y0..y0 + fh, x0..x0 + fw]);
+                for (dst, &v) in row.iter_mut().zip(window.iter()) {
+                    *dst = v;
+                }
+            }
+        }
+    }
+    col
+}
+
+/// `im2col` 的逆操作：把每一行加回它来自的窗口位置（窗口重叠处累加），再去掉填充
+pub fn col2im(
+    col: &Array2<f64>,
+    x_shape: (usize, usize, usize, usize),
+    fh: usize,
+    fw: usize,
+    stride: usize,
+    pad: usize,
+) -> Array4<f64> {
+    let (n, c, h, w) = x_shape;
+    let (oh, ow) = conv_output_size(h, w, fh, fw, stride, pad);
+    assert_eq!(
+        col.dim(),
+        (n * oh * ow, c * fh * fw),
+        "col2im: col shape does not match x_shape"
+    );
+
+    let mut padded = Array4::zeros((n, c, h + 2 * pad, w + 2 * pad));
+    for b in 0..n {
+        for i in 0..oh {
+            for j in 0..ow {
+                let row = col.row((b * oh + i) * ow + j);
+                let (y0, x0) = (i * stride, j * stride);
+                let mut window = padded.slice_mut(s![b, .., y0..y0 + fh, x0..x0 + fw]);
+                for (dst, &v) in window.iter_mut().zip(row.iter()) {
+                    *dst += v;
+                }
+            }
+        }
+    }
+    padded
+        .slice(s![.., .., pad..pad + h, pad..pad + w])
+        .to_owned()
+}
+
+/// 卷积输出的大小 ((H + 2P - FH) / S + 1, (W + 2P - FW) / S + 1)
+pub fn conv_output_size(
+    h: usize,
+    w: usize,
+    fh: usize,
+    fw: usize,
+    stride: usize,
+    pad: usize,
+) -> (usize, usize) {
+    assert!(stride > 0, "convolution: stride must be positive");
+    assert!(
+        h + 2 * pad >= fh && w + 2 * pad >= fw,
+        "convolution: padded input {}x{} is smaller than the {}x{} filter",
+        h + 2 * pad,
+        w + 2 * pad,
+        fh,
+        fw
+    );
+    (
+        (h + 2 * pad - fh) / stride + 1,
+        (w + 2 * pad - fw) / stride + 1,
+    )
+}
+
+fn pad_input(x: &Array4<f64>, pad: usize) -> Array4<f64> {
+    if pad == 0 {
+        return x.clone();
+    }
+    let (n, c, h, w) = x.dim();
+    let mut out = Array4::zeros((n, c, h + 2 * pad, w + 2 * pad));
+    out.slice_mut(s![.., .., pad..pad + h, pad..pad + w])
+        .assign(x);
+    out
+}
+
+/// 卷积层，输入 (N, C, H, W)，输出 (N, FN, OH, OW)。
+///
+/// 滤波器在内部以 im2col 的形式保存为 (C * FH * FW, FN) 的矩阵，
+/// 所以 `w` 可以像 `Affine` 的权重一样直接交给优化器和 `Initializer`（fan_in = C * FH * FW）。
+///
+/// 设置了 `input_shape` 时，作为 `Layer` 也接受每行一张展平图像的 (N, C * H * W) 矩阵，
+/// 此时 `Layer::forward` / `backward` 的输出和梯度同样是展平的
+pub struct Convolution {
+    pub w: Parameter,
+    /// 形状为 (1, FN)
+    pub b: Parameter,
+    /// (FN, C, FH, FW)
+    pub filter_shape: (usize, usize, usize, usize),
+    pub stride: usize,
+    pub pad: usize,
+    /// 二维输入每行还原成的图像形状 (C, H, W)
+    pub input_shape: Option<(usize, usize, usize)>,
+    x_shape: (usize, usize, usize, usize),
+    col: Array2<f64>,
+    // 最近一次 `forward_tensor` 的输入是否为二维，决定反向传播时梯度的形式
+    mat_input: bool,
+}
+
+impl Convolution {
+    /// `filters` 形状为 (FN, C, FH, FW)，`b` 形状为 (1, FN)
+    pub fn new(filters: Array4<f64>, b: Array2<f64>, stride: usize, pad: usize) -> Self {
+        let filter_shape = filters.dim();
+        let (fn_, c, fh, fw) = filter_shape;
+        assert_eq!(b.dim(), (1, fn_), "Convolution: bias must be (1, {})", fn_);
+        let w = filters
+            .into_shape_with_order((fn_, c * fh * fw))
+            .expect("filters are contiguous")
+            .reversed_axes()
+            .as_standard_layout()
+            .to_owned();
+        Self::from_col_weights(w, b, filter_shape, stride, pad)
+    }
+
+    /// 直接用 (C * FH * FW, FN) 形式的权重创建，例如由 `Initializer` 生成的矩阵
+    pub fn from_col_weights(
+        w: Array2<f64>,
+        b: Array2<f64>,
+        filter_shape: (usize, usize, usize, usize),
+        stride: usize,
+        pad: usize,
+    ) -> Self {
+        let (fn_, c, fh, fw) = filter_shape;
+        assert_eq!(
+            w.dim(),
+            (c * fh * fw, fn_),
+            "Convolution: weights must be ({}, {})",
+            c * fh * fw,
+            fn_
+        );
+        Self {
+            w: Parameter::new(w),
+            b: Parameter::new(b),
+            filter_shape,
+            stride,
+            pad,
+            input_shape: None,
+            x_shape: (0, c, 0, 0),
+            col: Array2::zeros((0, c * fh * fw)),
+            mat_input: false,
+        }
+    }
+
+    /// 接受展平成 (N, C * H * W) 的输入（例如直接放在 `Sequential` 的最前面）
+    pub fn with_input_shape(self, shape: (usize, usize, usize)) -> Self {
+        assert_eq!(
+            shape.0, self.filter_shape.1,
+            "Convolution: input_shape must have {} channels",
+            self.filter_shape.1
+        );
+        Self {
+            input_shape: Some(shape),
+            ..self
+        }
+    }
+
+    /// 以 (FN, C, FH, FW) 形状返回当前的滤波器
+    pub fn filters(&self) -> Array4<f64> {
+        self.w
+            .value
+            .t()
+            .as_standard_layout()
+            .into_owned()
+            .into_shape_with_order(self.filter_shape)
+            .expect("filter shape matches weight count")
+    }
+
+    pub fn forward(&mut self, x: &Array4<f64>) -> Array4<f64> {
+        let (_, _, fh, fw) = self.filter_shape;
+        self.x_shape = x.dim();
+        self.col = im2col(x, fh, fw, self.stride, self.pad);
+        self.output_from_col(&self.col, x.dim())
+    }
+
+    /// 只做前向计算、不缓存中间结果（推理用）
+    pub fn apply(&self, x: &Array4<f64>) -> Array4<f64> {
+        let (_, _, fh, fw) = self.filter_shape;
+        let col = im2col(x, fh, fw, self.stride, self.pad);
+        self.output_from_col(&col, x.dim())
+    }
+
+    fn output_from_col(
+        &self,
+        col: &Array2<f64>,
+        x_shape: (usize, usize, usize, usize),
+    ) -> Array4<f64> {
+        let (n, c, h, w) = x_shape;
+        let (fn_, fc, fh, fw) = self.filter_shape;
+        assert_eq!(
+            c, fc,
+            "Convolution: expected {} input channels, got {}",
+            fc, c
+        );
+        let (oh, ow) = conv_output_size(h, w, fh, fw, self.stride, self.pad);
+
+        let out = col.dot(&self.w.value) + &self.b.value;
+        out.into_shape_with_order((n, oh, ow, fn_))
+            .expect("one output row per position")
+            .permuted_axes([0, 3, 1, 2])
+            .as_standard_layout()
+            .into_owned()
+    }
+
+    /// 输入上游梯度 (N, FN, OH, OW)，计算 dW、db，返回对输入的梯度 (N, C, H, W)
+    pub fn backward(&mut self, dout: &Array4<f64>) -> Array4<f64> {
+        let (n, fn_, oh, ow) = dout.dim();
+        let (_, _, fh, fw) = self.filter_shape;
+        let dout = dout
+            .view()
+            .permuted_axes([0, 2, 3, 1])
+            .as_standard_layout()
+            .into_owned()
+            .into_shape_with_order((n * oh * ow, fn_))
+            .expect("dout matches the forward output shape");
+
+        self.b.grad = dout.sum_axis(Axis(0)).insert_axis(Axis(0));
+        self.w.grad = self.col.t().dot(&dout);
+        let dcol = dout.dot(&self.w.value.t());
+        col2im(&dcol, self.x_shape, fh, fw, self.stride, self.pad)
+    }
+}
+
+/// 四维输入通过 `forward_tensor` / `backward_tensor` 使用；二维的 `forward` / `backward`
+/// 需要先用 `with_input_shape` 指定每行图像的形状
+impl Layer for Convolution {
+    fn forward(&mut self, x: &Array2<f64>) -> Array2<f64> {
+        flatten(&self.forward_tensor(&Tensor::Mat(x.clone())).into_img())
+    }
+
+    fn backward(&mut self, dout: &Array2<f64>) -> Array2<f64> {
+        self.backward_tensor(&Tensor::Mat(dout.clone())).into_mat()
+    }
+
+    fn forward_tensor(&mut self, x: &Tensor) -> Tensor {
+        self.mat_input = matches!(x, Tensor::Mat(_));
+        let x = image_input(x, self.input_shape, "Convolution");
+        Tensor::Img(Convolution::forward(self, &x))
+    }
+
+    fn backward_tensor(&mut self, dout: &Tensor) -> Tensor {
+        let dout = match dout {
+            Tensor::Img(d) => d.clone(),
+            Tensor::Mat(d) => {
+                let (_, _, h, w) = self.x_shape;
+                let (fn_, _, fh, fw) = self.filter_shape;
+                let (oh, ow) = conv_output_size(h, w, fh, fw, self.stride, self.pad);
+                to_images(d, (fn_, oh, ow))
+            }
+        };
+        let dx = Convolution::backward(self, &dout);
+        if self.mat_input {
+            Tensor::Mat(flatten(&dx))
+        } else {
+            Tensor::Img(dx)
+        }
+    }
+
+    fn grads(&self) -> Vec<&Array2<f64>> {
+        vec![&self.w.grad, &self.b.grad]
+    }
+
+    fn params(&self) -> Vec<&Parameter> {
+        vec![&self.w, &self.b]
+    }
+
+    fn params_mut(&mut self) -> Vec<&mut Parameter> {
+        vec![&mut self.w, &mut self.b]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chapter02::grad::numerical_gradient;
+    use ndarray::{Array, array};
+
+    fn sample_input() -> Array4<f64> {
+        Array::from_shape_fn((2, 2, 4, 4), |(b, c, i, j)| {
+            ((b * 32 + c * 16 + i * 4 + j) as f64 * 0.37).sin()
+        })
+    }
+
+    fn sample_filters() -> Array4<f64> {
+        Array::from_shape_fn((3, 2, 3, 3), |(f, c, i, j)| {
+            ((f * 18 + c * 9 + i * 3 + j) as f64 * 0.71).cos()
+        })
+    }
+
+    #[test]
+    fn test_im2col_shape_and_round_trip() {
+        let x = sample_input();
+        let col = im2col(&x, 3, 3, 1, 1);
+        // 填充 1 后每张图 4×4 个位置，每行 2×3×3 个元素
+        assert_eq!(col.dim(), (2 * 4 * 4, 18));
+        assert_eq!(col[[0, 0]], 0.0);
+        assert_eq!(col[[0, 4]], x[[0, 0, 0, 0]]);
+
+        // 步幅等于窗口大小时窗口不重叠，col2im 能精确还原输入
+        let col = im2col(&x, 2, 2, 2, 0);
+        assert_eq!(col2im(&col, x.dim(), 2, 2, 2, 0), x);
+    }
+
+    #[test]
+    fn test_forward_matches_direct_convolution() {
+        let x = sample_input();
+        let filters = sample_filters();
+        let b = array![[0.1, -0.2, 0.3]];
+        let mut conv = Convolution::new(filters.clone(), b.clone(), 1, 0);
+        let y = conv.forward(&x);
+        assert_eq!(y.dim(), (2, 3, 2, 2));
+        assert_eq!(conv.filters(), filters);
+
+        for ((n, f, i, j), &v) in y.indexed_iter() {
+            let window = x.slice(s![n, .., i..i + 3, j..j + 3]);
+            let expected = (&window * &filters.slice(s![f, .., .., ..])).sum() + b[[0, f]];
+            assert!((v - expected).abs() < 1e-12);
+        }
+        assert_eq!(conv.apply(&x), y);
+    }
+
+    #[test]
+    fn test_backward_gradient_check() {
+        let x = sample_input();
+        let filters = sample_filters();
+        let b = array![[0.1, -0.2, 0.3]];
+        let make = |w: &Array2<f64>, b: &Array2<f64>| {
+            Convolution::from_col_weights(w.clone(), b.clone(), (3, 2, 3, 3), 2, 1)
+        };
+
+        let mut conv = Convolution::new(filters, b.clone(), 2, 1);
+        let w = conv.w.value.clone();
+        let out = conv.forward(&x);
+        let dout = out.mapv(|v| v.sin());
+        let dx = conv.backward(&dout);
+
+        let objective = |conv: &Convolution, x: &Array4<f64>| (conv.apply(x) * &dout).sum();
+        let numeric_dx = numerical_gradient(|x| objective(&make(&w, &b), x), &x);
+        let numeric_dw = numerical_gradient(|w| objective(&make(w, &b), &x), &w);
+        let numeric_db = numerical_gradient(|b| objective(&make(&w, b), &x), &b);
+
+        for (a, n) in dx.iter().zip(numeric_dx.iter()) {
+            assert!((a - n).abs() < 1e-6);
+        }
+        for (a, n) in conv.w.grad.iter().zip(numeric_dw.iter()) {
+            assert!((a - n).abs() < 1e-6);
+        }
+        for (a, n) in conv.b.grad.iter().zip(numeric_db.iter()) {
+            assert!((a - n).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,336 @@
+// src/chapter05/deep_conv_net.rs
+use super::convolution::{Convolution, conv_output_size};
+use super::layers::{Affine, Dropout, Flatten, Layer, ReluLayer, SoftmaxWithLoss};
+use super::pooling::Pooling;
+use super::tensor::{Tensor, map_in_batches, to_images};
+use crate::chapter02::activation::softmax;
+use crate::chapter02::init::{HeInit, Initializer};
+use crate::chapter02::loss::cross_entropy_from_logits;
+use crate::chapter02::metrics::{accuracy, argmax_rows};
+use crate::chapter02::model::{Model, Trainable};
+use crate::chapter02::serialize::{load_model, save_model};
+use ndarray::Array2;
+use std::io;
+use std::path::Path;
+
+/// 推理时每次送入网络的样本数
+const EVAL_BATCH: usize = 100;
+/// 所有卷积层都使用 3×3 的滤波器、步幅 1
+const FILTER_SIZE: usize = 3;
+/// 各卷积层的填充，与书中一致（第 4 层填充 2）
+const CONV_PADS: [usize; 6] = [1, 1, 1, 2, 1, 1];
+
+/// DeepConvNet 的超参数，默认值与书中第 8 章一致
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeepConvNetConfig {
+    /// (C, H, W)
+    pub input_dim: (usize, usize, usize),
+    /// 6 个卷积层各自的滤波器个数
+    pub conv_filters: [usize; 6],
+    pub hidden_size: usize,
+    pub output_size: usize,
+    pub dropout_ratio: f64,
+}
+
+impl Default for DeepConvNetConfig {
+    fn default() -> Self {
+        Self {
+            input_dim: (1, 28, 28),
+            conv_filters: [16, 16, 32, 32, 64, 64],
+            hidden_size: 50,
+            output_size: 10,
+            dropout_ratio: 0.5,
+        }
+    }
+}
+
+/// 书中第 8 章的深层卷积网络：
+///
+/// (Conv -> ReLU -> Conv -> ReLU -> Pool) × 3 -> Affine -> ReLU -> Dropout -> Affine -> Dropout -> Softmax
+///
+/// 卷积层都是 3×3 滤波器，权重用 He 初始化。输入与 `SimpleConvNet` 一样是 (N, C * H * W) 的矩阵。
+/// 参数按 w1, b1, …, w8, b8 的顺序排列（与书中的参数名一致），
+/// 可以用 `save` / `load_pretrained` 读写训练好的权重
+pub struct DeepConvNet {
+    pub config: DeepConvNetConfig,
+    pub convs: Vec<Convolution>,
+    pub affine1: Affine,
+    pub dropout1: Dropout,
+    pub affine2: Affine,
+    pub dropout2: Dropout,
+    conv_relus: Vec<ReluLayer>,
+    pools: Vec<Pooling>,
+    flatten: Flatten,
+    relu: ReluLayer,
+    last_layer: SoftmaxWithLoss,
+}
+
+impl DeepConvNet {
+    /// 用 He 初始化创建网络，`seed` 同时决定初始权重和 dropout 的掩码
+    pub fn new(config: DeepConvNetConfig, seed: u64) -> Self {
+        let (mut c, mut h, mut w) = config.input_dim;
+        let mut convs = Vec::with_capacity(6);
+        for (i, (&fnum, &pad)) in config.conv_filters.iter().zip(&CONV_PADS).enumerate() {
+            let (oh, ow) = conv_output_size(h, w, FILTER_SIZE, FILTER_SIZE, 1, pad);
+            convs.push(Convolution::from_col_weights(
+                HeInit.init(
+                    (c * FILTER_SIZE * FILTER_SIZE, fnum),
+                    seed.wrapping_add(i as u64),
+                ),
+                Array2::zeros((1, fnum)),
+                (fnum, c, FILTER_SIZE, FILTER_SIZE),
+                1,
+                pad,
+            ));
+            (c, h, w) = (fnum, oh, ow);
+            if i % 2 == 1 {
+                (h, w) = (h / 2, w / 2);
+            }
+        }
+        assert!(
+            h > 0 && w > 0,
+            "DeepConvNet: input {:?} is too small for three 2x2 poolings",
+            config.input_dim
+        );
+
+        let hidden = config.hidden_size;
+        Self {
+            config,
+            convs,
+            affine1: Affine::new(
+                HeInit.init((c * h * w, hidden), seed.wrapping_add(6)),
+                Array2::zeros((1, hidden)),
+            ),
+            dropout1: Dropout::new(config.dropout_ratio, seed.wrapping_add(8)),
+            affine2: Affine::new(
+                HeInit.init((hidden, config.output_size), seed.wrapping_add(7)),
+                Array2::zeros((1, config.output_size)),
+            ),
+            dropout2: Dropout::new(config.dropout_ratio, seed.wrapping_add(9)),
+            conv_relus: (0..6).map(|_| ReluLayer::new()).collect(),
+            pools: (0..3).map(|_| Pooling::new(2, 2, 2)).collect(),
+            flatten: Flatten::new(),
+            relu: ReluLayer::new(),
+            last_layer: SoftmaxWithLoss::new(),
+        }
+    }
+
+    /// 按 `config` 创建网络，再从 `save` / `save_model` 写出的 `.model` 文件读入权重。
+    /// 参数个数或形状与 `config` 不符时返回错误
+    pub fn load_pretrained<P: AsRef<Path>>(config: DeepConvNetConfig, path: P) -> io::Result<Self> {
+        let mut net = Self::new(config, 0);
+        load_model(&mut net, path)?;
+        Ok(net)
+    }
+
+    /// 把全部权重保存到 `.model` 文件
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        save_model(self, path)
+    }
+
+    /// 输出层 softmax 之前的得分（推理模式：dropout 只做缩放）
+    pub fn predict_logits(&self, x: &Array2<f64>) -> Array2<f64> {
+        map_in_batches(x, EVAL_BATCH, self.config.output_size, |x| {
+            self.logits_batch(x)
+        })
+    }
+
+    fn logits_batch(&self, x: &Array2<f64>) -> Array2<f64> {
+        let mut x = to_images(x, self.config.input_dim);
+        for (i, conv) in self.convs.iter().enumerate() {
+            x = conv.apply(&x).mapv(|v| v.max(0.0));
+            if i % 2 == 1 {
+                x = self.pools[i / 2].apply(&x);
+            }
+        }
+        let h = self.flatten.apply(&x);
+        let h = (h.dot(&self.affine1.w.value) + &self.affine1.b.value).mapv(|v| v.max(0.0));
+        let h = self.dropout1.apply(&h);
+        let y = h.dot(&self.affine2.w.value) + &self.affine2.b.value;
+        self.dropout2.apply(&y)
+    }
+
+    /// 各类别的概率
+    pub fn predict(&self, x: &Array2<f64>) -> Array2<f64> {
+        softmax(&self.predict_logits(x))
+    }
+
+    /// 交叉熵损失（推理模式）
+    pub fn loss(&self, x: &Array2<f64>, t: &Array2<f64>) -> f64 {
+        cross_entropy_from_logits(&self.predict_logits(x), t)
+    }
+
+    /// 识别精度，t 为 one-hot 标签
+    pub fn accuracy(&self, x: &Array2<f64>, t: &Array2<f64>) -> f64 {
+        accuracy(&argmax_rows(&self.predict_logits(x)), &argmax_rows(t))
+    }
+
+    /// 用误差反向传播求梯度（训练模式：dropout 随机丢弃），顺序与 `params` 一致
+    pub fn gradient(&mut self, x: &Array2<f64>, t: &Array2<f64>) -> Vec<Array2<f64>> {
+        let mut x = Tensor::Img(to_images(x, self.config.input_dim));
+        for (i, (conv, relu)) in self.convs.iter_mut().zip(&mut self.conv_relus).enumerate() {
+            x = relu.forward_tensor(&conv.forward_tensor(&x));
+            if i % 2 == 1 {
+                x = self.pools[i / 2].forward_tensor(&x);
+            }
+        }
+        let h = self.flatten.forward_tensor(&x).into_mat();
+        let h = self.affine1.forward(&h);
+        let h = self.relu.forward(&h);
+        let h = self.dropout1.forward(&h);
+        let y = self.affine2.forward(&h);
+        let y = self.dropout2.forward(&y);
+        self.last_layer.forward(&y, t);
+
+        let dout = self.last_layer.backward();
+        let dout = self.dropout2.backward(&dout);
+        let dout = self.affine2.backward(&dout);
+        let dout = self.dropout1.backward(&dout);
+        let dout = self.relu.backward(&dout);
+        let dout = Tensor::Mat(self.affine1.backward(&dout));
+        let mut dout = self.flatten.backward_tensor(&dout);
+        for (i, (conv, relu)) in self
+            .convs
+            .iter_mut()
+            .zip(&mut self.conv_relus)
+            .enumerate()
+            .rev()
+        {
+            if i % 2 == 1 {
+                dout = self.pools[i / 2].backward_tensor(&dout);
+            }
+            dout = conv.backward_tensor(&relu.backward_tensor(&dout));
+        }
+
+        self.convs
+            .iter()
+            .flat_map(|conv| [&conv.w, &conv.b])
+            .chain([
+                &self.affine1.w,
+                &self.affine1.b,
+                &self.affine2.w,
+                &self.affine2.b,
+            ])
+            .map(|p| p.grad.clone())
+            .collect()
+    }
+}
+
+impl Model for DeepConvNet {
+    fn predict(&self, x: &Array2<f64>) -> Array2<f64> {
+        DeepConvNet::predict(self, x)
+    }
+}
+
+impl Trainable for DeepConvNet {
+    fn loss(&self, x: &Array2<f64>, t: &Array2<f64>) -> f64 {
+        DeepConvNet::loss(self, x, t)
+    }
+
+    fn gradient(&mut self, x: &Array2<f64>, t: &Array2<f64>) -> Vec<Array2<f64>> {
+        DeepConvNet::gradient(self, x, t)
+    }
+
+    fn params(&self) -> Vec<&Array2<f64>> {
+        self.convs
+            .iter()
+            .flat_map(|conv| [&conv.w.value, &conv.b.value])
+            .chain([
+                &self.affine1.w.value,
+                &self.affine1.b.value,
+                &self.affine2.w.value,
+                &self.affine2.b.value,
+            ])
+            .collect()
+    }
+
+    fn params_mut(&mut self) -> Vec<&mut Array2<f64>> {
+        self.convs
+            .iter_mut()
+            .flat_map(|conv| [&mut conv.w.value, &mut conv.b.value])
+            .chain([
+                &mut self.affine1.w.value,
+                &mut self.affine1.b.value,
+                &mut self.affine2.w.value,
+                &mut self.affine2.b.value,
+            ])
+            .collect()
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        (1..=8)
+            .flat_map(|i| [format!("w{}", i), format!("b{}", i)])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chapter02::grad::check_gradients;
+    use crate::chapter02::metrics::to_one_hot;
+    use ndarray::Array1;
+
+    fn tiny_config(dropout_ratio: f64) -> DeepConvNetConfig {
+        DeepConvNetConfig {
+            input_dim: (1, 8, 8),
+            conv_filters: [2, 2, 3, 3, 4, 4],
+            hidden_size: 5,
+            output_size: 3,
+            dropout_ratio,
+        }
+    }
+
+    fn tiny_data(n: usize) -> (Array2<f64>, Array2<f64>) {
+        let x = Array2::from_shape_fn((n, 64), |(i, j)| ((i * 64 + j) as f64 * 0.291).sin());
+        let labels = Array1::from_shape_fn(n, |i| i % 3);
+        (x, to_one_hot(&labels, 3))
+    }
+
+    #[test]
+    fn test_default_matches_book_shapes() {
+        let net = DeepConvNet::new(DeepConvNetConfig::default(), 1);
+        let shapes: Vec<&[usize]> = net.params().iter().map(|p| p.shape()).collect();
+        assert_eq!(shapes.len(), 16);
+        assert_eq!(shapes[0], &[9, 16]);
+        // 三次池化后为 64 × 4 × 4
+        assert_eq!(shapes[12], &[64 * 4 * 4, 50]);
+        assert_eq!(shapes[14], &[50, 10]);
+        assert_eq!(net.param_names()[14], "w8");
+    }
+
+    #[test]
+    fn test_gradient_check_without_dropout() {
+        // 不丢弃时训练模式和推理模式的前向传播相同，可以和数值微分比较
+        let mut net = DeepConvNet::new(tiny_config(0.0), 5);
+        // 这么小的网络里很多 ReLU 输出为 0，池化窗口全为 0 时会落在折点上，数值微分不可靠；
+        // 用正的偏置让所有单元都处于激活状态
+        for conv in &mut net.convs {
+            conv.b.value.fill(2.0);
+        }
+        net.affine1.b.value.fill(2.0);
+        let (x, t) = tiny_data(3);
+        assert_eq!(check_gradients(&mut net, &x, &t, 1e-5), Ok(()));
+    }
+
+    #[test]
+    fn test_save_and_load_pretrained() {
+        let config = tiny_config(0.5);
+        let net = DeepConvNet::new(config, 11);
+        let path =
+            std::env::temp_dir().join(format!("rdl_deep_convnet_{}.model", std::process::id()));
+        net.save(&path).unwrap();
+
+        let loaded = DeepConvNet::load_pretrained(config, &path).unwrap();
+        let (x, _) = tiny_data(4);
+        assert_eq!(loaded.predict(&x), net.predict(&x));
+
+        // 结构不同的网络不能读入
+        let other = DeepConvNetConfig {
+            hidden_size: 6,
+            ..config
+        };
+        assert!(DeepConvNet::load_pretrained(other, &path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
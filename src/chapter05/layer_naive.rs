@@ -0,0 +1,123 @@
+// src/chapter05/layer_naive.rs
+// 计算图中最简单的两种节点（书中“买苹果”的例子），只处理标量
+
+/// 乘法节点：z = x * y，反向时 dx = dout * y，dy = dout * x（交换输入）
+#[derive(Debug, Default)]
+pub struct MulLayer {
+    x: f64,
+    y: f64,
+}
+
+impl MulLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn forward(&mut self, x: f64, y: f64) -> f64 {
+        self.x = x;
+        self.y = y;
+        x * y
+    }
+
+    /// 返回 (dx, dy)
+    pub fn backward(&self, dout: f64) -> (f64, f64) {
+        (dout * self.y, dout * self.x)
+    }
+}
+
+/// 加法节点：z = x + y，反向时把上游梯度原样传给两个输入
+#[derive(Debug, Default)]
+pub struct AddLayer;
+
+impl AddLayer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn forward(&mut self, x: f64, y: f64) -> f64 {
+        x + y
+    }
+
+    /// 返回 (dx, dy)
+    pub fn backward(&self, dout: f64) -> (f64, f64) {
+        (dout, dout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buy_apple() {
+        let (apple, apple_num, tax) = (100.0, 2.0, 1.1);
+        let mut mul_apple = MulLayer::new();
+        let mut mul_tax = MulLayer::new();
+
+        let apple_price = mul_apple.forward(apple, apple_num);
+        let price = mul_tax.forward(apple_price, tax);
+        assert!((price - 220.0).abs() < 1e-9);
+
+        let (dapple_price, dtax) = mul_tax.backward(1.0);
+        let (dapple, dapple_num) = mul_apple.backward(dapple_price);
+        assert!((dapple - 2.2).abs() < 1e-9);
+        assert!((dapple_num - 110.0).abs() < 1e-9);
+        assert!((dtax - 200.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_buy_apple_orange() {
+        let (apple, apple_num) = (100.0, 2.0);
+        let (orange, orange_num) = (150.0, 3.0);
+        let tax = 1.1;
+
+        let mut mul_apple = MulLayer::new();
+        let mut mul_orange = MulLayer::new();
+        let mut add_fruit = AddLayer::new();
+        let mut mul_tax = MulLayer::new();
+
+        let price = |apple: f64, apple_num: f64, orange: f64, orange_num: f64, tax: f64| {
+            (apple * apple_num + orange * orange_num) * tax
+        };
+
+        let apple_price = mul_apple.forward(apple, apple_num);
+        let orange_price = mul_orange.forward(orange, orange_num);
+        let all_price = add_fruit.forward(apple_price, orange_price);
+        let total = mul_tax.forward(all_price, tax);
+        assert!((total - price(apple, apple_num, orange, orange_num, tax)).abs() < 1e-9);
+
+        let (dall_price, dtax) = mul_tax.backward(1.0);
+        let (dapple_price, dorange_price) = add_fruit.backward(dall_price);
+        let (dorange, dorange_num) = mul_orange.backward(dorange_price);
+        let (dapple, dapple_num) = mul_apple.backward(dapple_price);
+
+        // 链式法则得到的梯度应与对整个式子做中心差分的结果一致
+        let h = 1e-4;
+        let numeric = [
+            (price(apple + h, apple_num, orange, orange_num, tax)
+                - price(apple - h, apple_num, orange, orange_num, tax))
+                / (2.0 * h),
+            (price(apple, apple_num + h, orange, orange_num, tax)
+                - price(apple, apple_num - h, orange, orange_num, tax))
+                / (2.0 * h),
+            (price(apple, apple_num, orange + h, orange_num, tax)
+                - price(apple, apple_num, orange - h, orange_num, tax))
+                / (2.0 * h),
+            (price(apple, apple_num, orange, orange_num + h, tax)
+                - price(apple, apple_num, orange, orange_num - h, tax))
+                / (2.0 * h),
+            (price(apple, apple_num, orange, orange_num, tax + h)
+                - price(apple, apple_num, orange, orange_num, tax - h))
+                / (2.0 * h),
+        ];
+        let analytic = [dapple, dapple_num, dorange, dorange_num, dtax];
+        for (a, n) in analytic.iter().zip(numeric.iter()) {
+            assert!((a - n).abs() < 1e-6);
+        }
+
+        // 书中给出的数值
+        assert!((dapple_num - 110.0).abs() < 1e-9);
+        assert!((dorange - 3.3).abs() < 1e-9);
+        assert!((dtax - 650.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,484 @@
+// src/chapter05/layers.rs
+use super::activation::{Activation, Relu, Sigmoid};
+use super::parameter::Parameter;
+use super::tensor::{Tensor, flatten};
+use crate::chapter02::activation::softmax;
+use crate::chapter02::loss::cross_entropy_from_logits;
+use ndarray::{Array2, Array4, Axis};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// 误差反向传播中的一层：前向传播时缓存需要的中间结果，反向传播时利用它们求梯度
+pub trait Layer {
+    fn forward(&mut self, x: &Array2<f64>) -> Array2<f64>;
+
+    /// 输入上游传来的梯度 dout，返回对本层输入的梯度。
+    /// 参数梯度会被覆盖而不是累加：每次 backward 之后 grad 只对应最近一个批次
+    fn backward(&mut self, dout: &Array2<f64>) -> Array2<f64>;
+
+    /// 以 `Tensor` 为输入输出的前向传播，`Sequential` 通过它把卷积层和全连接层串起来。
+    /// 默认只接受二维输入并转给 `forward`；处理四维数据的层需要覆盖它
+    fn forward_tensor(&mut self, x: &Tensor) -> Tensor {
+        Tensor::Mat(self.forward(x.as_mat()))
+    }
+
+    /// `forward_tensor` 对应的反向传播，默认转给 `backward`
+    fn backward_tensor(&mut self, dout: &Tensor) -> Tensor {
+        Tensor::Mat(self.backward(dout.as_mat()))
+    }
+
+    /// 最近一次反向传播得到的参数梯度（Affine 为 [dW, db]），没有参数的层返回空
+    fn grads(&self) -> Vec<&Array2<f64>> {
+        Vec::new()
+    }
+
+    /// 本层的可学习参数（只读），没有参数的层返回空
+    fn params(&self) -> Vec<&Parameter> {
+        Vec::new()
+    }
+
+    /// 本层的可学习参数（含梯度），供优化器更新；没有参数的层返回空
+    fn params_mut(&mut self) -> Vec<&mut Parameter> {
+        Vec::new()
+    }
+
+    /// 把本层所有参数梯度清零；默认实现遍历 `params_mut`
+    fn zero_grad(&mut self) {
+        for param in self.params_mut() {
+            param.zero_grad();
+        }
+    }
+
+    /// 可学习参数的个数（与梯度的元素总数相同）
+    fn param_count(&self) -> usize {
+        self.grads().iter().map(|g| g.len()).sum()
+    }
+}
+
+/// 全连接层：y = x·W + b（`use_bias` 为 false 时 y = x·W）
+pub struct Affine {
+    pub w: Parameter,
+    pub b: Parameter,
+    pub use_bias: bool,
+    x: Array2<f64>,
+}
+
+impl Affine {
+    /// `w` 形状为 (输入数, 输出数)，`b` 形状为 (1, 输出数)，与 `SimpleNet` 的参数一致
+    pub fn new(w: Array2<f64>, b: Array2<f64>) -> Self {
+        assert_eq!(
+            b.shape(),
+            &[1, w.ncols()],
+            "Affine: bias must be (1, {})",
+            w.ncols()
+        );
+        Self {
+            x: Array2::zeros((0, w.nrows())),
+            use_bias: true,
+            w: Parameter::new(w),
+            b: Parameter::new(b),
+        }
+    }
+
+    /// 不带偏置的全连接层：前向不加偏置，反向不计算 db，梯度列表中也没有 db
+    pub fn without_bias(w: Array2<f64>) -> Self {
+        let b = Array2::zeros((1, w.ncols()));
+        Self {
+            use_bias: false,
+            ..Self::new(w, b)
+        }
+    }
+}
+
+impl Layer for Affine {
+    fn forward(&mut self, x: &Array2<f64>) -> Array2<f64> {
+        self.x = x.clone();
+        let y = x.dot(&self.w.value);
+        if self.use_bias { y + &self.b.value } else { y }
+    }
+
+    fn backward(&mut self, dout: &Array2<f64>) -> Array2<f64> {
+        self.w.grad = self.x.t().dot(dout);
+        if self.use_bias {
+            self.b.grad = dout.sum_axis(Axis(0)).insert_axis(Axis(0));
+        }
+        dout.dot(&self.w.value.t())
+    }
+
+    fn grads(&self) -> Vec<&Array2<f64>> {
+        if self.use_bias {
+            vec![&self.w.grad, &self.b.grad]
+        } else {
+            vec![&self.w.grad]
+        }
+    }
+
+    fn params(&self) -> Vec<&Parameter> {
+        if self.use_bias {
+            vec![&self.w, &self.b]
+        } else {
+            vec![&self.w]
+        }
+    }
+
+    fn params_mut(&mut self) -> Vec<&mut Parameter> {
+        if self.use_bias {
+            vec![&mut self.w, &mut self.b]
+        } else {
+            vec![&mut self.w]
+        }
+    }
+}
+
+/// 把逐元素的 `Activation` 包装成一层：前向时缓存输入 x，反向时交给 `A::backward(x, dout)`
+#[derive(Default)]
+pub struct ActivationLayer<A: Activation> {
+    pub activation: A,
+    x: Array2<f64>,
+}
+
+impl<A: Activation + Default> ActivationLayer<A> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<A: Activation> Layer for ActivationLayer<A> {
+    fn forward(&mut self, x: &Array2<f64>) -> Array2<f64> {
+        self.x = x.clone();
+        self.activation.forward(x)
+    }
+
+    fn backward(&mut self, dout: &Array2<f64>) -> Array2<f64> {
+        self.activation.backward(&self.x, dout)
+    }
+
+    fn forward_tensor(&mut self, x: &Tensor) -> Tensor {
+        x.map_as_mat(|m| self.forward(m))
+    }
+
+    fn backward_tensor(&mut self, dout: &Tensor) -> Tensor {
+        dout.map_as_mat(|d| self.backward(d))
+    }
+}
+
+/// ReLU 层：输入 <= 0 的位置梯度为 0
+pub type ReluLayer = ActivationLayer<Relu>;
+
+/// Sigmoid 层：dx = dout * y * (1 - y)
+pub type SigmoidLayer = ActivationLayer<Sigmoid>;
+
+/// Dropout 层（与书中的实现相同）：训练时（`training = true`）每个元素以 `ratio` 的概率置 0，
+/// 反向时梯度只流过保留下来的元素；推理时不丢弃，而是把输出乘以 (1 - ratio)，
+/// 使其期望与训练时一致
+pub struct Dropout {
+    pub ratio: f64,
+    pub training: bool,
+    mask: Array2<bool>,
+    rng: StdRng,
+}
+
+impl Dropout {
+    /// `seed` 决定每次前向传播的丢弃掩码（例如用 `RunConfig::dropout_seed`）
+    pub fn new(ratio: f64, seed: u64) -> Self {
+        assert!(
+            (0.0..1.0).contains(&ratio),
+            "Dropout: ratio must be in [0, 1)"
+        );
+        Self {
+            ratio,
+            training: true,
+            mask: Array2::from_elem((0, 0), true),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// 推理时的输出 x * (1 - ratio)，不改变内部状态
+    pub fn apply(&self, x: &Array2<f64>) -> Array2<f64> {
+        x * (1.0 - self.ratio)
+    }
+}
+
+impl Layer for Dropout {
+    fn forward(&mut self, x: &Array2<f64>) -> Array2<f64> {
+        if !self.training {
+            return self.apply(x);
+        }
+        let ratio = self.ratio;
+        self.mask = x.map(|_| self.rng.random::<f64>() >= ratio);
+        let mut out = x.clone();
+        out.zip_mut_with(&self.mask, |v, &keep| {
+            if !keep {
+                *v = 0.0;
+            }
+        });
+        out
+    }
+
+    fn backward(&mut self, dout: &Array2<f64>) -> Array2<f64> {
+        if !self.training {
+            return self.apply(dout);
+        }
+        let mut dx = dout.clone();
+        dx.zip_mut_with(&self.mask, |d, &keep| {
+            if !keep {
+                *d = 0.0;
+            }
+        });
+        dx
+    }
+
+    fn forward_tensor(&mut self, x: &Tensor) -> Tensor {
+        x.map_as_mat(|m| self.forward(m))
+    }
+
+    fn backward_tensor(&mut self, dout: &Tensor) -> Tensor {
+        dout.map_as_mat(|d| self.backward(d))
+    }
+}
+
+/// 把卷积 / 池化层输出的 (N, C, H, W) 展平成 (N, C * H * W)，使其可以接到 `Affine` 之后；
+/// 反向时把梯度还原成前向输入的形状。二维输入原样通过
+#[derive(Default)]
+pub struct Flatten {
+    x_shape: Option<(usize, usize, usize, usize)>,
+}
+
+impl Flatten {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 只做展平、不记录形状（推理用）
+    pub fn apply(&self, x: &Array4<f64>) -> Array2<f64> {
+        flatten(x)
+    }
+}
+
+impl Layer for Flatten {
+    fn forward(&mut self, x: &Array2<f64>) -> Array2<f64> {
+        self.x_shape = None;
+        x.clone()
+    }
+
+    fn backward(&mut self, dout: &Array2<f64>) -> Array2<f64> {
+        dout.clone()
+    }
+
+    fn forward_tensor(&mut self, x: &Tensor) -> Tensor {
+        match x {
+            Tensor::Mat(m) => Tensor::Mat(self.forward(m)),
+            Tensor::Img(img) => {
+                self.x_shape = Some(img.dim());
+                Tensor::Mat(flatten(img))
+            }
+        }
+    }
+
+    fn backward_tensor(&mut self, dout: &Tensor) -> Tensor {
+        let dout = dout.as_mat();
+        match self.x_shape {
+            None => Tensor::Mat(dout.clone()),
+            Some(shape) => Tensor::Img(
+                dout.as_standard_layout()
+                    .into_owned()
+                    .into_shape_with_order(shape)
+                    .expect("Flatten: dout must match the flattened forward output"),
+            ),
+        }
+    }
+}
+
+/// Softmax-with-Loss 层：前向输出 softmax + 交叉熵损失，反向输出 (y - t) / batch_size。
+///
+/// 损失直接由得分用 log-sum-exp 计算，不会因为 softmax 输出下溢为 0 而出现 log(0)。
+/// 前向需要标签 t 且输出是标量，所以不实现 `Layer`
+#[derive(Default)]
+pub struct SoftmaxWithLoss {
+    y: Array2<f64>,
+    t: Array2<f64>,
+}
+
+impl SoftmaxWithLoss {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `x` 为得分（未经 softmax），`t` 为 one-hot 标签
+    pub fn forward(&mut self, x: &Array2<f64>, t: &Array2<f64>) -> f64 {
+        assert_eq!(x.dim(), t.dim(), "SoftmaxWithLoss: x and t shape mismatch");
+        self.y = softmax(x);
+        self.t = t.clone();
+        cross_entropy_from_logits(x, t)
+    }
+
+    /// 损失对输入得分的梯度（上游梯度为 1）
+    pub fn backward(&self) -> Array2<f64> {
+        assert!(
+            self.y.nrows() > 0,
+            "SoftmaxWithLoss::backward called before forward"
+        );
+        (&self.y - &self.t) / self.y.nrows() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chapter02::activation::sigmoid;
+    use ndarray::array;
+
+    #[test]
+    fn test_affine_forward_backward() {
+        let mut affine = Affine::new(
+            array![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]],
+            array![[0.5, -0.5]],
+        );
+        let x = array![[1.0, 0.0, -1.0], [2.0, 1.0, 0.0]];
+
+        let y = affine.forward(&x);
+        assert_eq!(y, array![[-3.5, -4.5], [5.5, 7.5]]);
+
+        let dout = array![[1.0, 0.0], [0.0, 1.0]];
+        let dx = affine.backward(&dout);
+        assert_eq!(dx, array![[1.0, 3.0, 5.0], [2.0, 4.0, 6.0]]);
+        assert_eq!(affine.w.grad, x.t().dot(&dout));
+        assert_eq!(affine.b.grad, array![[1.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_affine_without_bias() {
+        let w = array![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]];
+        let mut affine = Affine::without_bias(w.clone());
+        let x = array![[1.0, 0.0, -1.0], [2.0, 1.0, 0.0]];
+
+        assert_eq!(affine.forward(&x), x.dot(&w));
+        affine.backward(&array![[1.0, 1.0], [1.0, 1.0]]);
+        assert_eq!(affine.b.grad, array![[0.0, 0.0]]);
+        assert_eq!(affine.grads().len(), 1);
+        assert_eq!(affine.params_mut().len(), 1);
+        assert_eq!(affine.param_count(), 6);
+
+        let with_bias = Affine::new(w, array![[0.0, 0.0]]);
+        assert_eq!(with_bias.param_count(), 8);
+    }
+
+    #[test]
+    fn test_affine_accepts_simple_net_weights() {
+        use crate::chapter02::init::XavierInit;
+        use crate::chapter02::network::SimpleNet;
+
+        let net = SimpleNet::with_initializer(3, 4, 2, &XavierInit, 1);
+        let x = array![[1.0, 0.5, -1.2], [0.0, 0.1, 0.2]];
+        let cache = net.forward_with_activations(&x);
+
+        let mut affine1 = Affine::new(net.w1.clone(), net.b1.clone());
+        let mut affine2 = Affine::new(net.w2.clone(), net.b2.clone());
+        let a1 = affine1.forward(&x);
+        let a2 = affine2.forward(&sigmoid(&a1));
+        assert_eq!(a1, cache.a1);
+        assert_eq!(a2, cache.a2);
+
+        // 梯度形状与 SimpleNet 的参数一一对应，可直接用于更新
+        affine2.backward(&Array2::ones(a2.raw_dim()));
+        assert_eq!(affine2.w.grad.shape(), net.w2.shape());
+        assert_eq!(affine2.b.grad.shape(), net.b2.shape());
+    }
+
+    #[test]
+    fn test_relu_layer() {
+        let mut relu = ReluLayer::new();
+        let y = relu.forward(&array![[1.0, -0.5], [-2.0, 3.0]]);
+        assert_eq!(y, array![[1.0, 0.0], [0.0, 3.0]]);
+
+        let dx = relu.backward(&array![[1.0, 1.0], [1.0, 1.0]]);
+        assert_eq!(dx, array![[1.0, 0.0], [0.0, 1.0]]);
+        assert!(relu.grads().is_empty());
+    }
+
+    #[test]
+    fn test_sigmoid_layer() {
+        let mut layer = SigmoidLayer::new();
+        let y = layer.forward(&array![[0.0, 2.0]]);
+        assert_eq!(y[[0, 0]], 0.5);
+
+        let dx = layer.backward(&array![[2.0, 1.0]]);
+        assert_eq!(dx[[0, 0]], 0.5);
+        assert!((dx[[0, 1]] - y[[0, 1]] * (1.0 - y[[0, 1]])).abs() < 1e-12);
+        assert!(layer.grads().is_empty());
+    }
+
+    #[test]
+    fn test_softmax_with_loss() {
+        use crate::chapter02::grad::numerical_gradient;
+
+        let x = array![[0.3, 2.9, 4.0], [1.0, -0.5, 0.2]];
+        let t = array![[0.0, 0.0, 1.0], [1.0, 0.0, 0.0]];
+
+        let mut layer = SoftmaxWithLoss::new();
+        assert!(layer.forward(&x, &t) > 0.0);
+        let dx = layer.backward();
+        let numeric = numerical_gradient(|x| SoftmaxWithLoss::new().forward(x, &t), &x);
+        for (a, n) in dx.iter().zip(numeric.iter()) {
+            assert!((a - n).abs() < 1e-6);
+        }
+
+        // 得分差距极大时 softmax 输出会下溢为 0，损失仍应有限
+        let loss = layer.forward(&array![[1000.0, -1000.0]], &array![[0.0, 1.0]]);
+        assert!((loss - 2000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_flatten_round_trip() {
+        let x = Array4::from_shape_fn((2, 3, 2, 2), |(n, c, i, j)| {
+            (n * 12 + c * 4 + i * 2 + j) as f64
+        });
+        let mut flatten = Flatten::new();
+
+        let y = flatten.forward_tensor(&Tensor::from(x.clone())).into_mat();
+        assert_eq!(y.dim(), (2, 12));
+        assert_eq!(
+            y.row(1).to_vec(),
+            (12..24).map(f64::from).collect::<Vec<_>>()
+        );
+        assert_eq!(flatten.apply(&x), y);
+
+        // 反向传播把梯度还原成前向输入的形状
+        let dx = flatten.backward_tensor(&Tensor::Mat(y)).into_img();
+        assert_eq!(dx, x);
+
+        // 二维输入原样通过
+        let m = array![[1.0, 2.0]];
+        assert_eq!(
+            flatten.forward_tensor(&Tensor::Mat(m.clone())),
+            Tensor::Mat(m.clone())
+        );
+        assert_eq!(
+            flatten.backward_tensor(&Tensor::Mat(m.clone())),
+            Tensor::Mat(m)
+        );
+    }
+
+    #[test]
+    fn test_dropout_train_and_inference() {
+        let x = Array2::from_elem((50, 40), 2.0);
+        let mut dropout = Dropout::new(0.3, 7);
+
+        let y = dropout.forward(&x);
+        let kept = y.iter().filter(|&&v| v != 0.0).count() as f64 / y.len() as f64;
+        assert!((kept - 0.7).abs() < 0.05);
+        assert!(y.iter().all(|&v| v == 0.0 || v == 2.0));
+
+        // 梯度只流过保留下来的元素
+        let dx = dropout.backward(&Array2::ones((50, 40)));
+        for (d, v) in dx.iter().zip(y.iter()) {
+            assert_eq!(*d, if *v == 0.0 { 0.0 } else { 1.0 });
+        }
+
+        // 同一种子得到同样的掩码
+        assert_eq!(Dropout::new(0.3, 7).forward(&x), y);
+
+        dropout.training = false;
+        assert_eq!(dropout.forward(&x), Array2::from_elem((50, 40), 1.4));
+    }
+}
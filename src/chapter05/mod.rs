@@ -0,0 +1,12 @@
+pub mod activation;
+pub mod batch_norm;
+pub mod convolution;
+pub mod deep_conv_net;
+pub mod layer_naive;
+pub mod layers;
+pub mod parameter;
+pub mod pooling;
+pub mod sequential;
+pub mod simple_conv_net;
+pub mod tensor;
+pub mod two_layer_net;
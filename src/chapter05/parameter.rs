@@ -0,0 +1,42 @@
+// src/chapter05/parameter.rs
+use ndarray::Array2;
+
+/// 可学习参数：把参数值和反向传播得到的梯度放在一起，
+/// 优化器只需遍历 `Parameter` 就能完成更新，不必分别传参数和梯度
+#[derive(Clone, Debug)]
+pub struct Parameter {
+    pub value: Array2<f64>,
+    pub grad: Array2<f64>,
+}
+
+impl Parameter {
+    /// 梯度初始化为与参数同形状的 0
+    pub fn new(value: Array2<f64>) -> Self {
+        Self {
+            grad: Array2::zeros(value.raw_dim()),
+            value,
+        }
+    }
+
+    /// 把梯度清零，参数值不变
+    pub fn zero_grad(&mut self) {
+        self.grad.fill(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_zero_grad() {
+        let mut p = Parameter::new(array![[1.0, -2.0], [3.0, 4.0]]);
+        assert_eq!(p.grad, Array2::zeros((2, 2)));
+
+        p.grad = array![[0.5, 0.5], [-1.0, 2.0]];
+        p.zero_grad();
+        assert_eq!(p.grad, Array2::zeros((2, 2)));
+        assert_eq!(p.value, array![[1.0, -2.0], [3.0, 4.0]]);
+    }
+}
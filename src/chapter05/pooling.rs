@@ -0,0 +1,302 @@
+// src/chapter05/pooling.rs
+use super::layers::Layer;
+use super::tensor::{Tensor, flatten, image_input, to_images};
+use ndarray::{Array2, Array4, s};
+
+/// 池化方式：取窗口内的最大值，或取窗口内的平均值
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PoolMode {
+    #[default]
+    Max,
+    Average,
+}
+
+/// 池化层，输入输出形状均为 (N, C, H, W)。
+///
+/// 输出大小为 `(H + 2 * pad - pool_h) / stride + 1` × `(W + 2 * pad - pool_w) / stride + 1`。
+/// 填充部分在 Max 模式下视为 -∞（不会被选中），在 Average 模式下视为 0 并计入窗口面积。
+/// 池化层没有可学习参数；放进 `Sequential` 时通过 `Layer::forward_tensor` 接受四维输入，
+/// 设置了 `input_shape` 时也接受每行一张展平图像的 (N, C * H * W) 矩阵
+pub struct Pooling {
+    pub pool_h: usize,
+    pub pool_w: usize,
+    pub stride: usize,
+    pub pad: usize,
+    pub mode: PoolMode,
+    /// 二维输入每行还原成的图像形状 (C, H, W)
+    pub input_shape: Option<(usize, usize, usize)>,
+    x_shape: (usize, usize, usize, usize),
+    // Max 模式下每个输出位置对应的最大值在窗口内的下标 (dy, dx)
+    arg_max: Array4<(usize, usize)>,
+    // 最近一次 `forward_tensor` 的输入是否为二维，决定反向传播时梯度的形式
+    mat_input: bool,
+}
+
+impl Pooling {
+    /// 最大池化
+    pub fn new(pool_h: usize, pool_w: usize, stride: usize) -> Self {
+        assert!(
+            pool_h > 0 && pool_w > 0 && stride > 0,
+            "Pooling: sizes must be positive"
+        );
+        Self {
+            pool_h,
+            pool_w,
+            stride,
+            pad: 0,
+            mode: PoolMode::Max,
+            input_shape: None,
+            x_shape: (0, 0, 0, 0),
+            arg_max: Array4::from_elem((0, 0, 0, 0), (0, 0)),
+            mat_input: false,
+        }
+    }
+
+    pub fn with_mode(self, mode: PoolMode) -> Self {
+        Self { mode, ..self }
+    }
+
+    /// 接受展平成 (N, C * H * W) 的输入
+    pub fn with_input_shape(self, shape: (usize, usize, usize)) -> Self {
+        Self {
+            input_shape: Some(shape),
+            ..self
+        }
+    }
+
+    /// 在输入的上下左右各填充 `pad` 行 / 列。`pad` 必须小于窗口大小，
+    /// 否则会有窗口完全落在填充区域里
+    pub fn with_pad(self, pad: usize) -> Self {
+        assert!(
+            pad < self.pool_h && pad < self.pool_w,
+            "Pooling: pad must be smaller than the window"
+        );
+        Self { pad, ..self }
+    }
+
+    /// `h`、`w` 为填充后的大小
+    fn output_size(&self, h: usize, w: usize) -> (usize, usize) {
+        assert!(
+            h >= self.pool_h && w >= self.pool_w,
+            "Pooling: input {}x{} is smaller than the {}x{} window",
+            h,
+            w,
+            self.pool_h,
+            self.pool_w
+        );
+        (
+            (h - self.pool_h) / self.stride + 1,
+            (w - self.pool_w) / self.stride + 1,
+        )
+    }
+
+    pub fn forward(&mut self, x: &Array4<f64>) -> Array4<f64> {
+        self.x_shape = x.dim();
+        let (out, arg_max) = self.pool(x);
+        self.arg_max = arg_max;
+        out
+    }
+
+    /// 只做前向计算、不记录最大值位置（推理用）
+    pub fn apply(&self, x: &Array4<f64>) -> Array4<f64> {
+        self.pool(x).0
+    }
+
+    /// 返回池化结果，以及 Max 模式下每个输出对应的最大值在窗口内的位置
+    fn pool(&self, x: &Array4<f64>) -> (Array4<f64>, Array4<(usize, usize)>) {
+        let (n, c, h, w) = x.dim();
+        let fill = match self.mode {
+            PoolMode::Max => f64::NEG_INFINITY,
+            PoolMode::Average => 0.0,
+        };
+        let padded = self.padded(x, fill);
+        let (out_h, out_w) = self.output_size(h + 2 * self.pad, w + 2 * self.pad);
+        let mut arg_max = Array4::from_elem((n, c, out_h, out_w), (0, 0));
+
+        let mut out = Array4::zeros((n, c, out_h, out_w));
+        for ((b, ch, i, j), o) in out.indexed_iter_mut() {
+            let (y0, x0) = (i * self.stride, j * self.stride);
+            let window = padded.slice(s![b, ch, y0..y0 + self.pool_h, x0..x0 + self.pool_w]);
+            *o = match self.mode {
+                PoolMode::Max => {
+                    let (pos, &max) = window
+                        .indexed_iter()
+                        .fold(
+                            None,
+                            |best: Option<((usize, usize), &f64)>, (p, v)| match best {
+                                Some((_, m)) if *m >= *v => best,
+                                _ => Some((p, v)),
+                            },
+                        )
+                        .expect("pooling window is non-empty");
+                    arg_max[[b, ch, i, j]] = pos;
+                    max
+                }
+                PoolMode::Average => window.mean().unwrap_or(0.0),
+            };
+        }
+        (out, arg_max)
+    }
+
+    /// Max：梯度只流向窗口内的最大值位置；Average：梯度平均分给窗口内的每个元素。
+    /// 窗口重叠（stride 小于窗口大小）时梯度会累加
+    pub fn backward(&self, dout: &Array4<f64>) -> Array4<f64> {
+        assert_eq!(
+            dout.dim(),
+            self.arg_max.dim(),
+            "Pooling: dout shape mismatch"
+        );
+        let (n, c, h, w) = self.x_shape;
+        let p = self.pad;
+        let mut dx = Array4::zeros((n, c, h + 2 * p, w + 2 * p));
+        let area = (self.pool_h * self.pool_w) as f64;
+
+        for ((b, ch, i, j), &d) in dout.indexed_iter() {
+            let (y0, x0) = (i * self.stride, j * self.stride);
+            match self.mode {
+                PoolMode::Max => {
+                    let (dy, dxx) = self.arg_max[[b, ch, i, j]];
+                    dx[[b, ch, y0 + dy, x0 + dxx]] += d;
+                }
+                PoolMode::Average => {
+                    dx.slice_mut(s![b, ch, y0..y0 + self.pool_h, x0..x0 + self.pool_w])
+                        .mapv_inplace(|v| v + d / area);
+                }
+            }
+        }
+        dx.slice(s![.., .., p..p + h, p..p + w]).to_owned()
+    }
+
+    fn padded(&self, x: &Array4<f64>, fill: f64) -> Array4<f64> {
+        if self.pad == 0 {
+            return x.clone();
+        }
+        let (n, c, h, w) = x.dim();
+        let p = self.pad;
+        let mut out = Array4::from_elem((n, c, h + 2 * p, w + 2 * p), fill);
+        out.slice_mut(s![.., .., p..p + h, p..p + w]).assign(x);
+        out
+    }
+}
+
+impl Layer for Pooling {
+    fn forward(&mut self, x: &Array2<f64>) -> Array2<f64> {
+        flatten(&self.forward_tensor(&Tensor::Mat(x.clone())).into_img())
+    }
+
+    fn backward(&mut self, dout: &Array2<f64>) -> Array2<f64> {
+        self.backward_tensor(&Tensor::Mat(dout.clone())).into_mat()
+    }
+
+    fn forward_tensor(&mut self, x: &Tensor) -> Tensor {
+        self.mat_input = matches!(x, Tensor::Mat(_));
+        let x = image_input(x, self.input_shape, "Pooling");
+        Tensor::Img(Pooling::forward(self, &x))
+    }
+
+    fn backward_tensor(&mut self, dout: &Tensor) -> Tensor {
+        let dout = match dout {
+            Tensor::Img(d) => d.clone(),
+            Tensor::Mat(d) => {
+                let (_, c, oh, ow) = self.arg_max.dim();
+                to_images(d, (c, oh, ow))
+            }
+        };
+        let dx = Pooling::backward(self, &dout);
+        if self.mat_input {
+            Tensor::Mat(flatten(&dx))
+        } else {
+            Tensor::Img(dx)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    fn sample_input() -> Array4<f64> {
+        // 1 张图、1 个通道、4×4：值为 0..16
+        Array4::from_shape_fn((1, 1, 4, 4), |(_, _, i, j)| (i * 4 + j) as f64)
+    }
+
+    #[test]
+    fn test_max_pool_forward_backward() {
+        let mut pool = Pooling::new(2, 2, 2);
+        let y = pool.forward(&sample_input());
+        assert_eq!(y.dim(), (1, 1, 2, 2));
+        assert_eq!(
+            y.iter().copied().collect::<Vec<_>>(),
+            vec![5.0, 7.0, 13.0, 15.0]
+        );
+
+        let dx = pool.backward(&Array4::ones((1, 1, 2, 2)));
+        assert_eq!(dx.sum(), 4.0);
+        assert_eq!(dx[[0, 0, 1, 1]], 1.0);
+        assert_eq!(dx[[0, 0, 0, 0]], 0.0);
+    }
+
+    #[test]
+    fn test_average_pool_forward() {
+        let mut pool = Pooling::new(2, 2, 2).with_mode(PoolMode::Average);
+        let y = pool.forward(&sample_input());
+        // 左上窗口 (0 + 1 + 4 + 5) / 4 = 2.5，其余窗口依次平移
+        assert_eq!(
+            y.iter().copied().collect::<Vec<_>>(),
+            vec![2.5, 4.5, 10.5, 12.5]
+        );
+    }
+
+    #[test]
+    fn test_average_pool_backward_sums_to_dout() {
+        let mut pool = Pooling::new(2, 2, 2).with_mode(PoolMode::Average);
+        pool.forward(&sample_input());
+        let dout = Array4::from_shape_vec((1, 1, 2, 2), vec![1.0, -2.0, 0.5, 4.0]).unwrap();
+        let dx = pool.backward(&dout);
+
+        for ((_, _, i, j), &d) in dout.indexed_iter() {
+            let window = dx.slice(s![0, 0, i * 2..i * 2 + 2, j * 2..j * 2 + 2]);
+            assert!((window.sum() - d).abs() < 1e-12);
+            assert!(window.iter().all(|&v| (v - d / 4.0).abs() < 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_padded_pooling() {
+        // 1×1×2×2 输入，四周各填充 1，3×3 窗口、步幅 1 -> 输出 2×2
+        let x = Array4::from_shape_vec((1, 1, 2, 2), vec![-1.0, -2.0, -3.0, -4.0]).unwrap();
+
+        let mut max_pool = Pooling::new(3, 3, 1).with_pad(1);
+        let y = max_pool.forward(&x);
+        assert_eq!(y.dim(), (1, 1, 2, 2));
+        // 输入全为负数，填充值 -∞ 不会被选中
+        assert!(y.iter().all(|&v| v == -1.0));
+        let dx = max_pool.backward(&Array4::ones((1, 1, 2, 2)));
+        assert_eq!(dx.dim(), x.dim());
+        assert_eq!(dx[[0, 0, 0, 0]], 4.0);
+        assert_eq!(dx.sum(), 4.0);
+
+        let mut avg_pool = Pooling::new(3, 3, 1)
+            .with_pad(1)
+            .with_mode(PoolMode::Average);
+        let y = avg_pool.forward(&x);
+        // 每个窗口都覆盖全部 4 个输入，外加 5 个填充的 0
+        assert!(y.iter().all(|&v| (v + 10.0 / 9.0).abs() < 1e-12));
+        let dx = avg_pool.backward(&Array4::ones((1, 1, 2, 2)));
+        assert!(dx.iter().all(|&v| (v - 4.0 / 9.0).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_layer_accepts_flattened_images() {
+        let mut pool = Pooling::new(2, 2, 2).with_input_shape((1, 4, 4));
+        let x = sample_input().into_shape_with_order((1, 16)).unwrap();
+        let y = Layer::forward(&mut pool, &x);
+        assert_eq!(y, array![[5.0, 7.0, 13.0, 15.0]]);
+
+        let dx = Layer::backward(&mut pool, &Array2::ones((1, 4)));
+        assert_eq!(dx.dim(), (1, 16));
+        assert_eq!(dx[[0, 5]], 1.0);
+        assert_eq!(dx.sum(), 4.0);
+    }
+}
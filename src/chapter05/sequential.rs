@@ -0,0 +1,276 @@
+// src/chapter05/sequential.rs
+use super::layers::Layer;
+use super::parameter::Parameter;
+use super::tensor::Tensor;
+use ndarray::Array2;
+
+/// 按顺序堆叠的层，前向时依次调用，反向时逆序调用。
+///
+/// 层与层之间传递的是 `Tensor`，所以可以混合卷积、池化和全连接层，
+/// 例如 Conv -> ReLU -> Pool -> Flatten -> Affine
+#[derive(Default)]
+pub struct Sequential {
+    layers: Vec<Box<dyn Layer>>,
+}
+
+impl Sequential {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 在末尾追加一层
+    pub fn with_layer(mut self, layer: impl Layer + 'static) -> Self {
+        self.layers.push(Box::new(layer));
+        self
+    }
+
+    /// 二维输入、二维输出的前向传播（最后一层的输出必须是矩阵）
+    pub fn forward(&mut self, x: &Array2<f64>) -> Array2<f64> {
+        self.forward_tensor(&Tensor::Mat(x.clone())).into_mat()
+    }
+
+    /// 返回对二维输入的梯度
+    pub fn backward(&mut self, dout: &Array2<f64>) -> Array2<f64> {
+        self.backward_tensor(&Tensor::Mat(dout.clone())).into_mat()
+    }
+
+    pub fn forward_tensor(&mut self, x: &Tensor) -> Tensor {
+        self.layers
+            .iter_mut()
+            .fold(x.clone(), |out, layer| layer.forward_tensor(&out))
+    }
+
+    pub fn backward_tensor(&mut self, dout: &Tensor) -> Tensor {
+        self.layers
+            .iter_mut()
+            .rev()
+            .fold(dout.clone(), |d, layer| layer.backward_tensor(&d))
+    }
+
+    /// 所有层的可学习参数（按前向顺序，只读），例如用于统计参数量或记录日志
+    pub fn params(&self) -> Vec<&Parameter> {
+        self.layers
+            .iter()
+            .flat_map(|layer| layer.params())
+            .collect()
+    }
+
+    /// 所有层的可学习参数（按前向顺序），供优化器更新
+    pub fn params_mut(&mut self) -> Vec<&mut Parameter> {
+        self.layers
+            .iter_mut()
+            .flat_map(|layer| layer.params_mut())
+            .collect()
+    }
+
+    /// 清零所有层的参数梯度
+    pub fn zero_grad(&mut self) {
+        for layer in &mut self.layers {
+            layer.zero_grad();
+        }
+    }
+
+    /// 反向传播之后，每个带权重的层的 dW 的 L2 范数（按前向顺序）。
+    /// 用来观察梯度随深度消失或爆炸的情况
+    pub fn grad_norms(&self) -> Vec<f64> {
+        self.layers
+            .iter()
+            .filter_map(|layer| layer.grads().first().map(|dw| l2_norm(dw)))
+            .collect()
+    }
+}
+
+fn l2_norm(a: &Array2<f64>) -> f64 {
+    a.iter().map(|v| v * v).sum::<f64>().sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chapter05::layers::{Affine, ReluLayer};
+    use ndarray::array;
+
+    #[test]
+    fn test_grad_norms_three_affine_stack() {
+        let w1 = array![[0.5, -1.0], [2.0, 0.3]];
+        let w2 = array![[1.0, 0.0, -1.0], [0.5, 2.0, 1.0]];
+        let w3 = array![[1.0], [-2.0], [0.5]];
+        let b1 = array![[0.1, 0.2]];
+        let b2 = array![[0.0, 0.0, 0.0]];
+        let b3 = array![[0.3]];
+
+        let mut net = Sequential::new()
+            .with_layer(Affine::new(w1.clone(), b1.clone()))
+            .with_layer(ReluLayer::new())
+            .with_layer(Affine::new(w2.clone(), b2.clone()))
+            .with_layer(Affine::new(w3.clone(), b3.clone()));
+
+        let x = array![[1.0, 2.0], [-1.0, 0.5]];
+        net.forward(&x);
+        let dout = array![[1.0], [1.0]];
+        net.backward(&dout);
+
+        // 手工计算同样的前向 / 反向传播
+        let a1 = x.dot(&w1) + &b1;
+        let h1 = a1.mapv(|v| v.max(0.0));
+        let h2 = h1.dot(&w2) + &b2;
+        let dw3 = h2.t().dot(&dout);
+        let dh2 = dout.dot(&w3.t());
+        let dw2 = h1.t().dot(&dh2);
+        let mut da1 = dh2.dot(&w2.t());
+        da1.zip_mut_with(&a1, |d, &a| {
+            if a <= 0.0 {
+                *d = 0.0;
+            }
+        });
+        let dw1 = x.t().dot(&da1);
+
+        let norms = net.grad_norms();
+        assert_eq!(norms.len(), 3);
+        for (norm, dw) in norms.iter().zip([dw1, dw2, dw3].iter()) {
+            assert!((norm - l2_norm(dw)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_zero_grad_clears_all_layers() {
+        let mut net = Sequential::new()
+            .with_layer(Affine::new(
+                array![[0.5, -1.0], [2.0, 0.3]],
+                array![[0.1, 0.2]],
+            ))
+            .with_layer(ReluLayer::new())
+            .with_layer(Affine::without_bias(array![[1.0], [-2.0]]));
+
+        net.forward(&array![[1.0, 2.0], [-1.0, 0.5]]);
+        net.backward(&array![[1.0], [1.0]]);
+        assert!(net.grad_norms().iter().all(|&n| n > 0.0));
+
+        net.zero_grad();
+        for param in net.params_mut() {
+            assert!(param.grad.iter().all(|&g| g == 0.0));
+        }
+    }
+
+    #[test]
+    fn test_params_in_forward_order() {
+        let w1 = array![[0.5, -1.0], [2.0, 0.3]];
+        let b1 = array![[0.1, 0.2]];
+        let w2 = array![[1.0], [-2.0]];
+        let net = Sequential::new()
+            .with_layer(Affine::new(w1.clone(), b1.clone()))
+            .with_layer(ReluLayer::new())
+            .with_layer(Affine::without_bias(w2.clone()));
+
+        let values: Vec<&Array2<f64>> = net.params().iter().map(|p| &p.value).collect();
+        assert_eq!(values, vec![&w1, &b1, &w2]);
+    }
+
+    #[test]
+    fn test_conv_pool_affine_pipeline() {
+        use crate::chapter02::grad::numerical_gradient;
+        use crate::chapter05::convolution::Convolution;
+        use crate::chapter05::layers::Flatten;
+        use crate::chapter05::pooling::Pooling;
+        use ndarray::{Array, Array4};
+
+        let filters = Array::from_shape_fn((2, 1, 3, 3), |(f, _, i, j)| {
+            ((f * 9 + i * 3 + j) as f64 * 0.53).sin()
+        });
+        let w = Array::from_shape_fn((8, 3), |(i, j)| ((i * 3 + j) as f64 * 0.31).cos());
+        let build = |filters: &Array4<f64>| {
+            Sequential::new()
+                .with_layer(Convolution::new(filters.clone(), array![[0.1, -0.1]], 1, 1))
+                .with_layer(ReluLayer::new())
+                .with_layer(Pooling::new(2, 2, 2))
+                .with_layer(Flatten::new())
+                .with_layer(Affine::new(w.clone(), array![[0.0, 0.5, -0.5]]))
+        };
+        let x = Tensor::from(Array::from_shape_fn((2, 1, 4, 4), |(n, _, i, j)| {
+            ((n * 16 + i * 4 + j) as f64 * 0.77).sin()
+        }));
+
+        let mut net = build(&filters);
+        let y = net.forward_tensor(&x).into_mat();
+        assert_eq!(y.dim(), (2, 3));
+
+        // 对输入的梯度是四维的；卷积核的梯度与数值微分一致
+        let dout = y.mapv(|v| v.cos());
+        let dx = net.backward_tensor(&Tensor::Mat(dout.clone()));
+        assert_eq!(dx.shape(), &[2, 1, 4, 4]);
+
+        let dw = net.params()[0].grad.clone();
+        let w_col = net.params()[0].value.clone();
+        let numeric = numerical_gradient(
+            |w_col| {
+                let filters = w_col
+                    .t()
+                    .as_standard_layout()
+                    .into_owned()
+                    .into_shape_with_order((2, 1, 3, 3))
+                    .unwrap();
+                (build(&filters).forward_tensor(&x).into_mat() * &dout).sum()
+            },
+            &w_col,
+        );
+        for (a, n) in dw.iter().zip(numeric.iter()) {
+            assert!((a - n).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_conv_first_sequential_on_flattened_images() {
+        use crate::chapter05::convolution::Convolution;
+        use crate::chapter05::layers::Flatten;
+        use crate::chapter05::pooling::Pooling;
+        use crate::chapter05::tensor::flatten;
+        use ndarray::Array;
+
+        let filters = Array::from_shape_fn((2, 1, 3, 3), |(f, _, i, j)| {
+            ((f * 9 + i * 3 + j) as f64 * 0.53).sin()
+        });
+        let w = Array::from_shape_fn((8, 3), |(i, j)| ((i * 3 + j) as f64 * 0.31).cos());
+        let build = |input_shape: Option<(usize, usize, usize)>| {
+            let conv = Convolution::new(filters.clone(), array![[0.1, -0.1]], 1, 1);
+            let conv = match input_shape {
+                Some(shape) => conv.with_input_shape(shape),
+                None => conv,
+            };
+            Sequential::new()
+                .with_layer(conv)
+                .with_layer(ReluLayer::new())
+                .with_layer(Pooling::new(2, 2, 2))
+                .with_layer(Flatten::new())
+                .with_layer(Affine::new(w.clone(), array![[0.0, 0.5, -0.5]]))
+        };
+        let img = Array::from_shape_fn((2, 1, 4, 4), |(n, _, i, j)| {
+            ((n * 16 + i * 4 + j) as f64 * 0.77).sin()
+        });
+
+        // 每行一张展平的图像，结果和直接输入四维图像一致
+        let mut net = build(Some((1, 4, 4)));
+        let y = net.forward(&flatten(&img));
+        let y_img = build(None)
+            .forward_tensor(&Tensor::from(img.clone()))
+            .into_mat();
+        assert_eq!(y, y_img);
+
+        let dx = net.backward(&y.mapv(|v| v.cos()));
+        assert_eq!(dx.dim(), (2, 16));
+    }
+
+    #[test]
+    #[should_panic(expected = "with_input_shape")]
+    fn test_conv_rejects_flat_input_without_shape() {
+        use crate::chapter05::convolution::Convolution;
+        use ndarray::Array;
+
+        let mut net = Sequential::new().with_layer(Convolution::new(
+            Array::zeros((1, 1, 3, 3)),
+            array![[0.0]],
+            1,
+            0,
+        ));
+        net.forward(&Array2::zeros((2, 16)));
+    }
+}
@@ -0,0 +1,275 @@
+// src/chapter05/simple_conv_net.rs
+use super::convolution::{Convolution, conv_output_size};
+use super::layers::{Affine, Flatten, Layer, ReluLayer, SoftmaxWithLoss};
+use super::pooling::Pooling;
+use super::tensor::{Tensor, map_in_batches, to_images};
+use crate::chapter02::activation::softmax;
+use crate::chapter02::init::{Initializer, NormalInit};
+use crate::chapter02::loss::cross_entropy_from_logits;
+use crate::chapter02::metrics::{accuracy, argmax_rows};
+use crate::chapter02::model::{Model, Trainable};
+use ndarray::Array2;
+
+/// 推理时每次送入网络的样本数，限制 im2col 展开后的内存占用
+const EVAL_BATCH: usize = 100;
+
+/// SimpleConvNet 的超参数，默认值与书中第 7 章一致（MNIST 的 1×28×28 输入）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConvNetConfig {
+    /// (C, H, W)
+    pub input_dim: (usize, usize, usize),
+    pub filter_num: usize,
+    pub filter_size: usize,
+    pub pad: usize,
+    pub stride: usize,
+    pub hidden_size: usize,
+    pub output_size: usize,
+}
+
+impl Default for ConvNetConfig {
+    fn default() -> Self {
+        Self {
+            input_dim: (1, 28, 28),
+            filter_num: 30,
+            filter_size: 5,
+            pad: 0,
+            stride: 1,
+            hidden_size: 100,
+            output_size: 10,
+        }
+    }
+}
+
+/// 简单的卷积网络：Conv -> ReLU -> Pool(2×2) -> Affine -> ReLU -> Affine -> Softmax。
+///
+/// 输入与其他模型一样是 (N, C * H * W) 的矩阵（例如 MNIST 的 784 列），
+/// 内部按 `config.input_dim` 还原成 (N, C, H, W)，因此可以直接交给 `Trainer` 训练
+pub struct SimpleConvNet {
+    pub config: ConvNetConfig,
+    pub conv: Convolution,
+    pub pool: Pooling,
+    pub affine1: Affine,
+    pub affine2: Affine,
+    relu1: ReluLayer,
+    flatten: Flatten,
+    relu2: ReluLayer,
+    last_layer: SoftmaxWithLoss,
+}
+
+impl SimpleConvNet {
+    /// 权重按 N(0, 0.01²) 初始化（与书中 weight_init_std=0.01 一致），偏置为 0
+    pub fn new(config: ConvNetConfig) -> Self {
+        Self::with_initializer(config, &NormalInit { std: 0.01 }, rand::random())
+    }
+
+    /// 用指定的初始化器和随机种子创建网络（偏置始终初始化为 0）
+    pub fn with_initializer(config: ConvNetConfig, init: &dyn Initializer, seed: u64) -> Self {
+        let (c, h, w) = config.input_dim;
+        let (fs, fnum) = (config.filter_size, config.filter_num);
+        let (oh, ow) = conv_output_size(h, w, fs, fs, config.stride, config.pad);
+        let pool_output_size = fnum * (oh / 2) * (ow / 2);
+
+        Self {
+            config,
+            conv: Convolution::from_col_weights(
+                init.init((c * fs * fs, fnum), seed),
+                Array2::zeros((1, fnum)),
+                (fnum, c, fs, fs),
+                config.stride,
+                config.pad,
+            ),
+            pool: Pooling::new(2, 2, 2),
+            affine1: Affine::new(
+                init.init((pool_output_size, config.hidden_size), seed.wrapping_add(1)),
+                Array2::zeros((1, config.hidden_size)),
+            ),
+            affine2: Affine::new(
+                init.init(
+                    (config.hidden_size, config.output_size),
+                    seed.wrapping_add(2),
+                ),
+                Array2::zeros((1, config.output_size)),
+            ),
+            relu1: ReluLayer::new(),
+            flatten: Flatten::new(),
+            relu2: ReluLayer::new(),
+            last_layer: SoftmaxWithLoss::new(),
+        }
+    }
+
+    /// 输出层 softmax 之前的得分，每次处理 `EVAL_BATCH` 个样本
+    pub fn predict_logits(&self, x: &Array2<f64>) -> Array2<f64> {
+        map_in_batches(x, EVAL_BATCH, self.config.output_size, |x| {
+            self.logits_batch(x)
+        })
+    }
+
+    fn logits_batch(&self, x: &Array2<f64>) -> Array2<f64> {
+        let conv = self
+            .conv
+            .apply(&to_images(x, self.config.input_dim))
+            .mapv(|v| v.max(0.0));
+        let pooled = self.pool.apply(&conv);
+        let flat = self.flatten.apply(&pooled);
+        let h = (flat.dot(&self.affine1.w.value) + &self.affine1.b.value).mapv(|v| v.max(0.0));
+        h.dot(&self.affine2.w.value) + &self.affine2.b.value
+    }
+
+    /// 各类别的概率
+    pub fn predict(&self, x: &Array2<f64>) -> Array2<f64> {
+        softmax(&self.predict_logits(x))
+    }
+
+    /// 交叉熵损失
+    pub fn loss(&self, x: &Array2<f64>, t: &Array2<f64>) -> f64 {
+        cross_entropy_from_logits(&self.predict_logits(x), t)
+    }
+
+    /// 识别精度，t 为 one-hot 标签
+    pub fn accuracy(&self, x: &Array2<f64>, t: &Array2<f64>) -> f64 {
+        accuracy(&argmax_rows(&self.predict_logits(x)), &argmax_rows(t))
+    }
+
+    /// 用误差反向传播求梯度，顺序为 [卷积 W, 卷积 b, W1, b1, W2, b2]
+    pub fn gradient(&mut self, x: &Array2<f64>, t: &Array2<f64>) -> Vec<Array2<f64>> {
+        let x = Tensor::Img(to_images(x, self.config.input_dim));
+        let x = self.conv.forward_tensor(&x);
+        let x = self.relu1.forward_tensor(&x);
+        let x = self.pool.forward_tensor(&x);
+        let h = self.flatten.forward_tensor(&x).into_mat();
+        let h = self.affine1.forward(&h);
+        let h = self.relu2.forward(&h);
+        let scores = self.affine2.forward(&h);
+        self.last_layer.forward(&scores, t);
+
+        let dout = self.last_layer.backward();
+        let dout = self.affine2.backward(&dout);
+        let dout = self.relu2.backward(&dout);
+        let dout = Tensor::Mat(self.affine1.backward(&dout));
+        let dout = self.flatten.backward_tensor(&dout);
+        let dout = self.pool.backward_tensor(&dout);
+        let dout = self.relu1.backward_tensor(&dout);
+        self.conv.backward_tensor(&dout);
+
+        vec![
+            self.conv.w.grad.clone(),
+            self.conv.b.grad.clone(),
+            self.affine1.w.grad.clone(),
+            self.affine1.b.grad.clone(),
+            self.affine2.w.grad.clone(),
+            self.affine2.b.grad.clone(),
+        ]
+    }
+}
+
+impl Model for SimpleConvNet {
+    fn predict(&self, x: &Array2<f64>) -> Array2<f64> {
+        SimpleConvNet::predict(self, x)
+    }
+}
+
+impl Trainable for SimpleConvNet {
+    fn loss(&self, x: &Array2<f64>, t: &Array2<f64>) -> f64 {
+        SimpleConvNet::loss(self, x, t)
+    }
+
+    fn gradient(&mut self, x: &Array2<f64>, t: &Array2<f64>) -> Vec<Array2<f64>> {
+        SimpleConvNet::gradient(self, x, t)
+    }
+
+    fn params(&self) -> Vec<&Array2<f64>> {
+        vec![
+            &self.conv.w.value,
+            &self.conv.b.value,
+            &self.affine1.w.value,
+            &self.affine1.b.value,
+            &self.affine2.w.value,
+            &self.affine2.b.value,
+        ]
+    }
+
+    fn params_mut(&mut self) -> Vec<&mut Array2<f64>> {
+        vec![
+            &mut self.conv.w.value,
+            &mut self.conv.b.value,
+            &mut self.affine1.w.value,
+            &mut self.affine1.b.value,
+            &mut self.affine2.w.value,
+            &mut self.affine2.b.value,
+        ]
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        ["conv_w", "conv_b", "w1", "b1", "w2", "b2"]
+            .map(String::from)
+            .to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chapter02::grad::check_gradients;
+    use crate::chapter02::init::HeInit;
+    use crate::chapter02::metrics::to_one_hot;
+    use ndarray::Array1;
+
+    fn tiny_config() -> ConvNetConfig {
+        ConvNetConfig {
+            input_dim: (2, 6, 6),
+            filter_num: 3,
+            filter_size: 3,
+            pad: 1,
+            stride: 1,
+            hidden_size: 5,
+            output_size: 3,
+        }
+    }
+
+    fn tiny_data(n: usize) -> (Array2<f64>, Array2<f64>) {
+        let x = Array2::from_shape_fn((n, 72), |(i, j)| ((i * 72 + j) as f64 * 0.173).sin());
+        let labels = Array1::from_shape_fn(n, |i| i % 3);
+        (x, to_one_hot(&labels, 3))
+    }
+
+    #[test]
+    fn test_gradient_check() {
+        let mut net = SimpleConvNet::with_initializer(tiny_config(), &HeInit, 3);
+        let (x, t) = tiny_data(4);
+        assert_eq!(check_gradients(&mut net, &x, &t, 1e-5), Ok(()));
+    }
+
+    #[test]
+    fn test_predict_in_chunks() {
+        let net = SimpleConvNet::with_initializer(tiny_config(), &HeInit, 3);
+        let (x, t) = tiny_data(EVAL_BATCH + 7);
+
+        let y = net.predict(&x);
+        assert_eq!(y.dim(), (EVAL_BATCH + 7, 3));
+        for row in y.rows() {
+            assert!((row.sum() - 1.0).abs() < 1e-12);
+        }
+        // 分块推理的结果与单独处理最后一个样本一致
+        let last = x.slice(ndarray::s![EVAL_BATCH + 6.., ..]).to_owned();
+        let single = net.predict_logits(&last);
+        let batched = net.predict_logits(&x);
+        assert_eq!(single.row(0), batched.row(EVAL_BATCH + 6));
+
+        let acc = net.accuracy(&x, &t);
+        assert!((0.0..=1.0).contains(&acc));
+    }
+
+    #[test]
+    fn test_training_reduces_loss() {
+        let mut net = SimpleConvNet::with_initializer(tiny_config(), &HeInit, 3);
+        let (x, t) = tiny_data(12);
+        let before = net.loss(&x, &t);
+        for _ in 0..20 {
+            let grads = net.gradient(&x, &t);
+            for (param, grad) in net.params_mut().into_iter().zip(&grads) {
+                param.scaled_add(-0.1, grad);
+            }
+        }
+        assert!(net.loss(&x, &t) < before);
+    }
+}
@@ -0,0 +1,177 @@
+// src/chapter05/tensor.rs
+use ndarray::{Array2, Array4, Axis, concatenate};
+
+/// 在层之间传递的数据：全连接部分是 (N, D) 的矩阵，卷积部分是 (N, C, H, W) 的图像批次。
+///
+/// `Layer::forward_tensor` / `backward_tensor` 以它为输入输出，
+/// 这样卷积层、池化层和全连接层可以放进同一个 `Sequential`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Tensor {
+    Mat(Array2<f64>),
+    Img(Array4<f64>),
+}
+
+impl Tensor {
+    pub fn shape(&self) -> &[usize] {
+        match self {
+            Tensor::Mat(m) => m.shape(),
+            Tensor::Img(x) => x.shape(),
+        }
+    }
+
+    /// 取出二维矩阵；四维输入说明前面少了一个 `Flatten` 层
+    pub fn as_mat(&self) -> &Array2<f64> {
+        match self {
+            Tensor::Mat(m) => m,
+            Tensor::Img(x) => panic!(
+                "expected a 2-D tensor, got shape {:?} (insert a Flatten layer?)",
+                x.shape()
+            ),
+        }
+    }
+
+    pub fn as_img(&self) -> &Array4<f64> {
+        match self {
+            Tensor::Img(x) => x,
+            Tensor::Mat(m) => panic!(
+                "expected a 4-D (N, C, H, W) tensor, got shape {:?}",
+                m.shape()
+            ),
+        }
+    }
+
+    pub fn into_mat(self) -> Array2<f64> {
+        match self {
+            Tensor::Mat(m) => m,
+            other => other.as_mat().clone(),
+        }
+    }
+
+    pub fn into_img(self) -> Array4<f64> {
+        match self {
+            Tensor::Img(x) => x,
+            other => other.as_img().clone(),
+        }
+    }
+
+    /// 把数据按第一维展平成 (N, 其余维度之积) 交给 `f`，再还原成原来的形状。
+    /// 逐元素的层（ReLU、Sigmoid）和按特征标准化的 BatchNorm 借此同时支持二维和四维输入
+    pub fn map_as_mat(&self, f: impl FnOnce(&Array2<f64>) -> Array2<f64>) -> Tensor {
+        match self {
+            Tensor::Mat(m) => Tensor::Mat(f(m)),
+            Tensor::Img(x) => {
+                let shape = x.dim();
+                let out = f(&flatten(x));
+                Tensor::Img(
+                    out.into_shape_with_order(shape)
+                        .expect("map_as_mat: output must keep the input shape"),
+                )
+            }
+        }
+    }
+}
+
+impl From<Array2<f64>> for Tensor {
+    fn from(m: Array2<f64>) -> Self {
+        Tensor::Mat(m)
+    }
+}
+
+impl From<Array4<f64>> for Tensor {
+    fn from(x: Array4<f64>) -> Self {
+        Tensor::Img(x)
+    }
+}
+
+/// (N, C, H, W) -> (N, C * H * W)
+pub(crate) fn flatten(x: &Array4<f64>) -> Array2<f64> {
+    let (n, c, h, w) = x.dim();
+    x.as_standard_layout()
+        .into_owned()
+        .into_shape_with_order((n, c * h * w))
+        .expect("standard layout array is contiguous")
+}
+
+/// (N, C * H * W) -> (N, C, H, W)，卷积网络用它把 `Trainer` 传来的矩阵还原成图像批次
+pub(crate) fn to_images(x: &Array2<f64>, (c, h, w): (usize, usize, usize)) -> Array4<f64> {
+    assert_eq!(
+        x.ncols(),
+        c * h * w,
+        "expected {} input features for ({}, {}, {}) images, got {}",
+        c * h * w,
+        c,
+        h,
+        w,
+        x.ncols()
+    );
+    x.as_standard_layout()
+        .into_owned()
+        .into_shape_with_order((x.nrows(), c, h, w))
+        .expect("row count times feature count matches")
+}
+
+/// 卷积层、池化层的输入：四维的直接使用；二维的 (N, C * H * W) 按层上配置的
+/// `input_shape` 还原成图像，没有配置时报错
+pub(crate) fn image_input(
+    x: &Tensor,
+    input_shape: Option<(usize, usize, usize)>,
+    layer: &str,
+) -> Array4<f64> {
+    match (x, input_shape) {
+        (Tensor::Img(x), _) => x.clone(),
+        (Tensor::Mat(m), Some(shape)) => to_images(m, shape),
+        (Tensor::Mat(m), None) => panic!(
+            "{} got a 2-D input of shape {:?}; call with_input_shape((C, H, W)) to accept flattened images",
+            layer,
+            m.shape()
+        ),
+    }
+}
+
+/// 每次取 `batch` 行交给 `f`，再把结果按行拼接，限制 im2col 展开后的内存占用
+pub(crate) fn map_in_batches(
+    x: &Array2<f64>,
+    batch: usize,
+    out_cols: usize,
+    f: impl Fn(&Array2<f64>) -> Array2<f64>,
+) -> Array2<f64> {
+    if x.nrows() == 0 {
+        return Array2::zeros((0, out_cols));
+    }
+    let outputs: Vec<Array2<f64>> = x
+        .axis_chunks_iter(Axis(0), batch)
+        .map(|chunk| f(&chunk.to_owned()))
+        .collect();
+    let views: Vec<_> = outputs.iter().map(|y| y.view()).collect();
+    concatenate(Axis(0), &views).expect("chunk outputs share column count")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::Array;
+
+    #[test]
+    fn test_map_as_mat_keeps_shape() {
+        let x = Array::from_shape_fn((2, 3, 2, 2), |(n, c, i, j)| {
+            (n * 12 + c * 4 + i * 2 + j) as f64 - 10.0
+        });
+        let t = Tensor::from(x.clone());
+        let seen = std::cell::Cell::new((0, 0));
+        let y = t.map_as_mat(|m| {
+            seen.set(m.dim());
+            m.mapv(|v| v.max(0.0))
+        });
+
+        // 每个样本展平成一行，结果还原为原来的四维形状
+        assert_eq!(seen.get(), (2, 12));
+        assert_eq!(y, Tensor::Img(x.mapv(|v| v.max(0.0))));
+        assert_eq!(y.shape(), &[2, 3, 2, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "insert a Flatten layer")]
+    fn test_as_mat_rejects_images() {
+        Tensor::from(Array4::<f64>::zeros((1, 1, 2, 2))).as_mat();
+    }
+}
@@ -0,0 +1,383 @@
+// src/chapter05/two_layer_net.rs
+use super::activation::{Activation, Relu};
+use super::layers::{Affine, Layer};
+use crate::chapter02::activation::softmax;
+use crate::chapter02::grad::numerical_gradient;
+use crate::chapter02::init::{Initializer, NormalInit};
+use crate::chapter02::loss::{cross_entropy_from_logits, softmax_cross_entropy_backward};
+use crate::chapter02::metrics::{accuracy, argmax_rows};
+use crate::chapter02::model::{Model, Trainable};
+use ndarray::Array2;
+
+/// 用误差反向传播求梯度的两层网络：Affine -> 隐藏层激活（默认 ReLU）-> Affine -> Softmax
+pub struct TwoLayerNet {
+    pub affine1: Affine,
+    hidden_activation: Box<dyn Activation>,
+    // 反向传播时求激活函数导数用的隐藏层输入
+    hidden_in: Array2<f64>,
+    pub affine2: Affine,
+    /// L2 正则化（权值衰减）的系数 λ：损失加上 0.5·λ·(‖W1‖² + ‖W2‖²)，偏置不参与
+    pub weight_decay_lambda: f64,
+}
+
+/// TwoLayerNet 各参数的梯度，形状与对应参数一致
+#[derive(Clone, Debug)]
+pub struct TwoLayerNetGrads {
+    pub w1: Array2<f64>,
+    pub b1: Array2<f64>,
+    pub w2: Array2<f64>,
+    pub b2: Array2<f64>,
+}
+
+impl TwoLayerNet {
+    /// 权重按 N(0, 0.01²) 初始化（与书中 weight_init_std=0.01 一致），偏置为 0
+    pub fn new(input_size: usize, hidden_size: usize, output_size: usize) -> Self {
+        Self::with_initializer(
+            input_size,
+            hidden_size,
+            output_size,
+            &NormalInit { std: 0.01 },
+            rand::random(),
+        )
+    }
+
+    /// 用指定的初始化器和随机种子创建网络（偏置始终初始化为 0），隐藏层使用 ReLU
+    pub fn with_initializer(
+        input_size: usize,
+        hidden_size: usize,
+        output_size: usize,
+        init: &dyn Initializer,
+        seed: u64,
+    ) -> Self {
+        Self::with_activation(input_size, hidden_size, output_size, init, seed, Relu)
+    }
+
+    /// 同 `with_initializer`，但隐藏层使用指定的激活函数（如书中的 `Sigmoid`），
+    /// 反向传播时调用该激活函数自己的导数
+    pub fn with_activation(
+        input_size: usize,
+        hidden_size: usize,
+        output_size: usize,
+        init: &dyn Initializer,
+        seed: u64,
+        hidden_activation: impl Activation + 'static,
+    ) -> Self {
+        Self {
+            affine1: Affine::new(
+                init.init((input_size, hidden_size), seed),
+                Array2::zeros((1, hidden_size)),
+            ),
+            hidden_activation: Box::new(hidden_activation),
+            hidden_in: Array2::zeros((0, hidden_size)),
+            affine2: Affine::new(
+                init.init((hidden_size, output_size), seed.wrapping_add(1)),
+                Array2::zeros((1, output_size)),
+            ),
+            weight_decay_lambda: 0.0,
+        }
+    }
+
+    /// 设置权值衰减系数 λ（默认 0，即不做正则化）
+    pub fn with_weight_decay(mut self, lambda: f64) -> Self {
+        self.weight_decay_lambda = lambda;
+        self
+    }
+
+    /// 输出层 softmax 之前的得分
+    pub fn predict_logits(&self, x: &Array2<f64>) -> Array2<f64> {
+        self.logits_with(x, self.current_params())
+    }
+
+    fn current_params(&self) -> [&Array2<f64>; 4] {
+        [
+            &self.affine1.w.value,
+            &self.affine1.b.value,
+            &self.affine2.w.value,
+            &self.affine2.b.value,
+        ]
+    }
+
+    /// 用给定的 [W1, b1, W2, b2] 代替当前参数做前向传播，供数值微分扰动单个参数
+    fn logits_with(&self, x: &Array2<f64>, params: [&Array2<f64>; 4]) -> Array2<f64> {
+        let [w1, b1, w2, b2] = params;
+        let h = self.hidden_activation.forward(&(x.dot(w1) + b1));
+        h.dot(w2) + b2
+    }
+
+    /// 用给定的参数计算含权值衰减项的损失
+    fn loss_with(&self, x: &Array2<f64>, t: &Array2<f64>, params: [&Array2<f64>; 4]) -> f64 {
+        let [w1, _, w2, _] = params;
+        let squared = |w: &Array2<f64>| w.iter().map(|v| v * v).sum::<f64>();
+        cross_entropy_from_logits(&self.logits_with(x, params), t)
+            + 0.5 * self.weight_decay_lambda * (squared(w1) + squared(w2))
+    }
+
+    /// 各类别的概率
+    pub fn predict(&self, x: &Array2<f64>) -> Array2<f64> {
+        softmax(&self.predict_logits(x))
+    }
+
+    /// 交叉熵损失（直接由得分计算，数值稳定），加上权值衰减项
+    pub fn loss(&self, x: &Array2<f64>, t: &Array2<f64>) -> f64 {
+        self.loss_with(x, t, self.current_params())
+    }
+
+    /// 识别精度，t 为 one-hot 标签
+    pub fn accuracy(&self, x: &Array2<f64>, t: &Array2<f64>) -> f64 {
+        accuracy(&argmax_rows(&self.predict_logits(x)), &argmax_rows(t))
+    }
+
+    /// 用数值微分求梯度，速度远慢于 `gradient`，只用于验证反向传播的结果
+    pub fn numerical_gradient(&self, x: &Array2<f64>, t: &Array2<f64>) -> TwoLayerNetGrads {
+        let current = self.current_params();
+        let grad = |i: usize| {
+            numerical_gradient(
+                |p| {
+                    let mut params = current;
+                    params[i] = p;
+                    self.loss_with(x, t, params)
+                },
+                current[i],
+            )
+        };
+        TwoLayerNetGrads {
+            w1: grad(0),
+            b1: grad(1),
+            w2: grad(2),
+            b2: grad(3),
+        }
+    }
+
+    /// 用误差反向传播求损失对所有参数的梯度
+    pub fn gradient(&mut self, x: &Array2<f64>, t: &Array2<f64>) -> TwoLayerNetGrads {
+        self.hidden_in = self.affine1.forward(x);
+        let h = self.hidden_activation.forward(&self.hidden_in);
+        let scores = self.affine2.forward(&h);
+
+        let dout = softmax_cross_entropy_backward(&scores, t);
+        let dout = self.affine2.backward(&dout);
+        let dout = self.hidden_activation.backward(&self.hidden_in, &dout);
+        self.affine1.backward(&dout);
+
+        // 权值衰减项 0.5·λ·‖W‖² 对 W 的梯度为 λ·W
+        let lambda = self.weight_decay_lambda;
+        for affine in [&mut self.affine1, &mut self.affine2] {
+            affine.w.grad.scaled_add(lambda, &affine.w.value);
+        }
+
+        TwoLayerNetGrads {
+            w1: self.affine1.w.grad.clone(),
+            b1: self.affine1.b.grad.clone(),
+            w2: self.affine2.w.grad.clone(),
+            b2: self.affine2.b.grad.clone(),
+        }
+    }
+}
+
+impl Model for TwoLayerNet {
+    fn predict(&self, x: &Array2<f64>) -> Array2<f64> {
+        TwoLayerNet::predict(self, x)
+    }
+}
+
+impl Trainable for TwoLayerNet {
+    fn loss(&self, x: &Array2<f64>, t: &Array2<f64>) -> f64 {
+        TwoLayerNet::loss(self, x, t)
+    }
+
+    fn gradient(&mut self, x: &Array2<f64>, t: &Array2<f64>) -> Vec<Array2<f64>> {
+        let grads = TwoLayerNet::gradient(self, x, t);
+        vec![grads.w1, grads.b1, grads.w2, grads.b2]
+    }
+
+    fn params(&self) -> Vec<&Array2<f64>> {
+        vec![
+            &self.affine1.w.value,
+            &self.affine1.b.value,
+            &self.affine2.w.value,
+            &self.affine2.b.value,
+        ]
+    }
+
+    fn params_mut(&mut self) -> Vec<&mut Array2<f64>> {
+        vec![
+            &mut self.affine1.w.value,
+            &mut self.affine1.b.value,
+            &mut self.affine2.w.value,
+            &mut self.affine2.b.value,
+        ]
+    }
+
+    fn param_names(&self) -> Vec<String> {
+        ["w1", "b1", "w2", "b2"].map(String::from).to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chapter02::init::{HeInit, XavierInit};
+    use crate::chapter05::activation::Sigmoid;
+    use ndarray::array;
+
+    #[test]
+    fn test_gradient_matches_numerical() {
+        let mut net = TwoLayerNet::with_initializer(3, 5, 2, &HeInit, 3);
+        let x = array![[0.6, 0.9, -0.3], [0.1, -0.5, 0.8]];
+        let t = array![[0.0, 1.0], [1.0, 0.0]];
+
+        let grads = net.gradient(&x, &t);
+        let numeric_w1 = numerical_gradient(
+            |w| {
+                let mut probe = TwoLayerNet::with_initializer(3, 5, 2, &HeInit, 3);
+                probe.affine1.w.value = w.clone();
+                probe.loss(&x, &t)
+            },
+            &net.affine1.w.value,
+        );
+        let numeric_b2 = numerical_gradient(
+            |b| {
+                let mut probe = TwoLayerNet::with_initializer(3, 5, 2, &HeInit, 3);
+                probe.affine2.b.value = b.clone();
+                probe.loss(&x, &t)
+            },
+            &net.affine2.b.value,
+        );
+
+        for (a, n) in grads.w1.iter().zip(numeric_w1.iter()) {
+            assert!((a - n).abs() < 1e-6);
+        }
+        for (a, n) in grads.b2.iter().zip(numeric_b2.iter()) {
+            assert!((a - n).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_numerical_gradient_method_matches_backprop() {
+        let mut net = TwoLayerNet::with_initializer(3, 4, 2, &HeInit, 11);
+        let x = array![[0.6, 0.9, -0.3], [0.1, -0.5, 0.8], [-0.7, 0.2, 0.4]];
+        let t = array![[0.0, 1.0], [1.0, 0.0], [0.0, 1.0]];
+
+        let numeric = net.numerical_gradient(&x, &t);
+        let backprop = net.gradient(&x, &t);
+        for (n, b) in [
+            (&numeric.w1, &backprop.w1),
+            (&numeric.b1, &backprop.b1),
+            (&numeric.w2, &backprop.w2),
+            (&numeric.b2, &backprop.b2),
+        ] {
+            assert_eq!(n.shape(), b.shape());
+            for (a, b) in n.iter().zip(b.iter()) {
+                assert!((a - b).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_weight_decay() {
+        let x = array![[0.6, 0.9, -0.3], [0.1, -0.5, 0.8]];
+        let t = array![[0.0, 1.0], [1.0, 0.0]];
+        let lambda = 0.1;
+        let mut plain = TwoLayerNet::with_initializer(3, 4, 2, &HeInit, 2);
+        let mut decayed =
+            TwoLayerNet::with_initializer(3, 4, 2, &HeInit, 2).with_weight_decay(lambda);
+
+        let squared = |w: &Array2<f64>| w.iter().map(|v| v * v).sum::<f64>();
+        let penalty =
+            0.5 * lambda * (squared(&plain.affine1.w.value) + squared(&plain.affine2.w.value));
+        assert!((decayed.loss(&x, &t) - plain.loss(&x, &t) - penalty).abs() < 1e-12);
+
+        let g_plain = plain.gradient(&x, &t);
+        let g_decayed = decayed.gradient(&x, &t);
+        let expected_w1 = &g_plain.w1 + &(&plain.affine1.w.value * lambda);
+        for (a, e) in g_decayed.w1.iter().zip(expected_w1.iter()) {
+            assert!((a - e).abs() < 1e-12);
+        }
+        // 偏置不参与正则化
+        assert_eq!(g_decayed.b1, g_plain.b1);
+
+        let numeric = decayed.numerical_gradient(&x, &t);
+        for (a, n) in g_decayed.w2.iter().zip(numeric.w2.iter()) {
+            assert!((a - n).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_named_params_match_grads() {
+        let mut net = TwoLayerNet::with_initializer(3, 4, 2, &HeInit, 1);
+        let x = array![[0.6, 0.9, -0.3]];
+        let t = array![[0.0, 1.0]];
+
+        let grads = TwoLayerNet::gradient(&mut net, &x, &t);
+        let named = net.named_gradient(&x, &t);
+        assert_eq!(named["w1"], grads.w1);
+        assert_eq!(named.names().nth(3), Some("b2"));
+        assert_eq!(net.named_params()["b1"], &net.affine1.b.value);
+    }
+
+    #[test]
+    fn test_accuracy() {
+        let mut net = TwoLayerNet::with_initializer(2, 3, 2, &HeInit, 0);
+        // 手工设置参数，使网络输出恰好把 x[0] > x[1] 的样本判为第 0 类
+        net.affine1.w.value = array![[1.0, -1.0, 0.0], [-1.0, 1.0, 0.0]];
+        net.affine2.w.value = array![[1.0, 0.0], [0.0, 1.0], [0.0, 0.0]];
+
+        let x = array![[2.0, 1.0], [0.0, 3.0], [1.0, 0.5], [0.2, 0.1]];
+        let t = array![[1.0, 0.0], [0.0, 1.0], [1.0, 0.0], [0.0, 1.0]];
+        assert_eq!(net.accuracy(&x, &t), 0.75);
+    }
+
+    #[test]
+    fn test_predict_rows_sum_to_one() {
+        let net = TwoLayerNet::new(4, 3, 2);
+        let y = net.predict(&array![[1.0, 2.0, 3.0, 4.0]]);
+        assert_eq!(y.shape(), &[1, 2]);
+        assert!((y.sum() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_sigmoid_hidden_gradient_matches_numerical() {
+        let build = || TwoLayerNet::with_activation(3, 5, 2, &XavierInit, 7, Sigmoid);
+        let mut net = build();
+        let x = array![[0.6, 0.9, -0.3], [0.1, -0.5, 0.8]];
+        let t = array![[0.0, 1.0], [1.0, 0.0]];
+
+        let grads = net.gradient(&x, &t);
+        let numeric_w1 = numerical_gradient(
+            |w| {
+                let mut probe = build();
+                probe.affine1.w.value = w.clone();
+                probe.loss(&x, &t)
+            },
+            &net.affine1.w.value,
+        );
+        let numeric_b1 = numerical_gradient(
+            |b| {
+                let mut probe = build();
+                probe.affine1.b.value = b.clone();
+                probe.loss(&x, &t)
+            },
+            &net.affine1.b.value,
+        );
+
+        for (a, n) in grads.w1.iter().zip(numeric_w1.iter()) {
+            assert!((a - n).abs() < 1e-6);
+        }
+        for (a, n) in grads.b1.iter().zip(numeric_b1.iter()) {
+            assert!((a - n).abs() < 1e-6);
+        }
+
+        // 同样的权重，隐藏层换成 ReLU 后输出和梯度都应不同
+        let mut relu_net = TwoLayerNet::with_initializer(3, 5, 2, &XavierInit, 7);
+        assert_eq!(relu_net.affine1.w.value, net.affine1.w.value);
+        let relu_grads = relu_net.gradient(&x, &t);
+        assert!((relu_net.loss(&x, &t) - net.loss(&x, &t)).abs() > 1e-6);
+        assert!(
+            relu_grads
+                .w1
+                .iter()
+                .zip(grads.w1.iter())
+                .any(|(a, b)| (a - b).abs() > 1e-6)
+        );
+    }
+}
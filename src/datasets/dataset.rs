@@ -0,0 +1,107 @@
+use ndarray::{Array1, Array2};
+
+/// A labelled classification dataset that can be indexed sample by sample.
+pub trait Dataset {
+    fn len(&self) -> usize;
+
+    /// Feature vector and class label of sample `idx`
+    fn get(&self, idx: usize) -> (Array1<f64>, usize);
+
+    fn num_classes(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Iterates over any `Dataset` in mini-batches of `batch_size` samples.
+/// The last batch may be smaller when `len` is not a multiple of `batch_size`.
+pub struct DataLoader<'a, D: Dataset> {
+    dataset: &'a D,
+    batch_size: usize,
+}
+
+impl<'a, D: Dataset> DataLoader<'a, D> {
+    pub fn new(dataset: &'a D, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "batch_size must be positive");
+        Self {
+            dataset,
+            batch_size,
+        }
+    }
+
+    pub fn num_batches(&self) -> usize {
+        self.dataset.len().div_ceil(self.batch_size)
+    }
+
+    /// Batches in dataset order as `(features, labels)`
+    pub fn iter(&self) -> impl Iterator<Item = (Array2<f64>, Array1<usize>)> + '_ {
+        (0..self.num_batches()).map(move |b| {
+            let start = b * self.batch_size;
+            let end = (start + self.batch_size).min(self.dataset.len());
+            let indices: Vec<usize> = (start..end).collect();
+            self.batch(&indices)
+        })
+    }
+
+    /// Gather the samples at `indices` into a batch
+    pub fn batch(&self, indices: &[usize]) -> (Array2<f64>, Array1<usize>) {
+        let cols = if indices.is_empty() {
+            0
+        } else {
+            self.dataset.get(indices[0]).0.len()
+        };
+        let mut x = Array2::zeros((indices.len(), cols));
+        let mut labels = Array1::zeros(indices.len());
+
+        for (row, &idx) in indices.iter().enumerate() {
+            let (features, label) = self.dataset.get(idx);
+            x.row_mut(row).assign(&features);
+            labels[row] = label;
+        }
+
+        (x, labels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datasets::MnistDataset;
+    use crate::datasets::synthetic::SyntheticDataset;
+
+    fn collect_batches<D: Dataset>(
+        dataset: &D,
+        batch_size: usize,
+    ) -> Vec<(Array2<f64>, Array1<usize>)> {
+        DataLoader::new(dataset, batch_size).iter().collect()
+    }
+
+    #[test]
+    fn test_batching_is_identical_across_datasets() {
+        let images = Array2::from_shape_fn((5, 4), |(i, j)| (i * 4 + j) as f32);
+        let labels = Array1::from_vec(vec![0u8, 1, 2, 1, 0]);
+        let mnist = MnistDataset {
+            train_images: images.clone(),
+            train_labels: labels.clone(),
+            test_images: Array2::zeros((0, 4)),
+            test_labels: Array1::zeros(0),
+        };
+        let synthetic = SyntheticDataset::new(images.mapv(f64::from), labels.mapv(usize::from), 10);
+
+        let a = collect_batches(&mnist, 2);
+        let b = collect_batches(&synthetic, 2);
+
+        assert_eq!(a.len(), 3);
+        assert_eq!(a, b);
+        assert_eq!(a[2].0.nrows(), 1);
+        assert_eq!(a[0].1, Array1::from_vec(vec![0, 1]));
+    }
+
+    #[test]
+    fn test_num_batches() {
+        let synthetic = SyntheticDataset::blobs(7, &[(0.0, 0.0), (3.0, 3.0)], 0.5, 1);
+        assert_eq!(synthetic.len(), 14);
+        assert_eq!(DataLoader::new(&synthetic, 4).num_batches(), 4);
+    }
+}
@@ -1,3 +1,4 @@
+use super::dataset::Dataset;
 use byteorder::{BigEndian, ReadBytesExt};
 use flate2::read::GzDecoder;
 use ndarray::{Array1, Array2, s};
@@ -24,6 +25,9 @@ const TEST_IMAGES_URL: &str =
 const TEST_LABELS_URL: &str =
     "https://ossci-datasets.s3.amazonaws.com/mnist/t10k-labels-idx1-ubyte.gz";
 
+/// Train images, train labels, test images, test labels (one-hot encoded)
+pub type OneHotSplits = (Array2<f32>, Array2<f32>, Array2<f32>, Array2<f32>);
+
 /// Errors that can occur during MNIST loading
 #[derive(Debug)]
 pub enum MnistError {
@@ -124,6 +128,21 @@ impl MnistDataset {
         self.test_images.mapv_inplace(|x| x / 255.0);
     }
 
+    /// Per-pixel mean of the training images (length = image size, 784 for MNIST)
+    pub fn mean_image(&self) -> Array1<f32> {
+        self.train_images
+            .mean_axis(ndarray::Axis(0))
+            .unwrap_or_else(|| Array1::zeros(self.train_images.ncols()))
+    }
+
+    /// Subtract the training mean image from every train and test image.
+    /// The test set uses the training mean so no test statistics leak into preprocessing.
+    pub fn subtract_mean_image(&mut self) {
+        let mean = self.mean_image();
+        self.train_images -= &mean;
+        self.test_images -= &mean;
+    }
+
     /// Convert labels to one-hot encoding
     pub fn labels_to_one_hot(&self, labels: &Array1<u8>) -> Array2<f32> {
         let num_classes = 10;
@@ -136,6 +155,11 @@ impl MnistDataset {
         one_hot
     }
 
+    /// Convert labels to one-hot encoding as `f64`, ready to feed the loss functions directly
+    pub fn labels_to_one_hot_f64(&self, labels: &Array1<u8>) -> Array2<f64> {
+        self.labels_to_one_hot(labels).mapv(f64::from)
+    }
+
     /// Quick load for just training data, normalized
     pub fn load_train_normalized() -> Result<(Array2<f32>, Array1<u8>), MnistError> {
         let mut dataset = Self::load()?;
@@ -151,8 +175,7 @@ impl MnistDataset {
     }
 
     /// Load both train and test data, normalized, with one-hot encoded labels
-    pub fn load_one_hot() -> Result<(Array2<f32>, Array2<f32>, Array2<f32>, Array2<f32>), MnistError>
-    {
+    pub fn load_one_hot() -> Result<OneHotSplits, MnistError> {
         let mut dataset = Self::load()?;
         dataset.normalize();
 
@@ -183,6 +206,24 @@ impl MnistDataset {
     }
 }
 
+/// The training split as a generic `Dataset`; pixels are converted to `f64` as stored
+impl Dataset for MnistDataset {
+    fn len(&self) -> usize {
+        self.train_size()
+    }
+
+    fn get(&self, idx: usize) -> (Array1<f64>, usize) {
+        (
+            self.train_images.row(idx).mapv(f64::from),
+            self.train_labels[idx] as usize,
+        )
+    }
+
+    fn num_classes(&self) -> usize {
+        10
+    }
+}
+
 /// Download a file if it doesn't exist locally
 fn download_if_not_exists(url: &str, path: &str) -> Result<(), MnistError> {
     if !Path::new(path).exists() {
@@ -195,12 +236,14 @@ fn download_if_not_exists(url: &str, path: &str) -> Result<(), MnistError> {
     Ok(())
 }
 
-/// Load MNIST images from gzipped file
-fn load_images(path: &str) -> Result<Array2<f32>, MnistError> {
-    let file = File::open(path)?;
-    let mut reader = BufReader::new(GzDecoder::new(file));
+/// Parse an (already decompressed) idx3 image file: magic `0x00000803`, then the
+/// image count, rows and columns as big-endian u32, then one byte per pixel.
+///
+/// Returns one flattened image per row (`rows * cols` columns) with raw 0-255 values.
+/// A header that doesn't match the number of pixel bytes is `InvalidDimensions`.
+pub fn parse_idx_images(bytes: &[u8]) -> Result<Array2<f32>, MnistError> {
+    let mut reader = bytes;
 
-    // Read header
     let magic = reader.read_u32::<BigEndian>()?;
     if magic != 0x00000803 {
         return Err(MnistError::InvalidMagicNumber);
@@ -210,28 +253,28 @@ fn load_images(path: &str) -> Result<Array2<f32>, MnistError> {
     let num_rows = reader.read_u32::<BigEndian>()? as usize;
     let num_cols = reader.read_u32::<BigEndian>()? as usize;
 
-    if num_rows != 28 || num_cols != 28 {
+    // The counts come from the file itself, so check them against the bytes that are
+    // actually there before allocating anything
+    let len = num_images
+        .checked_mul(num_rows)
+        .and_then(|n| n.checked_mul(num_cols))
+        .ok_or(MnistError::InvalidDimensions)?;
+    if len != reader.len() {
         return Err(MnistError::InvalidDimensions);
     }
+    let buffer = reader.to_vec();
 
-    // Read image data
-    let mut buffer = vec![0u8; num_images * num_rows * num_cols];
-    reader.read_exact(&mut buffer)?;
-
-    // Convert to Array2<f32>
     let images: Vec<f32> = buffer.into_iter().map(|x| x as f32).collect();
-    let images_array = Array2::from_shape_vec((num_images, num_rows * num_cols), images)
-        .map_err(|_| MnistError::InvalidDimensions)?;
-
-    Ok(images_array)
+    Array2::from_shape_vec((num_images, num_rows * num_cols), images)
+        .map_err(|_| MnistError::InvalidDimensions)
 }
 
-/// Load MNIST labels from gzipped file
-fn load_labels(path: &str) -> Result<Array1<u8>, MnistError> {
-    let file = File::open(path)?;
-    let mut reader = BufReader::new(GzDecoder::new(file));
+/// Parse an (already decompressed) idx1 label file: magic `0x00000801`, the label
+/// count as a big-endian u32, then one byte per label.
+/// A count that doesn't match the number of label bytes is `InvalidDimensions`.
+pub fn parse_idx_labels(bytes: &[u8]) -> Result<Array1<u8>, MnistError> {
+    let mut reader = bytes;
 
-    // Read header
     let magic = reader.read_u32::<BigEndian>()?;
     if magic != 0x00000801 {
         return Err(MnistError::InvalidMagicNumber);
@@ -239,12 +282,36 @@ fn load_labels(path: &str) -> Result<Array1<u8>, MnistError> {
 
     let num_labels = reader.read_u32::<BigEndian>()? as usize;
 
-    // Read label data
-    let mut buffer = vec![0u8; num_labels];
-    reader.read_exact(&mut buffer)?;
+    if num_labels != reader.len() {
+        return Err(MnistError::InvalidDimensions);
+    }
+
+    Ok(Array1::from_vec(reader.to_vec()))
+}
+
+/// Read and decompress a gzipped file into memory
+fn read_gz(path: &str) -> Result<Vec<u8>, MnistError> {
+    let file = File::open(path)?;
+    let mut bytes = Vec::new();
+    BufReader::new(GzDecoder::new(file)).read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Load MNIST images from gzipped file
+fn load_images(path: &str) -> Result<Array2<f32>, MnistError> {
+    let images = parse_idx_images(&read_gz(path)?)?;
+
+    // MNIST images are always 28x28
+    if images.ncols() != 28 * 28 {
+        return Err(MnistError::InvalidDimensions);
+    }
+
+    Ok(images)
+}
 
-    let labels_array = Array1::from_vec(buffer);
-    Ok(labels_array)
+/// Load MNIST labels from gzipped file
+fn load_labels(path: &str) -> Result<Array1<u8>, MnistError> {
+    parse_idx_labels(&read_gz(path)?)
 }
 
 #[cfg(test)]
@@ -301,4 +368,113 @@ mod tests {
         assert_eq!(one_hot[[2, 2]], 1.0);
         assert_eq!(one_hot[[3, 9]], 1.0);
     }
+
+    #[test]
+    fn test_one_hot_encoding_f64() {
+        let labels = Array1::from_vec(vec![3, 0, 7, 7, 9]);
+        let mnist = MnistDataset {
+            train_images: Array2::zeros((0, 784)),
+            train_labels: Array1::zeros(0),
+            test_images: Array2::zeros((0, 784)),
+            test_labels: Array1::zeros(0),
+        };
+
+        let one_hot = mnist.labels_to_one_hot_f64(&labels);
+        assert_eq!(one_hot, mnist.labels_to_one_hot(&labels).mapv(|v| v as f64));
+        for (row, &label) in one_hot.rows().into_iter().zip(labels.iter()) {
+            assert_eq!(row.iter().filter(|&&v| v == 1.0).count(), 1);
+            assert_eq!(row[label as usize], 1.0);
+        }
+    }
+
+    #[test]
+    fn test_subtract_mean_image() {
+        let mut mnist = MnistDataset {
+            train_images: Array2::from_shape_fn((3, 4), |(i, j)| (i * 4 + j) as f32),
+            train_labels: Array1::zeros(3),
+            test_images: Array2::from_elem((2, 4), 10.0),
+            test_labels: Array1::zeros(2),
+        };
+
+        let mean = mnist.mean_image();
+        assert_eq!(mean, Array1::from_vec(vec![4.0, 5.0, 6.0, 7.0]));
+
+        mnist.subtract_mean_image();
+
+        // Per-pixel training mean is now ~0
+        let new_mean = mnist.mean_image();
+        assert!(new_mean.iter().all(|v| v.abs() < 1e-6));
+
+        // Test images are shifted by the training mean, not their own
+        assert_eq!(mnist.test_images.row(0).to_vec(), vec![6.0, 5.0, 4.0, 3.0]);
+    }
+
+    #[test]
+    fn test_parse_idx_images() {
+        // 2 images of 2x3 pixels
+        let mut bytes = vec![0, 0, 8, 3, 0, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0, 3];
+        bytes.extend(0u8..12);
+
+        let images = parse_idx_images(&bytes).unwrap();
+        assert_eq!(images.dim(), (2, 6));
+        assert_eq!(images.row(0).to_vec(), vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(images[[1, 5]], 11.0);
+    }
+
+    #[test]
+    fn test_parse_idx_labels() {
+        let bytes = [0, 0, 8, 1, 0, 0, 0, 3, 7, 0, 9];
+        let labels = parse_idx_labels(&bytes).unwrap();
+        assert_eq!(labels, Array1::from_vec(vec![7, 0, 9]));
+    }
+
+    #[test]
+    fn test_parse_idx_rejects_bad_input() {
+        // Wrong magic number (a label file passed as images)
+        let labels = [0, 0, 8, 1, 0, 0, 0, 1, 5];
+        assert!(matches!(
+            parse_idx_images(&labels),
+            Err(MnistError::InvalidMagicNumber)
+        ));
+
+        // Header says 4 labels but only 2 bytes follow
+        let truncated = [0, 0, 8, 1, 0, 0, 0, 4, 1, 2];
+        assert!(matches!(
+            parse_idx_labels(&truncated),
+            Err(MnistError::InvalidDimensions)
+        ));
+
+        // A header cut short is still an I/O error
+        assert!(matches!(
+            parse_idx_images(&[0, 0, 8, 3, 0, 0]),
+            Err(MnistError::IoError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_idx_rejects_oversized_header() {
+        // 2^31 images of 28x28 claimed, 16 bytes of pixels present: must not allocate
+        let mut bytes = vec![0, 0, 8, 3, 0x80, 0, 0, 0, 0, 0, 0, 28, 0, 0, 0, 28];
+        bytes.extend([0u8; 16]);
+        assert!(matches!(
+            parse_idx_images(&bytes),
+            Err(MnistError::InvalidDimensions)
+        ));
+
+        // Dimensions whose product overflows usize
+        let mut bytes = vec![0, 0, 8, 3];
+        bytes.extend([0xff; 12]);
+        bytes.extend([0u8; 16]);
+        assert!(matches!(
+            parse_idx_images(&bytes),
+            Err(MnistError::InvalidDimensions)
+        ));
+
+        let mut labels = vec![0, 0, 8, 1, 0x80, 0, 0, 0];
+        labels.extend([0u8; 16]);
+        assert!(matches!(
+            parse_idx_labels(&labels),
+            Err(MnistError::InvalidDimensions)
+        ));
+    }
 }
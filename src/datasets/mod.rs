@@ -1,3 +1,7 @@
+pub mod dataset;
 pub mod mnist;
+pub mod preprocess;
+pub mod synthetic;
 
-pub use mnist::{MnistDataset, MnistError};
+pub use dataset::{DataLoader, Dataset};
+pub use mnist::{MnistDataset, MnistError, parse_idx_images, parse_idx_labels};
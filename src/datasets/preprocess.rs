@@ -0,0 +1,313 @@
+use crate::chapter02::serialize::{load_params, save_params};
+use ndarray::{Array1, Array2, Axis};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::io;
+use std::path::Path;
+
+/// Shift an image by `dx` columns (positive = right) and `dy` rows (positive = down).
+/// Pixels shifted in from outside the image are zero-filled.
+pub fn shift(image: &Array2<f32>, dx: i32, dy: i32) -> Array2<f32> {
+    let (rows, cols) = image.dim();
+    let mut shifted = Array2::<f32>::zeros((rows, cols));
+
+    for ((r, c), &v) in image.indexed_iter() {
+        let nr = r as i64 + dy as i64;
+        let nc = c as i64 + dx as i64;
+        if (0..rows as i64).contains(&nr) && (0..cols as i64).contains(&nc) {
+            shifted[[nr as usize, nc as usize]] = v;
+        }
+    }
+
+    shifted
+}
+
+/// Randomly shift a (28x28) image by up to `max_px` pixels in each direction.
+/// This is the classic MNIST translation augmentation; exposed borders are zero-filled.
+pub fn random_shift(image: &Array2<f32>, max_px: u32, seed: u64) -> Array2<f32> {
+    // Shifts past the image size only zero it out, so clamping huge values changes nothing.
+    let max_px = i32::try_from(max_px).unwrap_or(i32::MAX);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let dx = rng.random_range(-max_px..=max_px);
+    let dy = rng.random_range(-max_px..=max_px);
+    shift(image, dx, dy)
+}
+
+/// Expand each feature into its powers `x, x^2, ..., x^degree`, prefixed by a bias column of ones.
+///
+/// For `n` input features the output has `1 + n * degree` columns laid out as
+/// `[1, x1, x1^2, .., x1^degree, x2, x2^2, ..]`, so a linear model on the result fits a polynomial.
+pub fn polynomial_features(x: &Array2<f64>, degree: usize) -> Array2<f64> {
+    let (rows, cols) = x.dim();
+    let mut out = Array2::<f64>::ones((rows, 1 + cols * degree));
+
+    for (feature, column) in x.axis_iter(Axis(1)).enumerate() {
+        for power in 1..=degree {
+            let target = 1 + feature * degree + (power - 1);
+            out.column_mut(target)
+                .assign(&column.mapv(|v| v.powi(power as i32)));
+        }
+    }
+
+    out
+}
+
+/// Per-feature min-max scaling to `[0, 1]`.
+///
+/// `fit` records each column's min and max on the training data; `transform` and
+/// `inverse_transform` reuse those statistics so test data is scaled exactly like train data.
+/// A constant feature (zero range) is mapped to 0 instead of dividing by zero.
+#[derive(Debug, Clone, Default)]
+pub struct MinMaxScaler {
+    pub min: Array1<f64>,
+    pub max: Array1<f64>,
+}
+
+impl MinMaxScaler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>) {
+        self.min = x.fold_axis(Axis(0), f64::INFINITY, |&m, &v| m.min(v));
+        self.max = x.fold_axis(Axis(0), f64::NEG_INFINITY, |&m, &v| m.max(v));
+    }
+
+    pub fn fit_transform(&mut self, x: &Array2<f64>) -> Array2<f64> {
+        self.fit(x);
+        self.transform(x)
+    }
+
+    pub fn transform(&self, x: &Array2<f64>) -> Array2<f64> {
+        self.check_fitted(x);
+        (x - &self.min) / &self.range()
+    }
+
+    pub fn inverse_transform(&self, x: &Array2<f64>) -> Array2<f64> {
+        self.check_fitted(x);
+        x * &self.range() + &self.min
+    }
+
+    fn range(&self) -> Array1<f64> {
+        (&self.max - &self.min).mapv(|r| if r > 0.0 { r } else { 1.0 })
+    }
+
+    fn check_fitted(&self, x: &Array2<f64>) {
+        assert_eq!(
+            x.ncols(),
+            self.min.len(),
+            "MinMaxScaler: fitted on {} features, got {} (call fit first)",
+            self.min.len(),
+            x.ncols()
+        );
+    }
+}
+
+/// Per-feature standardization to zero mean and unit standard deviation.
+///
+/// `fit` learns each column's mean and (population) standard deviation from the training
+/// data; `transform` applies exactly those statistics to any later input, and `save` / `load`
+/// persist them next to the model so inference uses the same preprocessing as training.
+/// A constant feature (zero std) is only centered, not scaled.
+#[derive(Debug, Clone, Default)]
+pub struct Normalizer {
+    pub mean: Array1<f64>,
+    pub std: Array1<f64>,
+}
+
+impl Normalizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn fit(&mut self, x: &Array2<f64>) {
+        assert!(x.nrows() > 0, "Normalizer: cannot fit on an empty dataset");
+        self.mean = x.mean_axis(Axis(0)).unwrap();
+        self.std = x.std_axis(Axis(0), 0.0);
+    }
+
+    pub fn fit_transform(&mut self, x: &Array2<f64>) -> Array2<f64> {
+        self.fit(x);
+        self.transform(x)
+    }
+
+    pub fn transform(&self, x: &Array2<f64>) -> Array2<f64> {
+        self.check_fitted(x);
+        (x - &self.mean) / &self.scale()
+    }
+
+    pub fn inverse_transform(&self, x: &Array2<f64>) -> Array2<f64> {
+        self.check_fitted(x);
+        x * &self.scale() + &self.mean
+    }
+
+    /// Save the fitted statistics in the same binary format as model parameters
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mean = self.mean.clone().insert_axis(Axis(0));
+        let std = self.std.clone().insert_axis(Axis(0));
+        save_params(path, &[&mean, &std])
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+        let [mean, std]: [Array2<f64>; 2] = load_params(path)?
+            .try_into()
+            .map_err(|p: Vec<_>| invalid(format!("expected 2 arrays, file has {}", p.len())))?;
+        if mean.nrows() != 1 || mean.shape() != std.shape() {
+            return Err(invalid(format!(
+                "expected two 1xN arrays, file has {:?} and {:?}",
+                mean.shape(),
+                std.shape()
+            )));
+        }
+        Ok(Self {
+            mean: mean.row(0).to_owned(),
+            std: std.row(0).to_owned(),
+        })
+    }
+
+    fn scale(&self) -> Array1<f64> {
+        self.std.mapv(|s| if s > 0.0 { s } else { 1.0 })
+    }
+
+    fn check_fitted(&self, x: &Array2<f64>) {
+        assert_eq!(
+            x.ncols(),
+            self.mean.len(),
+            "Normalizer: fitted on {} features, got {} (call fit first)",
+            self.mean.len(),
+            x.ncols()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    fn sample_image() -> Array2<f32> {
+        Array2::from_shape_fn((28, 28), |(r, c)| (r * 28 + c) as f32 + 1.0)
+    }
+
+    #[test]
+    fn test_shift_right_by_one() {
+        let image = sample_image();
+        let shifted = shift(&image, 1, 0);
+
+        for r in 0..28 {
+            for c in 0..27 {
+                assert_eq!(shifted[[r, c + 1]], image[[r, c]]);
+            }
+            // The exposed left border column is zero-filled
+            assert_eq!(shifted[[r, 0]], 0.0);
+        }
+    }
+
+    #[test]
+    fn test_random_shift_is_seeded_and_bounded() {
+        let image = sample_image();
+        let a = random_shift(&image, 2, 42);
+        let b = random_shift(&image, 2, 42);
+        assert_eq!(a, b);
+        assert_eq!(a.dim(), (28, 28));
+
+        // A shift of at most 2 pixels keeps the center pixel's neighbourhood in view
+        let center = image[[14, 14]];
+        assert!(a.iter().any(|&v| v == center));
+    }
+
+    #[test]
+    fn test_random_shift_zero_is_identity() {
+        let image = sample_image();
+        assert_eq!(random_shift(&image, 0, 7), image);
+    }
+
+    #[test]
+    fn test_random_shift_accepts_huge_max_px() {
+        let image = Array2::<f32>::ones((4, 4));
+        assert_eq!(random_shift(&image, u32::MAX, 7).dim(), (4, 4));
+    }
+
+    #[test]
+    fn test_polynomial_features_degree_two() {
+        let x = array![[2.0]];
+        assert_eq!(polynomial_features(&x, 2), array![[1.0, 2.0, 4.0]]);
+    }
+
+    #[test]
+    fn test_polynomial_features_multiple_features() {
+        let x = array![[2.0, 3.0], [-1.0, 0.5]];
+        let expanded = polynomial_features(&x, 3);
+        assert_eq!(
+            expanded,
+            array![
+                [1.0, 2.0, 4.0, 8.0, 3.0, 9.0, 27.0],
+                [1.0, -1.0, 1.0, -1.0, 0.5, 0.25, 0.125]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_min_max_scaler_round_trip() {
+        let x = array![[1.0, -10.0, 5.0], [3.0, 0.0, 5.0], [2.0, 10.0, 5.0]];
+        let mut scaler = MinMaxScaler::new();
+        let scaled = scaler.fit_transform(&x);
+
+        assert_eq!(
+            scaled,
+            array![[0.0, 0.0, 0.0], [1.0, 0.5, 0.0], [0.5, 1.0, 0.0]]
+        );
+        assert_eq!(scaler.inverse_transform(&scaled), x);
+    }
+
+    #[test]
+    fn test_min_max_scaler_uses_train_statistics() {
+        let mut scaler = MinMaxScaler::new();
+        scaler.fit(&array![[0.0], [4.0]]);
+        assert_eq!(
+            scaler.transform(&array![[2.0], [8.0]]),
+            array![[0.5], [2.0]]
+        );
+    }
+
+    #[test]
+    fn test_normalizer_standardizes_training_data() {
+        let train = array![[1.0, 10.0, 3.0], [3.0, 20.0, 3.0], [5.0, 60.0, 3.0]];
+        let mut normalizer = Normalizer::new();
+        let z = normalizer.fit_transform(&train);
+
+        for (mean, std) in z
+            .mean_axis(Axis(0))
+            .unwrap()
+            .iter()
+            .zip(z.std_axis(Axis(0), 0.0).iter())
+            .take(2)
+        {
+            assert!(mean.abs() < 1e-12);
+            assert!((std - 1.0).abs() < 1e-12);
+        }
+        // The constant column is only centered
+        assert!(z.column(2).iter().all(|&v| v == 0.0));
+
+        // Held-out data gets the same shift and scale as the training data
+        let held_out = array![[3.0, 30.0, 4.0]];
+        let expected = (&held_out - &normalizer.mean) / &normalizer.scale();
+        assert_eq!(normalizer.transform(&held_out), expected);
+        assert_eq!(normalizer.transform(&held_out)[[0, 0]], 0.0);
+    }
+
+    #[test]
+    fn test_normalizer_save_and_load() {
+        let mut normalizer = Normalizer::new();
+        normalizer.fit(&array![[1.0, -2.0], [4.0, 6.0], [0.5, 1.0]]);
+
+        let path = std::env::temp_dir().join(format!("rdl_normalizer_{}.bin", std::process::id()));
+        normalizer.save(&path).unwrap();
+        let loaded = Normalizer::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.mean, normalizer.mean);
+        assert_eq!(loaded.std, normalizer.std);
+    }
+}
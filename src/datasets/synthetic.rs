@@ -0,0 +1,218 @@
+use super::dataset::Dataset;
+use ndarray::{Array1, Array2};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand_distr::{Distribution, Normal, Uniform};
+
+/// In-memory dataset for synthetic (generated) data
+#[derive(Debug, Clone)]
+pub struct SyntheticDataset {
+    pub x: Array2<f64>,
+    pub labels: Array1<usize>,
+    num_classes: usize,
+}
+
+impl SyntheticDataset {
+    pub fn new(x: Array2<f64>, labels: Array1<usize>, num_classes: usize) -> Self {
+        assert_eq!(x.nrows(), labels.len());
+        Self {
+            x,
+            labels,
+            num_classes,
+        }
+    }
+
+    /// Isotropic 2D Gaussian blobs, `n_per_class` points around each center.
+    /// Class `k` is the blob around `centers[k]`.
+    pub fn blobs(n_per_class: usize, centers: &[(f64, f64)], std: f64, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let normal = Normal::new(0.0, std).unwrap();
+
+        let n = n_per_class * centers.len();
+        let mut x = Array2::zeros((n, 2));
+        let mut labels = Array1::zeros(n);
+
+        for (class, &(cx, cy)) in centers.iter().enumerate() {
+            for i in 0..n_per_class {
+                let row = class * n_per_class + i;
+                x[[row, 0]] = cx + normal.sample(&mut rng);
+                x[[row, 1]] = cy + normal.sample(&mut rng);
+                labels[row] = class;
+            }
+        }
+
+        Self::new(x, labels, centers.len())
+    }
+}
+
+/// Two interleaving half circles (the scikit-learn "two moons" toy problem).
+///
+/// Class 0 is the upper moon `(cos t, sin t)` and class 1 the lower moon
+/// `(1 - cos t, 0.5 - sin t)` for `t` in `[0, pi]`, each with `n_samples / 2`
+/// points (class 0 gets the extra one when `n_samples` is odd). Gaussian noise
+/// with standard deviation `noise` is added to both coordinates. The classes are
+/// not linearly separable, so this is a good check that a network learned a
+/// nonlinear decision boundary.
+pub fn make_moons(n_samples: usize, noise: f64, seed: u64) -> SyntheticDataset {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(0.0, noise).unwrap();
+
+    let n_outer = n_samples - n_samples / 2;
+    let mut x = Array2::zeros((n_samples, 2));
+    let mut labels = Array1::zeros(n_samples);
+
+    for row in 0..n_samples {
+        let (class, i, count) = if row < n_outer {
+            (0, row, n_outer)
+        } else {
+            (1, row - n_outer, n_samples - n_outer)
+        };
+        let t = std::f64::consts::PI * i as f64 / (count.max(2) - 1) as f64;
+        let (px, py) = if class == 0 {
+            (t.cos(), t.sin())
+        } else {
+            (1.0 - t.cos(), 0.5 - t.sin())
+        };
+        x[[row, 0]] = px + normal.sample(&mut rng);
+        x[[row, 1]] = py + normal.sample(&mut rng);
+        labels[row] = class;
+    }
+
+    SyntheticDataset::new(x, labels, 2)
+}
+
+/// `n` points drawn from an isotropic 2D Gaussian around `center`, as an `(n, 2)` array
+pub fn normal_cloud(n: usize, center: (f64, f64), std: f64, seed: u64) -> Array2<f64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(0.0, std).unwrap();
+    let mut x = Array2::zeros((n, 2));
+    for mut row in x.rows_mut() {
+        row[0] = center.0 + normal.sample(&mut rng);
+        row[1] = center.1 + normal.sample(&mut rng);
+    }
+    x
+}
+
+/// `n` points drawn uniformly from the square `[low, high) x [low, high)`
+pub fn uniform_square(n: usize, low: f64, high: f64, seed: u64) -> Array2<f64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let uniform = Uniform::new(low, high).unwrap();
+    Array2::from_shape_simple_fn((n, 2), || uniform.sample(&mut rng))
+}
+
+/// `n` points evenly spaced around a circle of the given `radius`, with the radius of
+/// each point perturbed by Gaussian noise of standard deviation `noise`
+pub fn noisy_circle(n: usize, radius: f64, noise: f64, seed: u64) -> Array2<f64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(0.0, noise).unwrap();
+    let mut x = Array2::zeros((n, 2));
+    for (i, mut row) in x.rows_mut().into_iter().enumerate() {
+        let angle = 2.0 * std::f64::consts::PI * i as f64 / n as f64;
+        let r = radius + normal.sample(&mut rng);
+        row[0] = r * angle.cos();
+        row[1] = r * angle.sin();
+    }
+    x
+}
+
+/// `n` points along an Archimedean spiral `r = 0.1 t` (`t = 0, 0.1, 0.2, ..`),
+/// with Gaussian noise of standard deviation `noise` added to both coordinates
+pub fn spiral(n: usize, noise: f64, seed: u64) -> Array2<f64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(0.0, noise).unwrap();
+    let mut x = Array2::zeros((n, 2));
+    for (i, mut row) in x.rows_mut().into_iter().enumerate() {
+        let t = i as f64 * 0.1;
+        let r = 0.1 * t;
+        row[0] = r * t.cos() + normal.sample(&mut rng);
+        row[1] = r * t.sin() + normal.sample(&mut rng);
+    }
+    x
+}
+
+/// 1D regression data: `x` uniform in `[-2, 2)` and `y = x^2 + 0.5 x` plus Gaussian
+/// noise of standard deviation `noise`
+pub fn quadratic_regression(n: usize, noise: f64, seed: u64) -> (Array1<f64>, Array1<f64>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(0.0, noise).unwrap();
+    let uniform = Uniform::new(-2.0, 2.0).unwrap();
+
+    let x: Array1<f64> = (0..n).map(|_| uniform.sample(&mut rng)).collect();
+    let y = x.mapv(|v| v * v + 0.5 * v + normal.sample(&mut rng));
+    (x, y)
+}
+
+impl Dataset for SyntheticDataset {
+    fn len(&self) -> usize {
+        self.x.nrows()
+    }
+
+    fn get(&self, idx: usize) -> (Array1<f64>, usize) {
+        (self.x.row(idx).to_owned(), self.labels[idx])
+    }
+
+    fn num_classes(&self) -> usize {
+        self.num_classes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blobs_shape_and_labels() {
+        let data = SyntheticDataset::blobs(10, &[(0.0, 0.0), (5.0, 5.0), (-5.0, 5.0)], 0.1, 42);
+        assert_eq!(data.len(), 30);
+        assert_eq!(data.num_classes(), 3);
+
+        let (x, label) = data.get(15);
+        assert_eq!(label, 1);
+        assert!((x[0] - 5.0).abs() < 1.0 && (x[1] - 5.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_make_moons_is_seeded() {
+        let data = make_moons(101, 0.1, 7);
+        assert_eq!(data.len(), 101);
+        assert_eq!(data.num_classes(), 2);
+        assert_eq!(data.labels.iter().filter(|&&l| l == 0).count(), 51);
+        assert_eq!(data.x, make_moons(101, 0.1, 7).x);
+    }
+
+    #[test]
+    fn test_make_moons_without_noise_lies_on_arcs() {
+        let data = make_moons(20, 0.0, 0);
+        for (row, &label) in data.x.rows().into_iter().zip(data.labels.iter()) {
+            let (cx, cy) = if label == 0 { (0.0, 0.0) } else { (1.0, 0.5) };
+            let r = ((row[0] - cx).powi(2) + (row[1] - cy).powi(2)).sqrt();
+            assert!((r - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_generators_are_reproducible() {
+        assert_eq!(
+            normal_cloud(50, (1.0, -1.0), 0.5, 3),
+            normal_cloud(50, (1.0, -1.0), 0.5, 3)
+        );
+        assert_eq!(
+            uniform_square(50, -1.0, 1.0, 3),
+            uniform_square(50, -1.0, 1.0, 3)
+        );
+        assert_eq!(noisy_circle(50, 1.0, 0.1, 3), noisy_circle(50, 1.0, 0.1, 3));
+        assert_eq!(spiral(50, 0.05, 3), spiral(50, 0.05, 3));
+        assert_eq!(
+            quadratic_regression(50, 0.3, 3),
+            quadratic_regression(50, 0.3, 3)
+        );
+
+        assert_ne!(spiral(50, 0.05, 3), spiral(50, 0.05, 4));
+        assert_eq!(uniform_square(17, -1.0, 1.0, 0).dim(), (17, 2));
+        assert!(
+            uniform_square(100, -1.0, 1.0, 0)
+                .iter()
+                .all(|v| (-1.0..1.0).contains(v))
+        );
+    }
+}
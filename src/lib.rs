@@ -1,4 +1,70 @@
 pub mod chapter01;
 pub mod chapter02;
+pub mod chapter05;
 pub mod datasets;
 pub mod utils;
+
+use std::fmt;
+
+/// 章节的运行方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunMode {
+    /// 运行固定的演示
+    Demo,
+    /// 交互式命令行
+    Interactive,
+}
+
+/// `run` 无法执行所请求的章节 / 模式时的错误
+#[derive(Debug, PartialEq)]
+pub enum RunError {
+    UnknownChapter(u8),
+    UnsupportedMode { chapter: u8, mode: RunMode },
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::UnknownChapter(chapter) => write!(f, "unknown chapter {}", chapter),
+            RunError::UnsupportedMode { chapter, mode } => {
+                write!(f, "chapter {} has no {:?} mode", chapter, mode)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+/// 统一入口：按章节和运行方式分发到对应的演示或交互程序
+pub fn run(chapter: u8, mode: RunMode) -> Result<(), RunError> {
+    match (chapter, mode) {
+        (1, RunMode::Demo) => chapter01::perceptron::run_chapter01(),
+        (1, RunMode::Interactive) => chapter01::cli::interactive_mode(),
+        (2, RunMode::Demo) => chapter02::train_simple::train_example(),
+        (2, mode) => return Err(RunError::UnsupportedMode { chapter, mode }),
+        _ => return Err(RunError::UnknownChapter(chapter)),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_chapter01_demo() {
+        assert_eq!(run(1, RunMode::Demo), Ok(()));
+    }
+
+    #[test]
+    fn test_run_errors() {
+        assert_eq!(run(9, RunMode::Demo), Err(RunError::UnknownChapter(9)));
+        assert_eq!(
+            run(2, RunMode::Interactive),
+            Err(RunError::UnsupportedMode {
+                chapter: 2,
+                mode: RunMode::Interactive
+            })
+        );
+    }
+}
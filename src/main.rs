@@ -1,9 +1,32 @@
-mod chapter01;
-use chapter01::cli::interactive_mode;
-mod chapter02;
+use rust_dl_from_scratch::{RunMode, run};
+use std::process::ExitCode;
 
-use chapter02::train_simple::train_example;
+const USAGE: &str = "usage: rust-dl-from-scratch [chapter <N> [--interactive]]";
 
-fn main() {
-    train_example();
+fn parse_args(args: &[String]) -> Option<(u8, RunMode)> {
+    match args {
+        // 不带参数时保持原来的行为：运行第 2 章的训练示例
+        [] => Some((2, RunMode::Demo)),
+        [cmd, n] if cmd == "chapter" => Some((n.parse().ok()?, RunMode::Demo)),
+        [cmd, n, flag] if cmd == "chapter" && flag == "--interactive" => {
+            Some((n.parse().ok()?, RunMode::Interactive))
+        }
+        _ => None,
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some((chapter, mode)) = parse_args(&args) else {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    };
+
+    match run(chapter, mode) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
 }
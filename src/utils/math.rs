@@ -1,3 +1,5 @@
+use ndarray::{Array1, Array2, ArrayView1, Axis};
+
 pub fn sigmoid(x: f64) -> f64 {
     1.0 / (1.0 + (-x).exp())
 }
@@ -5,3 +7,82 @@ pub fn sigmoid(x: f64) -> f64 {
 pub fn relu(x: f64) -> f64 {
     x.max(0.0)
 }
+
+/// 逐元素截断到 [min, max]，可用于按值裁剪梯度或把像素限制在合法范围
+pub fn clip(a: &Array2<f64>, min: f64, max: f64) -> Array2<f64> {
+    a.mapv(|v| v.clamp(min, max))
+}
+
+/// 把 `values` 按 `range` 等宽分成 `bins` 个区间并统计每个区间的数量。
+///
+/// 区间为左闭右开，最后一个区间包含右端点；超出 `range` 的值会被归入
+/// 两端的区间（小于下界的计入第一个，大于上界的计入最后一个），NaN 会被忽略。
+pub fn histogram(values: &ArrayView1<f64>, bins: usize, range: (f64, f64)) -> Vec<usize> {
+    let (lo, hi) = range;
+    assert!(hi > lo, "histogram: range must satisfy lo < hi");
+
+    let mut counts = vec![0; bins];
+    if bins == 0 {
+        return counts;
+    }
+
+    let width = (hi - lo) / bins as f64;
+    for &v in values.iter().filter(|v| !v.is_nan()) {
+        let idx = ((v - lo) / width).floor().clamp(0.0, (bins - 1) as f64) as usize;
+        counts[idx] += 1;
+    }
+    counts
+}
+
+/// 每一列（每个特征）在批次上的均值，BatchNorm 与标准化都会用到
+pub fn column_mean(a: &Array2<f64>) -> Array1<f64> {
+    assert!(a.nrows() > 0, "column_mean: empty batch");
+    a.mean_axis(Axis(0)).unwrap()
+}
+
+/// 每一列的方差 Σ(x - mean)² / (n - ddof)：ddof = 0 为有偏估计（BatchNorm 使用），
+/// ddof = 1 为带 Bessel 校正的无偏估计
+pub fn column_var(a: &Array2<f64>, ddof: usize) -> Array1<f64> {
+    assert!(
+        a.nrows() > ddof,
+        "column_var: need more than {} rows, got {}",
+        ddof,
+        a.nrows()
+    );
+    let centered = a - &column_mean(a);
+    (&centered * &centered).sum_axis(Axis(0)) / (a.nrows() - ddof) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_histogram() {
+        let values = array![0.1, 0.5, 0.9];
+        assert_eq!(histogram(&values.view(), 2, (0.0, 1.0)), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_histogram_out_of_range_goes_to_edges() {
+        let values = array![-5.0, 0.0, 1.0, 7.0, f64::NAN];
+        assert_eq!(histogram(&values.view(), 4, (0.0, 1.0)), vec![2, 0, 0, 2]);
+    }
+
+    #[test]
+    fn test_clip() {
+        let a = array![[-2.0, 0.5], [1.0, 3.0]];
+        assert_eq!(clip(&a, 0.0, 1.0), array![[0.0, 0.5], [1.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_column_mean_and_var() {
+        let a = array![[1.0, 2.0], [3.0, 6.0], [5.0, 10.0]];
+        assert_eq!(column_mean(&a), array![3.0, 6.0]);
+
+        // 第一列偏差平方和为 8，第二列为 32
+        assert_eq!(column_var(&a, 0), array![8.0 / 3.0, 32.0 / 3.0]);
+        assert_eq!(column_var(&a, 1), array![4.0, 16.0]);
+    }
+}
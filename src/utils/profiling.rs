@@ -0,0 +1,62 @@
+use crate::chapter02::network::SimpleNet;
+use ndarray::Array2;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// 正式计时前先运行的次数，用于预热缓存和分配器
+const WARMUP_RUNS: usize = 3;
+
+/// 测量 `net.predict(x)` 的平均耗时：先预热几次，再取 `runs` 次前向传播的平均值。
+/// 不需要搭建 criterion 就能快速估计一个网络的推理速度
+pub fn profile_predict(net: &SimpleNet, x: &Array2<f64>, runs: usize) -> Duration {
+    time_average(runs, || {
+        black_box(net.predict(black_box(x)));
+    })
+}
+
+/// 先调用 `f` 预热 `WARMUP_RUNS` 次，再计时调用 `runs` 次，返回平均每次的耗时
+pub fn time_average<F: FnMut()>(runs: usize, mut f: F) -> Duration {
+    assert!(runs > 0, "time_average: runs must be positive");
+    for _ in 0..WARMUP_RUNS {
+        f();
+    }
+
+    let start = Instant::now();
+    for _ in 0..runs {
+        f();
+    }
+    // 用浮点数相除：`runs as u32` 在 runs 超过 u32::MAX 时会截断
+    Duration::from_secs_f64(start.elapsed().as_secs_f64() / runs as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_predict_is_non_zero() {
+        let net = SimpleNet::new(784, 50, 10);
+        let x = Array2::from_elem((32, 784), 0.5);
+        assert!(profile_predict(&net, &x, 5) > Duration::ZERO);
+    }
+
+    #[test]
+    #[should_panic(expected = "runs must be positive")]
+    fn test_profile_predict_rejects_zero_runs() {
+        let net = SimpleNet::new(4, 3, 2);
+        profile_predict(&net, &Array2::zeros((1, 4)), 0);
+    }
+
+    #[test]
+    fn test_time_average_divides_total_time() {
+        let average = time_average(4, || std::thread::sleep(Duration::from_millis(5)));
+        assert!(average >= Duration::from_millis(5), "{:?}", average);
+    }
+
+    #[test]
+    fn test_time_average_call_count() {
+        let mut calls = 0;
+        time_average(10, || calls += 1);
+        assert_eq!(calls, WARMUP_RUNS + 10);
+    }
+}
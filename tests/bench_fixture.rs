@@ -0,0 +1,24 @@
+// tests/bench_fixture.rs
+// 确认基准测试的固定数据集在相同种子下可复现
+#[path = "../benches/common.rs"]
+mod common;
+
+use common::{fixed_dataset, fixed_uniform};
+
+#[test]
+fn test_fixed_dataset_is_reproducible() {
+    let a = fixed_dataset(50, 10, 42);
+    let b = fixed_dataset(50, 10, 42);
+    let c = fixed_dataset(50, 10, 43);
+
+    assert_eq!(a.shape(), &[50, 10]);
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert!(a.iter().all(|&v| (0.0..1.0).contains(&v)));
+}
+
+#[test]
+fn test_fixed_uniform_range() {
+    let a = fixed_uniform(20, 20, 7, -5.0, 5.0);
+    assert!(a.iter().all(|&v| (-5.0..5.0).contains(&v)));
+}
@@ -0,0 +1,42 @@
+// tests/end_to_end.rs
+// 完整流程：训练 -> 保存 -> 加载 -> 评估，防止各模块单独正确但串起来出错
+use rust_dl_from_scratch::chapter02::init::HeInit;
+use rust_dl_from_scratch::chapter02::metrics::{accuracy, to_one_hot};
+use rust_dl_from_scratch::chapter02::model::argmax_predictions;
+use rust_dl_from_scratch::chapter02::serialize::{load_model, save_model};
+use rust_dl_from_scratch::chapter02::trainer::{RunConfig, Trainer};
+use rust_dl_from_scratch::chapter05::two_layer_net::TwoLayerNet;
+use rust_dl_from_scratch::datasets::synthetic::SyntheticDataset;
+
+const SEED: u64 = 42;
+
+#[test]
+fn test_train_save_load_evaluate() {
+    // 两团相距很远的点，线性可分
+    let data = SyntheticDataset::blobs(50, &[(-2.0, -2.0), (2.0, 2.0)], 0.5, SEED);
+    let t = to_one_hot(&data.labels, 2);
+    let config = RunConfig::new(SEED);
+
+    let net = TwoLayerNet::with_initializer(2, 8, 2, &HeInit, config.weight_seed());
+    let mut trainer = Trainer::new(net, 0.1, 20, 10).with_run_config(config);
+    let history = trainer.fit(&data.x, &t);
+    assert!(history.train_loss.last() < history.train_loss.first());
+
+    let trained = trainer.model;
+    let acc = accuracy(&argmax_predictions(&trained, &data.x), &data.labels);
+    assert!(acc > 0.95, "training accuracy too low: {acc}");
+
+    let dir = std::env::temp_dir().join(format!("rdl_end_to_end_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("two_layer.model");
+    save_model(&trained, &path).unwrap();
+
+    // 用不同的种子初始化，确认评估结果完全来自加载的参数
+    let mut reloaded = TwoLayerNet::with_initializer(2, 8, 2, &HeInit, SEED + 1);
+    load_model(&mut reloaded, &path).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let reloaded_acc = accuracy(&argmax_predictions(&reloaded, &data.x), &data.labels);
+    assert_eq!(reloaded_acc, acc);
+    assert_eq!(reloaded.predict(&data.x), trained.predict(&data.x));
+}
@@ -0,0 +1,218 @@
+// tests/gradient_check.rs
+// 用数值微分逐层检查反向传播的梯度，新增层时在这里补上对应的检查
+use ndarray::{Array2, Array4};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand_distr::{Distribution, Normal};
+use rust_dl_from_scratch::chapter02::grad::numerical_gradient;
+use rust_dl_from_scratch::chapter05::batch_norm::BatchNorm;
+use rust_dl_from_scratch::chapter05::convolution::Convolution;
+use rust_dl_from_scratch::chapter05::layers::{Affine, Dropout, Layer, ReluLayer, SigmoidLayer};
+use rust_dl_from_scratch::chapter05::pooling::{PoolMode, Pooling};
+use rust_dl_from_scratch::chapter05::tensor::Tensor;
+
+const SEED: u64 = 42;
+const TOL: f64 = 1e-4;
+
+fn random_array(shape: (usize, usize), seed: u64) -> Array2<f64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    Array2::from_shape_fn(shape, |_| normal.sample(&mut rng))
+}
+
+/// 一小批 (N, C, H, W) 图像
+fn random_images(shape: (usize, usize, usize, usize), seed: u64) -> Array4<f64> {
+    let (n, c, h, w) = shape;
+    random_array((n, c * h * w), seed)
+        .into_shape_with_order(shape)
+        .unwrap()
+}
+
+/// 比较解析梯度与数值梯度，超出容差时报告层名、梯度名和误差最大的下标
+fn assert_close(layer: &str, what: &str, analytic: &Array2<f64>, numeric: &Array2<f64>) {
+    assert_eq!(
+        analytic.shape(),
+        numeric.shape(),
+        "{layer}: {what} shape mismatch"
+    );
+
+    let (worst_idx, worst_diff) = analytic
+        .indexed_iter()
+        .map(|(idx, &a)| (idx, (a - numeric[idx]).abs()))
+        .fold(
+            ((0, 0), 0.0),
+            |best, cur| if cur.1 > best.1 { cur } else { best },
+        );
+
+    assert!(
+        worst_diff <= TOL,
+        "{layer}: {what} gradient check failed at index {worst_idx:?}: analytic {} vs numeric {} (diff {worst_diff:e})",
+        analytic[worst_idx],
+        numeric[worst_idx],
+    );
+}
+
+/// 检查对输入的梯度：用 L = sum(forward(x) * dout) 作为标量目标，dL/dx 应等于 backward(dout)
+fn check_input_grad<L: Layer>(name: &str, make: impl Fn() -> L, x: &Array2<f64>) {
+    let mut layer = make();
+    let out = layer.forward(x);
+    let dout = random_array(out.dim(), SEED + 1);
+    let dx = layer.backward(&dout);
+
+    let numeric = numerical_gradient(|x| (make().forward(x) * &dout).sum(), x);
+    assert_close(name, "dx", &dx, &numeric);
+}
+
+/// 图像层版本的 `check_input_grad`：通过 `forward_tensor` / `backward_tensor` 传四维数据，
+/// 数值微分在展平的输入上进行
+fn check_image_input_grad<L: Layer>(name: &str, make: impl Fn() -> L, x: &Array4<f64>) {
+    let shape = x.dim();
+    let (n, c, h, w) = shape;
+    let mut layer = make();
+    let out = layer.forward_tensor(&Tensor::from(x.clone())).into_img();
+    let dout = random_images(out.dim(), SEED + 1);
+    let dx = layer
+        .backward_tensor(&Tensor::from(dout.clone()))
+        .into_img()
+        .into_shape_with_order((n, c * h * w))
+        .unwrap();
+
+    let flat = x.clone().into_shape_with_order((n, c * h * w)).unwrap();
+    let numeric = numerical_gradient(
+        |x| {
+            let x = x.clone().into_shape_with_order(shape).unwrap();
+            (make().forward_tensor(&Tensor::from(x)).into_img() * &dout).sum()
+        },
+        &flat,
+    );
+    assert_close(name, "dx", &dx, &numeric);
+}
+
+#[test]
+fn gradient_check_affine() {
+    let w = random_array((4, 3), SEED);
+    let b = random_array((1, 3), SEED + 2);
+    let x = random_array((5, 4), SEED + 3);
+    let make = |w: &Array2<f64>, b: &Array2<f64>| Affine::new(w.clone(), b.clone());
+
+    check_input_grad("Affine", || make(&w, &b), &x);
+
+    let mut layer = make(&w, &b);
+    let out = layer.forward(&x);
+    let dout = random_array(out.dim(), SEED + 1);
+    layer.backward(&dout);
+
+    let objective = |layer: &mut Affine| (layer.forward(&x) * &dout).sum();
+    let dw = numerical_gradient(|w| objective(&mut make(w, &b)), &w);
+    let db = numerical_gradient(|b| objective(&mut make(&w, b)), &b);
+    assert_close("Affine", "dW", &layer.w.grad, &dw);
+    assert_close("Affine", "db", &layer.b.grad, &db);
+}
+
+#[test]
+fn gradient_check_relu() {
+    let x = random_array((5, 4), SEED);
+    check_input_grad("ReluLayer", ReluLayer::new, &x);
+}
+
+#[test]
+fn gradient_check_sigmoid() {
+    let x = random_array((5, 4), SEED);
+    check_input_grad("SigmoidLayer", SigmoidLayer::new, &x);
+}
+
+#[test]
+fn gradient_check_dropout() {
+    // 训练模式（默认）下检查；同一个种子每次生成相同的掩码，数值微分的每次前向传播都丢弃同样的元素
+    let x = random_array((5, 4), SEED);
+    let make = || Dropout::new(0.5, SEED);
+    let kept = make().forward(&x).iter().filter(|&&v| v != 0.0).count();
+    assert!(kept > 0 && kept < x.len(), "mask should drop some elements");
+
+    check_input_grad("Dropout", make, &x);
+}
+
+#[test]
+fn gradient_check_batch_norm() {
+    let x = random_array((5, 4), SEED);
+    let gamma = random_array((1, 4), SEED + 2);
+    let beta = random_array((1, 4), SEED + 3);
+    let make = |gamma: &Array2<f64>, beta: &Array2<f64>| {
+        let mut bn = BatchNorm::new(4);
+        bn.gamma.value = gamma.clone();
+        bn.beta.value = beta.clone();
+        bn
+    };
+
+    check_input_grad("BatchNorm", || make(&gamma, &beta), &x);
+
+    let mut layer = make(&gamma, &beta);
+    let out = layer.forward(&x);
+    let dout = random_array(out.dim(), SEED + 1);
+    layer.backward(&dout);
+
+    let objective = |layer: &mut BatchNorm| (layer.forward(&x) * &dout).sum();
+    let dgamma = numerical_gradient(|g| objective(&mut make(g, &beta)), &gamma);
+    let dbeta = numerical_gradient(|b| objective(&mut make(&gamma, b)), &beta);
+    assert_close("BatchNorm", "dgamma", &layer.gamma.grad, &dgamma);
+    assert_close("BatchNorm", "dbeta", &layer.beta.grad, &dbeta);
+
+    // 推理模式下均值和方差是常数
+    let mut eval = make(&gamma, &beta);
+    eval.forward(&x);
+    eval.training = false;
+    eval.forward(&x);
+    let dx = eval.backward(&dout);
+    let numeric = numerical_gradient(
+        |x| {
+            let mut probe = make(&gamma, &beta);
+            probe.running_mean = eval.running_mean.clone();
+            probe.running_var = eval.running_var.clone();
+            probe.training = false;
+            (probe.forward(x) * &dout).sum()
+        },
+        &x,
+    );
+    assert_close("BatchNorm (inference)", "dx", &dx, &numeric);
+}
+
+#[test]
+fn gradient_check_convolution() {
+    let filter_shape = (2, 2, 3, 3);
+    let w = random_array((2 * 3 * 3, 2), SEED);
+    let b = random_array((1, 2), SEED + 2);
+    let x = random_images((2, 2, 5, 5), SEED + 3);
+    let make = |w: &Array2<f64>, b: &Array2<f64>| {
+        Convolution::from_col_weights(w.clone(), b.clone(), filter_shape, 2, 1)
+    };
+
+    check_image_input_grad("Convolution", || make(&w, &b), &x);
+
+    let mut layer = make(&w, &b);
+    let out = layer.forward_tensor(&Tensor::from(x.clone())).into_img();
+    let dout = random_images(out.dim(), SEED + 1);
+    layer.backward_tensor(&Tensor::from(dout.clone()));
+
+    let objective = |layer: &mut Convolution| {
+        (layer.forward_tensor(&Tensor::from(x.clone())).into_img() * &dout).sum()
+    };
+    let dw = numerical_gradient(|w| objective(&mut make(w, &b)), &w);
+    let db = numerical_gradient(|b| objective(&mut make(&w, b)), &b);
+    assert_close("Convolution", "dW", &layer.w.grad, &dw);
+    assert_close("Convolution", "db", &layer.b.grad, &db);
+}
+
+#[test]
+fn gradient_check_pooling() {
+    let x = random_images((2, 2, 4, 4), SEED);
+    check_image_input_grad("Pooling (max)", || Pooling::new(2, 2, 2), &x);
+    check_image_input_grad(
+        "Pooling (average, padded)",
+        || {
+            Pooling::new(3, 3, 2)
+                .with_mode(PoolMode::Average)
+                .with_pad(1)
+        },
+        &x,
+    );
+}